@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+/// Raise above geosynchronous altitude of the standard GEO graveyard orbit in km
+/// (IADC: 235 km plus an allowance for solar radiation pressure)
+pub const GEO_GRAVEYARD_RAISE: f64 = 300.0;
+
+/// What a satellite does with itself when its mission ends
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum DisposalStrategy {
+    /// Lower perigee to `perigee_altitude` km so drag brings the satellite down
+    Deorbit { perigee_altitude: f64 },
+    /// Raise the orbit by `raise` km into a circular graveyard above the operational region
+    Graveyard { raise: f64 },
+    /// Switch off and stay where it is
+    Abandon,
+}
+
+impl DisposalStrategy {
+    /// Standard graveyard orbit for geostationary satellites
+    pub fn geo_graveyard() -> Self {
+        DisposalStrategy::Graveyard { raise: GEO_GRAVEYARD_RAISE }
+    }
+}
+
+/// How a mission ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissionOutcome {
+    /// Low enough to decay within the compliance lifetime without maneuvering
+    NaturalDecay,
+    /// Disposal burns completed and the satellite was passivated
+    Disposed,
+    /// The satellite died before or during disposal
+    DisposalFailed,
+    /// The operator chose to leave it in place
+    Abandoned,
+}
+
+impl MissionOutcome {
+    /// Whether the outcome meets post-mission disposal guidelines
+    pub fn is_compliant(&self) -> bool {
+        matches!(self, MissionOutcome::NaturalDecay | MissionOutcome::Disposed)
+    }
+}
+
+/// Stage of a satellite's life
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissionPhase {
+    /// Between launch and operations, climbing to the operational orbit if inserted below it
+    OrbitRaising,
+    Operational,
+    /// Disposal burns are planned or under way
+    Disposing,
+    Ended(MissionOutcome),
+}
+
+/// Mission lifetime and end-of-life plan of a satellite
+#[derive(Component, Clone, Debug)]
+pub struct Mission {
+    /// Simulation time in seconds operations started
+    pub start: f64,
+    /// Planned mission length in seconds
+    pub lifetime: f64,
+    pub disposal: DisposalStrategy,
+    pub phase: MissionPhase,
+}
+
+impl Mission {
+    pub fn new(start: f64, lifetime: f64, disposal: DisposalStrategy) -> Self {
+        Self {
+            start,
+            lifetime,
+            disposal,
+            phase: MissionPhase::Operational,
+        }
+    }
+
+    /// Start in orbit raising; the mission clock restarts when the satellite reaches its orbit
+    pub fn with_orbit_raising(mut self) -> Self {
+        self.phase = MissionPhase::OrbitRaising;
+        self
+    }
+
+    /// Simulation time in seconds the mission ends
+    pub fn end_of_life(&self) -> f64 {
+        self.start + self.lifetime
+    }
+
+    pub fn is_operational(&self) -> bool {
+        self.phase == MissionPhase::Operational
+    }
+
+    /// The outcome, once the mission has ended
+    pub fn outcome(&self) -> Option<MissionOutcome> {
+        match self.phase {
+            MissionPhase::Ended(outcome) => Some(outcome),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::components::OrbitalState;
+use crate::resources::Constants;
+use crate::utils::analytical::AnalyticalPropagator;
+use crate::utils::atmosphere::STANDARD_GRAVITY;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::frames::rtn_to_inertial;
+use crate::utils::gravity::GravityModel;
+use crate::utils::units::*;
+
+/// Specific impulse in seconds of hydrazine monopropellant thrusters, the default propulsion
+/// of spacecraft maneuvering in the simulation
+pub const HYDRAZINE_ISP: f64 = 220.0;
+
+/// Fraction of its mass an operational spacecraft carries as propellant unless told otherwise
+pub const PROPELLANT_FRACTION: f64 = 0.1;
+
+/// Frame a burn's delta-V components are given in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BurnFrame {
+    /// Simulation inertial axes
+    Inertial,
+    /// Radial, transverse (along-track) and orbit-normal axes of the object's current state
+    Rtn,
+}
+
+/// A single planned velocity change
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Burn {
+    /// Simulation time the burn starts, in seconds
+    pub time: f64,
+    /// Velocity change in km/s, in `frame` components
+    pub delta_v: DVec3,
+    pub frame: BurnFrame,
+    /// Specific impulse in seconds
+    pub isp: f64,
+    /// Thrust in N for a finite burn, `None` for an impulsive one
+    pub thrust: Option<f64>,
+}
+
+impl Burn {
+    pub fn impulsive(time: f64, delta_v: DVec3, frame: BurnFrame, isp: f64) -> Self {
+        Self { time, delta_v, frame, isp, thrust: None }
+    }
+
+    pub fn finite(time: f64, delta_v: DVec3, frame: BurnFrame, isp: f64, thrust: f64) -> Self {
+        Self { time, delta_v, frame, isp, thrust: Some(thrust) }
+    }
+
+    /// Exhaust velocity in km/s
+    pub fn exhaust_velocity(&self) -> f64 {
+        self.isp * STANDARD_GRAVITY / METERS_PER_KM
+    }
+
+    /// Propellant to deliver the whole burn starting from `mass` (rocket equation)
+    pub fn propellant_mass(&self, mass: Kg) -> Kg {
+        mass * (1.0 - (-self.delta_v.length() / self.exhaust_velocity()).exp())
+    }
+
+    /// Burn duration in seconds starting from `mass`, zero for impulsive burns
+    pub fn duration(&self, mass: Kg) -> f64 {
+        match self.thrust {
+            Some(thrust) => self.propellant_mass(mass).0 * self.exhaust_velocity() * METERS_PER_KM / thrust,
+            None => 0.0,
+        }
+    }
+
+    /// Unit thrust direction in inertial axes for the given state
+    fn direction(&self, position: DVec3, velocity: DVec3) -> DVec3 {
+        let direction = self.delta_v.normalize_or_zero();
+        match self.frame {
+            BurnFrame::Inertial => direction,
+            BurnFrame::Rtn => rtn_to_inertial(position, velocity, direction),
+        }
+    }
+}
+
+/// Propellant to deliver `burns` one after another starting from `mass`
+pub fn propellant_for(burns: &[Burn], mass: Kg) -> Kg {
+    burns.iter().fold(Kg(0.0), |used, burn| used + burn.propellant_mass(mass - used))
+}
+
+/// Timed burns for one object, executed by the physics stage at sub-step boundaries
+///
+/// Impulsive burns fire whole at the first sub-step boundary at or after their start time.
+/// Finite burns fire one pulse per sub-step, each pulse burning the propellant the engine
+/// uses in one sub-step, until the planned delta-V is delivered.
+#[derive(Component, Clone, Debug)]
+pub struct ManeuverPlan {
+    /// Burns not yet started, earliest first
+    burns: VecDeque<Burn>,
+    /// Finite burn in progress and the delta-V it still has to deliver in km/s
+    active: Option<(Burn, f64)>,
+    /// Simulation time of the active burn's next pulse
+    next_pulse: f64,
+    /// Propellant left in kg
+    pub propellant: f64,
+    /// Total delta-V delivered in km/s
+    pub delta_v_used: f64,
+    /// Total propellant burned in kg
+    pub propellant_used: f64,
+    /// Burns finished, including those cut short by running out of propellant
+    pub burns_completed: u32,
+}
+
+impl Default for ManeuverPlan {
+    /// An empty plan with unlimited propellant
+    fn default() -> Self {
+        Self::new(f64::INFINITY)
+    }
+}
+
+impl ManeuverPlan {
+    /// An empty plan with `propellant` kg on board
+    pub fn new(propellant: f64) -> Self {
+        Self {
+            burns: VecDeque::new(),
+            active: None,
+            next_pulse: 0.0,
+            propellant,
+            delta_v_used: 0.0,
+            propellant_used: 0.0,
+            burns_completed: 0,
+        }
+    }
+
+    /// An empty plan carrying `PROPELLANT_FRACTION` of a spacecraft's `mass` as propellant
+    pub fn for_spacecraft(mass: Kg) -> Self {
+        Self::new(PROPELLANT_FRACTION * mass.0)
+    }
+
+    /// Add a burn, keeping the plan in start-time order
+    pub fn schedule(&mut self, burn: Burn) {
+        let index = self.burns.partition_point(|b| b.time <= burn.time);
+        self.burns.insert(index, burn);
+    }
+
+    /// Burns not yet started, earliest first
+    pub fn pending(&self) -> impl Iterator<Item = &Burn> {
+        self.burns.iter()
+    }
+
+    /// Whether a finite burn is under way
+    pub fn is_burning(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Whether nothing is scheduled or under way
+    pub fn is_idle(&self) -> bool {
+        self.active.is_none() && self.burns.is_empty()
+    }
+
+    /// Drop every burn not yet under way
+    pub fn cancel_pending(&mut self) {
+        self.burns.clear();
+    }
+
+    /// Simulation time the plan next needs to fire
+    pub fn next_event(&self) -> Option<f64> {
+        match self.active {
+            Some(_) => Some(self.next_pulse),
+            None => self.burns.front().map(|b| b.time),
+        }
+    }
+
+    /// Fire the next event at `time`, which the caller has found due: a whole impulsive burn or
+    /// one finite-burn pulse of `pulse` seconds. Returns the inertial velocity change in km/s
+    /// and reduces `mass` by the propellant burned.
+    pub fn fire(&mut self, time: f64, position: DVec3, velocity: DVec3, mass: &mut Kg, pulse: f64) -> DVec3 {
+        let (burn, remaining) = match self.active.take() {
+            Some(active) => active,
+            None => match self.burns.pop_front() {
+                Some(burn) => (burn, burn.delta_v.length()),
+                None => return DVec3::ZERO,
+            },
+        };
+        let exhaust_velocity = burn.exhaust_velocity();
+
+        // Propellant for the rest of the burn, or for one pulse of a finite burn
+        let wanted = mass.0 * (1.0 - (-remaining / exhaust_velocity).exp());
+        let propellant = match burn.thrust {
+            Some(thrust) => wanted.min(thrust / (exhaust_velocity * METERS_PER_KM) * pulse),
+            None => wanted,
+        }
+        .min(self.propellant)
+        .min(mass.0)
+        .max(0.0);
+        let delta_v = if propellant < mass.0 {
+            exhaust_velocity * (mass.0 / (mass.0 - propellant)).ln()
+        } else {
+            remaining
+        }
+        .min(remaining);
+
+        *mass -= Kg(propellant);
+        self.propellant -= propellant;
+        self.propellant_used += propellant;
+        self.delta_v_used += delta_v;
+
+        let remaining = remaining - delta_v;
+        let finished = burn.thrust.is_none() || remaining <= 1e-12 || self.propellant <= 0.0;
+        if finished {
+            self.burns_completed += 1;
+        } else {
+            self.active = Some((burn, remaining));
+            self.next_pulse = time + pulse;
+        }
+
+        delta_v * burn.direction(position, velocity)
+    }
+}
+
+/// Box a satellite's station-keeping holds it in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StationKeepingBox {
+    /// Geostationary slot: east-west about a longitude, north-south under an inclination
+    Geostationary {
+        /// Slot longitude in degrees east
+        longitude: f64,
+        /// Half-width of the longitude deadband in degrees
+        longitude_tolerance: f64,
+        /// Largest inclination allowed in degrees
+        max_inclination: f64,
+    },
+    /// Constellation slot: mean altitude and position along a reference circular orbit
+    Constellation {
+        /// Mean semi-major axis of the slot in km
+        semi_major_axis: f64,
+        /// Slot argument of latitude at `epoch` in radians
+        latitude_argument: f64,
+        /// Secular drift rate of the slot's argument of latitude in rad/s
+        latitude_rate: f64,
+        /// Simulation time in seconds the slot is defined at
+        epoch: f64,
+        /// Half-width of the altitude deadband in km
+        altitude_tolerance: f64,
+        /// Half-width of the along-track deadband in km
+        along_track_tolerance: f64,
+    },
+}
+
+/// Keeps an active satellite in its box with periodic burns through its `ManeuverPlan`
+///
+/// Only satellites with a `Mission` are kept. Station-keeping stops at the end of the mission,
+/// which comes early once the propellant left is only what disposal needs.
+#[derive(Component, Clone, Debug)]
+pub struct StationKeeping {
+    pub station: StationKeepingBox,
+    /// Station-keeping burns planned so far
+    pub maneuvers: u32,
+    /// Station-keeping delta-V planned so far in km/s
+    pub delta_v: f64,
+}
+
+impl StationKeeping {
+    pub fn new(station: StationKeepingBox) -> Self {
+        Self {
+            station,
+            maneuvers: 0,
+            delta_v: 0.0,
+        }
+    }
+
+    /// Hold a geostationary slot at `longitude` degrees east
+    pub fn geostationary(longitude: f64, longitude_tolerance: f64, max_inclination: f64) -> Self {
+        Self::new(StationKeepingBox::Geostationary { longitude, longitude_tolerance, max_inclination })
+    }
+
+    /// Hold the constellation slot the satellite occupies at simulation time `now`
+    pub fn constellation_slot(
+        orbital_state: &OrbitalState,
+        constants: &Constants,
+        now: f64,
+        altitude_tolerance: f64,
+        along_track_tolerance: f64,
+    ) -> Self {
+        let propagator = AnalyticalPropagator::new(constants, GravityModel::J2, None);
+        let semi_major_axis = propagator.mean_semi_major_axis(orbital_state.position, orbital_state.velocity);
+        let elements = KeplerianElements::from_state(orbital_state.position, orbital_state.velocity, propagator.mu);
+        Self::new(StationKeepingBox::Constellation {
+            semi_major_axis,
+            latitude_argument: elements.argument_of_latitude(),
+            latitude_rate: propagator.latitude_argument_rate(semi_major_axis, elements.inclination),
+            epoch: now,
+            altitude_tolerance,
+            along_track_tolerance,
+        })
+    }
+}
@@ -1,8 +1,12 @@
+pub mod lifecycle;
+pub mod maneuver;
 pub mod objects;
 pub mod orbital;
 pub mod physics;
 pub mod trails;
 
+pub use lifecycle::*;
+pub use maneuver::*;
 pub use objects::*;
 pub use orbital::*;
 pub use physics::*;
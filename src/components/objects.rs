@@ -1,3 +1,4 @@
+use bevy::math::DVec3;
 use bevy::prelude::*;
 
 /// Component for active satellites
@@ -49,10 +50,51 @@ impl Debris {
     }
 }
 
+/// Kind of orbiting object, used by the breakup model and fragmentation rates
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectClass {
+    /// Payloads and other spacecraft
+    Spacecraft,
+    /// Spent upper stages
+    RocketBody,
+    /// Fragments and other debris
+    Debris,
+}
+
+impl ObjectClass {
+    /// Classify a catalog object from its name using the SATCAT "R/B" and "DEB" conventions
+    pub fn from_catalog_name(name: &str) -> Self {
+        let name_upper = name.to_uppercase();
+        if name_upper.contains("R/B") {
+            Self::RocketBody
+        } else if name_upper.contains("DEB") {
+            Self::Debris
+        } else {
+            Self::Spacecraft
+        }
+    }
+}
+
+/// Marker component for passivated objects: vented propellant and discharged batteries
+/// mean the object can no longer explode
+#[derive(Component)]
+pub struct Passivated;
+
+/// Component scheduling an explosion at a given simulation time
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ScheduledExplosion {
+    /// Simulation time of the explosion in seconds
+    pub time: f64,
+}
+
 /// Marker component for objects that should be rendered as satellites
 #[derive(Component)]
 pub struct RenderAsSatellite;
 
+/// Marker component for the textured Earth mesh, turned to match Earth orientation
+#[derive(Component)]
+pub struct EarthBody;
+
 /// Marker component for objects that should be rendered as debris
 #[derive(Component)]
 pub struct RenderAsDebris;
@@ -163,4 +205,4 @@ pub struct ScheduledForDeletion;
 
 /// Previous position for incremental octree updates
 #[derive(Component)]
-pub struct PreviousPosition(pub Vec3);
+pub struct PreviousPosition(pub DVec3);
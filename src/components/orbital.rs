@@ -1,18 +1,24 @@
+use bevy::math::DVec3;
 use bevy::prelude::*;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::units::*;
 
 /// Core orbital state component containing position and velocity vectors
+///
+/// State is double precision: f32 only resolves ~0.5 m at LEO and ~4 m at GEO, and that
+/// rounding compounds every step. Rendering converts with [`OrbitalState::render_position`].
 #[derive(Component)]
 pub struct OrbitalState {
     /// Position vector in kilometers from Earth center
-    pub position: Vec3,
+    pub position: DVec3,
     /// Velocity vector in km/s
-    pub velocity: Vec3,
-    /// Object mass in kilograms
-    pub mass: f64,
+    pub velocity: DVec3,
+    /// Object mass
+    pub mass: Kg,
 }
 
 impl OrbitalState {
-    pub fn new(position: Vec3, velocity: Vec3, mass: f64) -> Self {
+    pub fn new(position: DVec3, velocity: DVec3, mass: Kg) -> Self {
         Self {
             position,
             velocity,
@@ -20,33 +26,59 @@ impl OrbitalState {
         }
     }
 
-    /// Calculate distance from Earth center in km
-    pub fn altitude(&self) -> f64 {
-        self.position.length() as f64
+    /// Distance from Earth center
+    pub fn radius(&self) -> Km {
+        Km::length(self.position)
     }
 
     /// Calculate orbital speed in km/s
     pub fn speed(&self) -> f64 {
-        self.velocity.length() as f64
+        self.velocity.length()
+    }
+
+    /// Single-precision position in km for rendering and spatial indexing
+    pub fn render_position(&self) -> Vec3 {
+        self.position.as_vec3()
     }
 
     /// Calculate kinetic energy in Joules
     pub fn kinetic_energy(&self) -> f64 {
-        0.5 * self.mass * (self.speed() * 1000.0).powi(2) // Convert km/s to m/s
+        let speed = MetersPerSec::from(KmPerSec::length(self.velocity));
+        0.5 * self.mass.0 * speed.0 * speed.0
     }
 
-    /// Calculate gravitational potential energy in Joules
-    pub fn potential_energy(&self, gm: f64) -> f64 {
-        let r_meters = self.altitude() * 1000.0; // Convert km to meters
-        -gm * self.mass / r_meters
+    /// Calculate gravitational potential energy in Joules about a body of gravitational parameter `gm`
+    pub fn potential_energy(&self, gm: Km3PerSec2) -> f64 {
+        gm.potential_energy(self.mass, self.radius())
     }
 
     /// Calculate total orbital energy in Joules
-    pub fn total_energy(&self, gm: f64) -> f64 {
+    pub fn total_energy(&self, gm: Km3PerSec2) -> f64 {
         self.kinetic_energy() + self.potential_energy(gm)
     }
 }
 
+/// Keplerian elements derived from an object's `OrbitalState`
+///
+/// Optional: only objects that carry it are converted, and `orbital_elements_system` keeps it current.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct OrbitalElements(pub KeplerianElements);
+
+impl OrbitalElements {
+    /// `mu` is the gravitational parameter in km³/s²
+    pub fn from_state(orbital_state: &OrbitalState, mu: f64) -> Self {
+        Self(KeplerianElements::from_state(orbital_state.position, orbital_state.velocity, mu))
+    }
+}
+
+/// Mean elements anchored at a simulation time, advanced by the analytical backend
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MeanElements {
+    pub elements: KeplerianElements,
+    /// Simulation time in seconds the elements refer to
+    pub epoch: f64,
+}
+
 /// Component to store the original TLE data for reference
 #[derive(Component)]
 pub struct TleData {
@@ -54,6 +86,7 @@ pub struct TleData {
     pub name: String,
     pub line1: String,
     pub line2: String,
+    /// TLE epoch as a UTC Julian date
     pub epoch: f64,
 }
 
@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use crate::utils::units::*;
+use crate::utils::forces::ForceCoefficients;
+use crate::utils::sgp4_wrapper::BSTAR_REFERENCE_DENSITY;
 
 /// Physics properties for objects that can experience forces
 #[derive(Component)]
@@ -7,12 +10,12 @@ pub struct PhysicsObject {
     pub cross_section: f64,
     /// Drag coefficient (dimensionless)
     pub drag_coefficient: f64,
-    /// Radius for collision detection in meters
-    pub collision_radius: f64,
+    /// Radius for collision detection (positions are in km, so compare via `Km::from`)
+    pub collision_radius: Meters,
 }
 
 impl PhysicsObject {
-    pub fn new(cross_section: f64, drag_coefficient: f64, collision_radius: f64) -> Self {
+    pub fn new(cross_section: f64, drag_coefficient: f64, collision_radius: Meters) -> Self {
         Self {
             cross_section,
             drag_coefficient,
@@ -21,27 +24,77 @@ impl PhysicsObject {
     }
 
     /// Create physics object for a typical satellite
-    pub fn satellite(mass_kg: f64) -> Self {
+    pub fn satellite(mass: Kg) -> Self {
         // Rough estimates based on satellite mass
-        let radius = (mass_kg / 1000.0).powf(1.0/3.0); // Crude mass-to-size relationship
+        let radius = (mass.0 / 1000.0).powf(1.0/3.0); // Crude mass-to-size relationship, in m
         Self::new(
             radius * radius * std::f64::consts::PI, // Cross section
             2.2, // Typical drag coefficient for satellites
-            radius, // Collision radius
+            Meters(radius), // Collision radius
         )
     }
 
     /// Create physics object for debris
-    pub fn debris(mass_kg: f64) -> Self {
-        let radius = (mass_kg / 2000.0).powf(1.0/3.0); // Debris typically less dense
+    pub fn debris(mass: Kg) -> Self {
+        let radius = (mass.0 / 2000.0).powf(1.0/3.0); // Debris typically less dense
         Self::new(
             radius * radius * std::f64::consts::PI,
             2.5, // Higher drag coefficient for irregular debris
-            radius,
+            Meters(radius),
         )
     }
+
+    /// Create physics object for a breakup fragment of known area (m²) and characteristic length (m)
+    pub fn fragment(area: f64, characteristic_length: f64) -> Self {
+        Self::new(
+            area,
+            2.2, // Flat-plate tumbling value assumed by the breakup model's A/M ratios
+            Meters(characteristic_length / 2.0),
+        )
+    }
+
+    /// Create physics object whose drag matches a TLE BSTAR term (1/earth radii)
+    /// Falls back to the mass-based satellite estimate when BSTAR is not positive
+    pub fn from_bstar(bstar: f64, mass: Kg) -> Self {
+        let mut object = Self::satellite(mass);
+        if bstar > 0.0 {
+            // B* = ρ₀·Cd·A / (2m), so Cd·A/m = 2·B*/ρ₀
+            let ballistic_coefficient = 2.0 * bstar / BSTAR_REFERENCE_DENSITY;
+            object.cross_section = ballistic_coefficient * mass.0 / object.drag_coefficient;
+        }
+        object
+    }
+
+    /// Ballistic coefficient Cd·A/m in m²/kg used by the drag model
+    pub fn ballistic_coefficient(&self, mass: Kg) -> f64 {
+        if mass.0 <= 0.0 {
+            return 0.0;
+        }
+        self.drag_coefficient * self.cross_section / mass.0
+    }
+
+    /// Area-to-mass ratio A/m in m²/kg used by solar radiation pressure
+    pub fn area_to_mass(&self, mass: Kg) -> f64 {
+        if mass.0 <= 0.0 {
+            return 0.0;
+        }
+        self.cross_section / mass.0
+    }
+
+    /// Drag and radiation pressure coefficients for the force model
+    pub fn force_coefficients(&self, mass: Kg) -> ForceCoefficients {
+        ForceCoefficients {
+            ballistic_coefficient: self.ballistic_coefficient(mass),
+            area_to_mass: self.area_to_mass(mass),
+        }
+    }
 }
 
+/// Step size the adaptive integrator proposed for an object's next interval, in seconds
+/// Carried between frames so each object keeps the step its orbit needs
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AdaptiveStep(pub f64);
+
 /// Component to track collision events
 #[derive(Component)]
 pub struct CollisionEvent {
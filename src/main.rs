@@ -22,10 +22,22 @@ fn main() {
         .init_resource::<TleDataCache>()
         .init_resource::<SpatialOctree>()
         .init_resource::<CollisionPairs>()
-        .init_resource::<OptimizedPhysicsData>()
         .init_resource::<StressTestConfig>()
         .init_resource::<DebrisInjectionConfig>()
-        .init_resource::<OrbitalDecayConfig>()
+        .init_resource::<ReentryConfig>()
+        .init_resource::<ReentryLog>()
+        .add_event::<ReentryEvent>()
+        .init_resource::<ExplosionConfig>()
+        .add_event::<ExplosionEvent>()
+        .init_resource::<AvoidanceConfig>()
+        .init_resource::<AvoidanceLog>()
+        .init_resource::<StationKeepingConfig>()
+        .init_resource::<StationKeepingStatistics>()
+        .init_resource::<LifecycleConfig>()
+        .init_resource::<LifecycleStatistics>()
+        .init_resource::<LaunchConfig>()
+        .init_resource::<LaunchLog>()
+        .init_resource::<CatalogNumbers>()
         .init_resource::<SatelliteSelection>()
         .init_resource::<systems::render_mode::RenderMode>()
         .init_resource::<systems::gpu_physics::GpuPhysicsState>()
@@ -33,6 +45,8 @@ fn main() {
         .init_resource::<systems::ui::FpsHistory>()
         .init_resource::<SimulationRecorder>()
         .init_resource::<utils::integrators::IntegratorConfig>()
+        .init_resource::<utils::breakup::BreakupConfig>()
+        .init_resource::<utils::breakup::BreakupStatistics>()
         .insert_resource(systems::profiling::SystemProfiler::new(100))
         .add_plugins(systems::gpu_instancing::GpuInstancingPlugin)
         .add_plugins(systems::simple_gpu_render::SimpleGpuRenderPlugin)
@@ -57,9 +71,6 @@ fn main() {
         ))
         .add_systems(Update, (
             systems::gpu_physics::gpu_physics_toggle_system,
-            prepare_optimized_physics_system,
-            optimized_physics_system,
-            apply_optimized_physics_system,
             optimized_physics_monitor_system,
             systems::gpu_physics::gpu_physics_readback_system,
         ))
@@ -67,6 +78,7 @@ fn main() {
             update_spatial_octree_system,
             collision_detection_system,
             debris_generation_system,
+            explosion_system,
         ))
         .add_systems(Update, (
             satellite_rendering_system,
@@ -75,7 +87,9 @@ fn main() {
             cleanup_expired_flash_system,
             update_debris_effects_system,
             update_positions_system,
+            earth_orientation_system,
             energy_analytics_system,
+            orbital_elements_system,
         ))
         .add_systems(Update, (
             debug_orbital_system,
@@ -90,7 +104,11 @@ fn main() {
         ))
         .add_systems(Update, (
             random_debris_injection_system,
-            orbital_decay_system,
+            reentry_detection_system,
+            collision_avoidance_system,
+            station_keeping_system,
+            lifecycle_system,
+            launch_system,
         ))
         .add_systems(Update, (
             satellite_selection_system,
@@ -143,6 +161,7 @@ fn setup_scene(
         })),
         Mesh3d(meshes.add(Sphere::new(6.371).mesh().uv(32, 18))),
         Transform::default(),
+        components::EarthBody,
     ));
     
     commands.spawn((
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+/// Catalog numbers handed to objects created during the simulation start here, above the
+/// five-digit catalog
+pub const GENERATED_CATALOG_START: u32 = 100_000;
+
+/// Resource handing out catalog numbers to launched and generated satellites from one range,
+/// so no two share a number however long the simulation runs
+#[derive(Resource, Debug)]
+pub struct CatalogNumbers {
+    next: u32,
+}
+
+impl Default for CatalogNumbers {
+    fn default() -> Self {
+        Self { next: GENERATED_CATALOG_START }
+    }
+}
+
+impl CatalogNumbers {
+    /// Reserve `count` consecutive catalog numbers, returning the first
+    pub fn allocate(&mut self, count: u32) -> u32 {
+        let first = self.next;
+        self.next += count;
+        first
+    }
+
+    /// Catalog number the next allocation starts at
+    pub fn next(&self) -> u32 {
+        self.next
+    }
+}
@@ -1,46 +1,71 @@
 use bevy::prelude::*;
+use crate::utils::frames::EARTH_ROTATION_RATE;
+use crate::utils::units::*;
 
 /// Physical and mathematical constants for the simulation
+///
+/// Lengths are in km and gravitational parameters in km³/s², the units of simulation
+/// positions, so they combine without conversion.
 #[derive(Resource)]
 pub struct Constants {
-    /// Earth's mass in kg
-    pub earth_mass: f64,
-    /// Earth's gravitational parameter GM in m³/s²
-    pub gravitational_parameter: f64,
-    /// Earth's radius in km
-    pub earth_radius: f64,
-    /// Earth's radius in meters
-    pub earth_radius_m: f64,
+    /// Earth's mass
+    pub earth_mass: Kg,
+    /// Earth's gravitational parameter GM
+    pub gravitational_parameter: Km3PerSec2,
+    /// Earth's mean radius
+    pub earth_radius: Km,
+    /// Earth's equatorial radius (reference radius for the zonal harmonics)
+    pub earth_equatorial_radius: Km,
+    /// Second zonal harmonic (oblateness), dimensionless
+    pub j2: f64,
+    /// Third zonal harmonic (pear shape), dimensionless
+    pub j3: f64,
+    /// Fourth zonal harmonic, dimensionless
+    pub j4: f64,
+    /// Earth's rotation rate in rad/s
+    pub earth_rotation_rate: f64,
+    /// Sun's gravitational parameter GM
+    pub sun_gravitational_parameter: Km3PerSec2,
+    /// Moon's gravitational parameter GM
+    pub moon_gravitational_parameter: Km3PerSec2,
+    /// Astronomical unit
+    pub astronomical_unit: Km,
+    /// Solar radiation pressure at 1 AU in N/m²
+    pub solar_radiation_pressure: f64,
 }
 
 impl Default for Constants {
     fn default() -> Self {
         Self {
-            earth_mass: 5.972e24,              // kg
-            gravitational_parameter: 3.986004418e14, // m³/s²
-            earth_radius: 6371.0,              // km
-            earth_radius_m: 6.371e6,           // m
+            earth_mass: Kg(5.972e24),
+            gravitational_parameter: Km3PerSec2(398600.4418),
+            earth_radius: Km(6371.0),
+            earth_equatorial_radius: Km(6378.137), // WGS-84
+            j2: 1.08262668e-3,                     // EGM96
+            j3: -2.53265648e-6,
+            j4: -1.61962159e-6,
+            earth_rotation_rate: EARTH_ROTATION_RATE,
+            sun_gravitational_parameter: Km3PerSec2(1.32712440018e11),
+            moon_gravitational_parameter: Km3PerSec2(4902.8),
+            astronomical_unit: Km(149597870.7),
+            solar_radiation_pressure: 4.56e-6, // N/m²
         }
     }
 }
 
 impl Constants {
-    /// Calculate gravitational acceleration at distance r (in km) from Earth center
-    pub fn gravity_acceleration(&self, r_km: f64) -> f64 {
-        let r_m = r_km * 1000.0; // Convert to meters
-        self.gravitational_parameter / (r_m * r_m)
+    /// Gravitational acceleration at distance `r` from Earth center
+    pub fn gravity_acceleration(&self, r: Km) -> MetersPerSec2 {
+        self.gravitational_parameter.acceleration(r)
     }
 
-    /// Calculate orbital velocity for circular orbit at altitude h (km above surface)
-    pub fn circular_velocity(&self, altitude_km: f64) -> f64 {
-        let r_km = self.earth_radius + altitude_km;
-        let r_m = r_km * 1000.0;
-        (self.gravitational_parameter / r_m).sqrt() / 1000.0 // Return in km/s
+    /// Orbital velocity of a circular orbit at `altitude` above the surface
+    pub fn circular_velocity(&self, altitude: Km) -> KmPerSec {
+        self.gravitational_parameter.circular_speed(self.earth_radius + altitude)
     }
 
-    /// Calculate escape velocity at distance r (km from center)
-    pub fn escape_velocity(&self, r_km: f64) -> f64 {
-        let r_m = r_km * 1000.0;
-        (2.0 * self.gravitational_parameter / r_m).sqrt() / 1000.0 // Return in km/s
+    /// Escape velocity at distance `r` from Earth center
+    pub fn escape_velocity(&self, r: Km) -> KmPerSec {
+        self.gravitational_parameter.escape_speed(r)
     }
-}
\ No newline at end of file
+}
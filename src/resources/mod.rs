@@ -1,7 +1,9 @@
+pub mod catalog;
 pub mod constants;
 pub mod simulation;
 pub mod recording;
 
+pub use catalog::*;
 pub use constants::*;
 pub use simulation::*;
 pub use recording::*;
\ No newline at end of file
@@ -36,7 +36,11 @@ pub struct DebrisEventSnapshot {
 /// A single frame of simulation state
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct SimulationFrame {
+    /// Simulation time in seconds since the simulation epoch
     pub timestamp: f64,
+    /// UTC time of the frame in ISO-8601
+    #[serde(default)]
+    pub utc: String,
     pub objects: Vec<ObjectSnapshot>,
     pub collisions: Vec<CollisionEventSnapshot>,
     pub debris_created: Vec<DebrisEventSnapshot>,
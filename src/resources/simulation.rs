@@ -1,43 +1,131 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use crate::utils::time::Epoch;
 
 /// Global simulation time and control
+///
+/// Elapsed simulated time collects in an accumulator and is drained in whole `timestep`s, so
+/// `current` only moves by the time the physics actually integrated.
+/// Simulation time 0 is the UTC instant `epoch`, so TLEs, recordings and the clock share one timeline.
 #[derive(Resource)]
 pub struct SimulationTime {
-    /// Current simulation time in seconds since epoch
+    /// Current simulation time in SI seconds since `epoch`
     pub current: f64,
+    /// Instant simulation time 0 corresponds to
+    pub epoch: Epoch,
+    /// Re-anchor `epoch` to the newest TLE epoch when a catalogue loads; cleared by an explicit epoch
+    pub epoch_from_catalog: bool,
     /// Time speed multiplier (1.0 = real time, 3600.0 = 1 hour per second)
     pub speed_multiplier: f64,
     /// Whether the simulation is paused
     pub paused: bool,
     /// Simulation timestep in seconds
     pub timestep: f64,
+    /// Simulated time elapsed but not yet integrated, in seconds
+    pub accumulator: f64,
+    /// Most physics sub-steps to run in one frame
+    pub max_substeps: u32,
+    /// Sub-steps taken this frame
+    pub frame_steps: u32,
+    /// Whether the last frame needed more sub-steps than the budget allows
+    pub behind: bool,
+    /// Total simulated time discarded because the physics fell behind, in seconds
+    pub dropped_time: f64,
 }
 
 impl Default for SimulationTime {
     fn default() -> Self {
         Self {
             current: 0.0,
+            epoch: Epoch::j2000(),
+            epoch_from_catalog: true,
             speed_multiplier: 3600.0, // Default to 1 hour per second
             paused: false,
             timestep: 1.0, // 1 second timesteps
+            accumulator: 0.0,
+            max_substeps: 1000,
+            frame_steps: 0,
+            behind: false,
+            dropped_time: 0.0,
         }
     }
 }
 
 impl SimulationTime {
+    /// Start the simulation at a fixed UTC epoch instead of the catalogue's
+    pub fn with_epoch(epoch: Epoch) -> Self {
+        Self { epoch, epoch_from_catalog: false, ..Default::default() }
+    }
+
+    /// Shift the epoch so the current simulation time falls on `now`
+    pub fn anchor_to(&mut self, now: Epoch) {
+        self.epoch = now.plus_seconds(-self.current);
+    }
+
+    /// The instant the simulation has reached
+    pub fn now(&self) -> Epoch {
+        self.epoch.plus_seconds(self.current)
+    }
+
+    /// UTC Julian date of the current simulation time
+    pub fn julian_day(&self) -> f64 {
+        self.now().utc_julian_day()
+    }
+
+    /// UTC Modified Julian Date of the current simulation time
+    pub fn mjd(&self) -> f64 {
+        self.now().mjd()
+    }
+
+    /// ISO-8601 UTC timestamp of the current simulation time
+    pub fn iso8601(&self) -> String {
+        self.now().to_iso8601()
+    }
+
+    /// Accumulate a frame's worth of simulated time
     pub fn advance(&mut self, delta_time: f32) {
         if !self.paused {
-            // Adaptive timestep: at high speeds, use smaller physics steps
-            let effective_dt = if self.speed_multiplier > 3600.0 {
-                (delta_time as f64 * self.speed_multiplier / 3600.0) * 60.0
-            } else {
-                delta_time as f64 * self.speed_multiplier
-            };
-            self.current += effective_dt;
+            self.accumulator += delta_time as f64 * self.speed_multiplier;
         }
     }
 
+    /// Drain whole timesteps from the accumulator, up to `max_substeps`, advancing `current`
+    /// Returns the number of sub-steps to integrate this frame. When more were due than the
+    /// budget allows the backlog is dropped, `behind` is set and `dropped_time` grows, so the
+    /// simulation runs slower than requested instead of falling further behind every frame.
+    pub fn consume_steps(&mut self) -> u32 {
+        self.consume_steps_up_to(self.max_substeps)
+    }
+
+    /// Drain whole timesteps from the accumulator with an explicit per-frame budget
+    /// Backends whose cost does not grow with the step count pass `u32::MAX`.
+    pub fn consume_steps_up_to(&mut self, budget: u32) -> u32 {
+        if self.paused || self.timestep <= 0.0 {
+            self.frame_steps = 0;
+            return 0;
+        }
+
+        let due = (self.accumulator / self.timestep).floor();
+        let steps = due.min(budget as f64);
+        self.accumulator -= steps * self.timestep;
+        self.current += steps * self.timestep;
+
+        self.behind = due > steps;
+        if self.behind {
+            let remainder = self.accumulator % self.timestep;
+            self.dropped_time += self.accumulator - remainder;
+            self.accumulator = remainder;
+        }
+
+        self.frame_steps = steps as u32;
+        self.frame_steps
+    }
+
+    /// Simulated seconds integrated this frame
+    pub fn frame_duration(&self) -> f64 {
+        self.frame_steps as f64 * self.timestep
+    }
+
     pub fn pause(&mut self) {
         self.paused = true;
     }
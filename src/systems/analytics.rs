@@ -19,7 +19,7 @@ pub fn energy_analytics_system(
     let mut total_objects = 0;
 
     for orbital_state in orbital_query.iter() {
-        let altitude = orbital_state.altitude() - constants.earth_radius;
+        let altitude = (orbital_state.radius() - constants.earth_radius).0;
         let energy = orbital_state.total_energy(constants.gravitational_parameter);
         
         analytics.add_energy_measurement(altitude, energy);
@@ -34,6 +34,20 @@ pub fn energy_analytics_system(
     analytics.total_energy = total_energy;
 }
 
+/// Objects whose derived elements are out of date
+type StaleElements = Or<(Changed<OrbitalState>, Added<OrbitalElements>)>;
+
+/// Keep derived orbital elements in step with the Cartesian state of objects that carry them
+pub fn orbital_elements_system(
+    mut elements_query: Query<(&OrbitalState, &mut OrbitalElements), StaleElements>,
+    constants: Res<Constants>,
+) {
+    let mu = constants.gravitational_parameter.0;
+    for (orbital_state, mut elements) in elements_query.iter_mut() {
+        *elements = OrbitalElements::from_state(orbital_state, mu);
+    }
+}
+
 /// Debug system to print analytics information
 pub fn debug_analytics_system(
     analytics: Res<EnergyAnalytics>,
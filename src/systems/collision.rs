@@ -2,6 +2,10 @@
 
 use crate::components::*;
 use crate::resources::*;
+use crate::utils::breakup::*;
+use crate::utils::units::*;
+use bevy::ecs::system::SystemParam;
+use bevy::math::DVec3;
 use bevy::prelude::*;
 
 /// Octree node for spatial partitioning
@@ -239,13 +243,13 @@ pub fn update_spatial_octree_system(
 ) {
     // Remove moved objects from old positions
     for (entity, orbital_state, prev_pos) in moved_query.iter() {
-        octree.root.remove(entity, prev_pos.0);
-        octree.root.insert(entity, orbital_state.position);
+        octree.root.remove(entity, prev_pos.0.as_vec3());
+        octree.root.insert(entity, orbital_state.render_position());
     }
 
     // Add new objects
     for (entity, orbital_state) in new_objects_query.iter() {
-        octree.root.insert(entity, orbital_state.position);
+        octree.root.insert(entity, orbital_state.render_position());
         commands
             .entity(entity)
             .insert(PreviousPosition(orbital_state.position));
@@ -282,11 +286,12 @@ pub fn collision_detection_system(
     for (entity, orbital_state, physics_object) in orbital_query.iter() {
         // Pre-allocate nearby objects vector
         let mut nearby_objects = Vec::with_capacity(32);
-        let search_radius = physics_object.collision_radius as f32 * 2.0;
+        // Collision radii are in metres while positions and the octree are in km
+        let search_radius = Km::from(physics_object.collision_radius * 2.0);
 
         octree
             .root
-            .query_sphere(orbital_state.position, search_radius, &mut nearby_objects);
+            .query_sphere(orbital_state.render_position(), search_radius.0 as f32, &mut nearby_objects);
 
         for &other_entity in &nearby_objects {
             if entity == other_entity {
@@ -306,9 +311,9 @@ pub fn collision_detection_system(
             checked_pairs.insert(pair);
 
             if let Ok((_, other_orbital, other_physics)) = orbital_query.get(other_entity) {
-                let distance = (orbital_state.position - other_orbital.position).length();
+                let distance = Km::between(orbital_state.position, other_orbital.position);
                 let combined_radius =
-                    (physics_object.collision_radius + other_physics.collision_radius) as f32;
+                    Km::from(physics_object.collision_radius + other_physics.collision_radius);
 
                 if distance <= combined_radius {
                     collision_pairs.pairs.push((entity, other_entity));
@@ -325,17 +330,26 @@ pub fn collision_detection_system(
     }
 }
 
+/// Components read from each object taking part in a collision
+type CollisionParticipant<'a> = (
+    Entity,
+    &'a OrbitalState,
+    &'a PhysicsObject,
+    Option<&'a Satellite>,
+    Option<&'a ObjectClass>,
+);
+
 /// Debris generation system with enhanced visual effects
 pub fn debris_generation_system(
     mut commands: Commands,
     collision_pairs: Res<CollisionPairs>,
-    orbital_query: Query<
-        (Entity, &OrbitalState, &PhysicsObject, Option<&Satellite>),
-        Without<ScheduledForDeletion>,
-    >,
-    mut debris_count: Local<u32>,
+    orbital_query: Query<CollisionParticipant<'_>, Without<ScheduledForDeletion>>,
+    mut breakup_resources: BreakupResources,
     time: Res<Time>,
 ) {
+    let breakup_config = breakup_resources.config();
+    let mut rng = rand::thread_rng();
+
     // Track entities that have already been processed this frame
     let mut processed_entities = std::collections::HashSet::new();
 
@@ -347,7 +361,7 @@ pub fn debris_generation_system(
         }
 
         // Check if entities are available and not scheduled for deletion
-        if let (Ok((_, orbital1, _physics1, sat1)), Ok((_, orbital2, _physics2, sat2))) =
+        if let (Ok((_, orbital1, physics1, sat1, class1)), Ok((_, orbital2, physics2, sat2, class2))) =
             (orbital_query.get(entity1), orbital_query.get(entity2))
         {
             // Mark entities as processed immediately
@@ -358,7 +372,7 @@ pub fn debris_generation_system(
             let collision_point = (orbital1.position + orbital2.position) / 2.0;
             let relative_velocity = orbital2.velocity - orbital1.velocity;
             let collision_energy =
-                0.5 * (orbital1.mass + orbital2.mass) as f32 * relative_velocity.length_squared();
+                (0.5 * (orbital1.mass + orbital2.mass).0 * relative_velocity.length_squared()) as f32;
 
             // Log collision event
             let obj1_name = sat1.as_ref().map(|s| s.name.as_str()).unwrap_or("Unknown");
@@ -375,38 +389,47 @@ pub fn debris_generation_system(
             //     Transform::from_translation(collision_point / 1000.0), // Scale for rendering
             // ));
 
-            // Generate debris based on collision energy
-            // NASA standard breakup model: more energy = more debris
-            let debris_pieces =
-                calculate_debris_count(collision_energy, orbital1.mass + orbital2.mass);
-
-            for _i in 0..debris_pieces {
-                *debris_count += 1;
-
-                // Generate debris with random velocity distribution around collision point
-                let debris_velocity = generate_debris_velocity(
-                    orbital1.velocity,
-                    orbital2.velocity,
-                    relative_velocity.length(),
-                );
-
-                // Create debris entity with enhanced visual properties
-                let debris_mass = (orbital1.mass + orbital2.mass) / debris_pieces as f64 * 0.1; // Smaller fragments
-
-                let enhanced_debris =
-                    EnhancedDebris::from_collision(*debris_count, current_time, collision_energy);
-
-                commands.spawn((
-                    enhanced_debris.debris.clone(), // Add the base Debris component
-                    enhanced_debris,                // Add the EnhancedDebris component
-                    OrbitalState::new(collision_point, debris_velocity, debris_mass),
-                    PhysicsObject::debris(debris_mass),
-                    RenderAsDebris,
-                ));
+            // Fragment both objects with the NASA standard breakup model
+            let breakup = collision_breakup(
+                &breakup_config,
+                &breakup_parent(orbital1, physics1, sat1, class1),
+                &breakup_parent(orbital2, physics2, sat2, class2),
+                &mut rng,
+            );
+
+            breakup_resources.spawn_fragments(&mut commands, &breakup, collision_point, collision_energy, current_time);
+
+            debug!(
+                "Generated {} debris pieces ({:.1} kg unresolved) from {} collision",
+                breakup.fragments.len(),
+                breakup.unresolved_mass,
+                if breakup.catastrophic { "catastrophic" } else { "non-catastrophic" }
+            );
+
+            // A non-catastrophic collision leaves the heavier object intact minus the cratered mass,
+            // moving with the pair's center of mass
+            if !breakup.catastrophic {
+                let (target, target_orbital) = if orbital1.mass >= orbital2.mass {
+                    (entity1, orbital1)
+                } else {
+                    (entity2, orbital2)
+                };
+                let projectile = if target == entity1 { entity2 } else { entity1 };
+
+                if let Ok(mut entity_commands) = commands.get_entity(target) {
+                    entity_commands.insert(OrbitalState::new(
+                        target_orbital.position,
+                        breakup.center_of_mass_velocity,
+                        Kg(breakup.remnant_mass),
+                    ));
+                }
+                if let Ok(mut entity_commands) = commands.get_entity(projectile) {
+                    entity_commands.insert(ScheduledForDeletion);
+                    entity_commands.despawn();
+                }
+                continue;
             }
 
-            debug!("Generated {} debris pieces from collision", debris_pieces);
-
             // Mark entities for deletion first to prevent double processing, then despawn
             if let Ok(mut entity_commands) = commands.get_entity(entity1) {
                 entity_commands.insert(ScheduledForDeletion);
@@ -438,32 +461,67 @@ pub fn debris_generation_system(
     }
 }
 
-/// Calculate number of debris pieces from collision energy (NASA breakup model)
-fn calculate_debris_count(collision_energy: f32, total_mass: f64) -> u32 {
-    // Simplified NASA standard breakup model
-    // More massive objects and higher energy create more debris
-    let base_debris = (total_mass / 1000.0).sqrt() as u32; // Base on mass
-    let energy_multiplier = (collision_energy / 1e12).sqrt().min(10.0) as u32; // Energy scaling
-
-    (base_debris + energy_multiplier).clamp(2, 50) // Minimum 2, maximum 50 pieces
+/// Breakup configuration and bookkeeping shared by the systems that fragment objects
+#[derive(SystemParam)]
+pub struct BreakupResources<'w, 's> {
+    config: Option<Res<'w, BreakupConfig>>,
+    statistics: Option<ResMut<'w, BreakupStatistics>>,
+    debris_count: Local<'s, u32>,
 }
 
-/// Generate debris velocity with realistic distribution
-fn generate_debris_velocity(vel1: Vec3, vel2: Vec3, relative_speed: f32) -> Vec3 {
-    use rand::prelude::*;
+impl BreakupResources<'_, '_> {
+    /// Breakup configuration, falling back to the defaults when the resource is absent
+    pub fn config(&self) -> BreakupConfig {
+        self.config.as_ref().map(|c| (**c).clone()).unwrap_or_default()
+    }
 
-    // Average velocity of colliding objects
-    let avg_velocity = (vel1 + vel2) / 2.0;
+    /// Record a breakup and spawn a debris entity for every fragment at the given position
+    pub fn spawn_fragments(
+        &mut self,
+        commands: &mut Commands,
+        breakup: &BreakupResult,
+        position: DVec3,
+        event_energy: f32,
+        current_time: f64,
+    ) {
+        if let Some(statistics) = self.statistics.as_mut() {
+            statistics.record(breakup);
+        }
 
-    // Generate random direction for debris
-    let mut rng = thread_rng();
-    let theta = rng.gen::<f32>() * 2.0 * std::f32::consts::PI;
-    let phi = rng.gen::<f32>() * std::f32::consts::PI;
+        for fragment in &breakup.fragments {
+            *self.debris_count += 1;
 
-    let random_dir = Vec3::new(phi.sin() * theta.cos(), phi.sin() * theta.sin(), phi.cos());
+            let enhanced_debris = EnhancedDebris::from_collision(*self.debris_count, current_time, event_energy);
 
-    // Debris gets kicked with fraction of relative collision speed
-    let debris_kick_speed = relative_speed * rng.gen_range(0.1..0.5);
+            commands.spawn((
+                enhanced_debris.debris.clone(), // Add the base Debris component
+                enhanced_debris,                // Add the EnhancedDebris component
+                OrbitalState::new(position, fragment.velocity, Kg(fragment.mass)),
+                PhysicsObject::fragment(fragment.area, fragment.characteristic_length),
+                ObjectClass::Debris,
+                RenderAsDebris,
+            ));
+        }
+    }
+}
 
-    avg_velocity + random_dir * debris_kick_speed
+/// Describe an object taking part in a breakup
+pub fn breakup_parent(
+    orbital_state: &OrbitalState,
+    physics_object: &PhysicsObject,
+    satellite: Option<&Satellite>,
+    class: Option<&ObjectClass>,
+) -> BreakupParent {
+    let class = class.copied().unwrap_or(if satellite.is_some() {
+        ObjectClass::Spacecraft
+    } else {
+        ObjectClass::Debris
+    });
+
+    BreakupParent {
+        mass: orbital_state.mass.0,
+        velocity: orbital_state.velocity,
+        characteristic_length: (physics_object.collision_radius * 2.0).0,
+        class,
+    }
 }
@@ -0,0 +1,404 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::reentry::{yearly_totals, SECONDS_PER_YEAR};
+use crate::utils::analytical::AnalyticalPropagator;
+use crate::utils::conjunction::*;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::gravity::GravityModel;
+use crate::utils::units::*;
+
+/// Operator rule that turns a conjunction into an avoidance maneuver
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AvoidanceThreshold {
+    /// Maneuver when the predicted miss distance is below this many km
+    MissDistance(f64),
+    /// Maneuver when the collision probability is above this value
+    Probability(f64),
+}
+
+/// Resource to control collision avoidance for active satellites
+#[derive(Resource, Clone, Debug)]
+pub struct AvoidanceConfig {
+    pub enabled: bool,
+    pub threshold: AvoidanceThreshold,
+    /// Simulation seconds between screenings
+    pub screening_interval: f64,
+    /// How far ahead a screening looks, in seconds
+    pub screening_window: f64,
+    /// Sampling step of the closest approach search in seconds
+    pub screening_step: f64,
+    /// Objects whose radial ranges or coarse samples never come within this many km of each
+    /// other are not searched for a closest approach
+    pub screening_margin: f64,
+    /// Most closest approach searches one frame may run; a screening with more candidate pairs
+    /// carries on over the following frames
+    pub max_pairs_per_frame: usize,
+    /// Standard deviation of the combined position uncertainty per axis, in km
+    pub position_sigma: f64,
+    /// Smallest combined hard-body radius in km; operators pad the objects' own radii to
+    /// cover unknown shape and attitude
+    pub hard_body_radius: f64,
+    /// Separation in km the maneuver aims to open at closest approach
+    pub target_separation: f64,
+    /// Preferred time between the burn and closest approach in seconds; half an orbit in LEO
+    /// turns along-track delta-V into the most displacement
+    pub lead_time: f64,
+    /// Largest delta-V a single avoidance burn may use, in km/s
+    pub max_delta_v: f64,
+    /// Specific impulse of the avoidance thrusters in seconds
+    pub isp: f64,
+}
+
+impl Default for AvoidanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: AvoidanceThreshold::Probability(1e-4), // Common operator action threshold
+            screening_interval: 3600.0,
+            screening_window: 6.0 * 3600.0,
+            screening_step: 60.0,
+            screening_margin: 10.0,
+            max_pairs_per_frame: 500,
+            position_sigma: 0.2,
+            hard_body_radius: 0.02,
+            target_separation: 2.0,
+            lead_time: 2700.0,
+            max_delta_v: 0.001,
+            isp: HYDRAZINE_ISP,
+        }
+    }
+}
+
+impl AvoidanceConfig {
+    /// Collision probability for a conjunction between objects of `combined_radius` km
+    pub fn probability(&self, conjunction: &Conjunction, combined_radius: f64) -> f64 {
+        collision_probability(conjunction.miss_distance, combined_radius, self.position_sigma)
+    }
+
+    /// Whether a conjunction crosses the operator threshold
+    pub fn requires_maneuver(&self, conjunction: &Conjunction, combined_radius: f64) -> bool {
+        match self.threshold {
+            AvoidanceThreshold::MissDistance(distance) => conjunction.miss_distance < distance,
+            AvoidanceThreshold::Probability(probability) => {
+                self.probability(conjunction, combined_radius) > probability
+            }
+        }
+    }
+
+    /// Prograde burn opening `target_separation` at a conjunction found at `now` (simulation
+    /// seconds), `lead_time` ahead of closest approach or straight away if that has passed
+    ///
+    /// `mean_motion` is the satellite's in rad/s.
+    pub fn plan_burn(&self, now: f64, conjunction: &Conjunction, mean_motion: f64) -> Burn {
+        let coast = conjunction.time_to_closest_approach.min(self.lead_time).max(0.0);
+        let response = conjunction.avoidance_response(mean_motion, coast);
+        let delta_v = if response > 0.0 {
+            (self.target_separation / response).min(self.max_delta_v)
+        } else {
+            self.max_delta_v
+        };
+        let time = now + conjunction.time_to_closest_approach - coast;
+        Burn::impulsive(time, DVec3::new(0.0, delta_v, 0.0), BurnFrame::Rtn, self.isp)
+    }
+}
+
+/// One planned avoidance maneuver
+#[derive(Clone, Debug)]
+pub struct AvoidanceManeuver {
+    pub satellite: Entity,
+    pub norad_id: u32,
+    /// Object the satellite is avoiding
+    pub threat: Entity,
+    /// Simulation time the maneuver was decided, in seconds
+    pub time: f64,
+    /// Simulation time of the burn in seconds
+    pub burn_time: f64,
+    /// Simulation time of the predicted closest approach in seconds
+    pub closest_approach: f64,
+    /// Predicted miss distance without the maneuver, in km
+    pub miss_distance: f64,
+    /// Collision probability without the maneuver
+    pub probability: f64,
+    /// Planned delta-V in km/s
+    pub delta_v: f64,
+}
+
+/// Resource recording every avoidance maneuver, the operational cost of a crowded environment
+#[derive(Resource, Default)]
+pub struct AvoidanceLog {
+    pub maneuvers: Vec<AvoidanceManeuver>,
+    /// Conjunctions already acted on and the time of their closest approach, so later
+    /// screenings before the burn fires do not plan it again
+    handled: HashMap<(Entity, Entity), f64>,
+    /// Simulation time of the last screening
+    last_screening: Option<f64>,
+    /// Screening still working through its candidate pairs
+    screening: Option<Screening>,
+    /// Pairs the coarse filter passed to the closest approach search in the latest screening
+    pub candidate_pairs: usize,
+    /// Closest approach searches run in the latest frame
+    pub frame_searches: usize,
+}
+
+impl AvoidanceLog {
+    pub fn record(&mut self, maneuver: AvoidanceManeuver) {
+        self.handled.insert((maneuver.satellite, maneuver.threat), maneuver.closest_approach);
+        self.maneuvers.push(maneuver);
+    }
+
+    /// Whether `interval` seconds have passed since the last screening, starting a new one if so
+    pub fn screening_due(&mut self, now: f64, interval: f64) -> bool {
+        if self.last_screening.is_some_and(|last| now - last < interval) {
+            return false;
+        }
+        self.last_screening = Some(now);
+        true
+    }
+
+    /// Whether a screening is still working through its candidate pairs
+    pub fn screening_in_progress(&self) -> bool {
+        self.screening.is_some()
+    }
+
+    /// Whether the conjunction between these objects has already been acted on
+    pub fn is_handled(&self, satellite: Entity, threat: Entity) -> bool {
+        self.handled.contains_key(&(satellite, threat))
+    }
+
+    /// Forget conjunctions whose closest approach has passed
+    pub fn expire(&mut self, now: f64) {
+        self.handled.retain(|_, closest_approach| *closest_approach > now);
+    }
+
+    /// Total number of avoidance maneuvers
+    pub fn total(&self) -> usize {
+        self.maneuvers.len()
+    }
+
+    /// Total delta-V planned for avoidance in km/s
+    pub fn total_delta_v(&self) -> f64 {
+        self.maneuvers.iter().map(|m| m.delta_v).sum()
+    }
+
+    /// Number of avoidance maneuvers for each satellite, by NORAD catalog number
+    pub fn per_satellite(&self) -> HashMap<u32, usize> {
+        let mut counts = HashMap::new();
+        for maneuver in &self.maneuvers {
+            *counts.entry(maneuver.norad_id).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Number of avoidance maneuvers decided in each simulated year, starting at year 0
+    pub fn yearly_summary(&self) -> Vec<usize> {
+        yearly_totals(&self.maneuvers, |maneuver| maneuver.time, |year: &mut usize, _| *year += 1)
+    }
+
+    /// Average maneuvers per satellite per year over the elapsed simulation time
+    pub fn rate_per_satellite_year(&self, satellites: usize, elapsed_seconds: f64) -> f64 {
+        if satellites == 0 || elapsed_seconds <= 0.0 {
+            return 0.0;
+        }
+        self.maneuvers.len() as f64 * SECONDS_PER_YEAR / (elapsed_seconds * satellites as f64)
+    }
+}
+
+/// Components read from each satellite that may maneuver
+type AvoidanceCandidate<'a> = (
+    Entity,
+    &'a OrbitalState,
+    &'a PhysicsObject,
+    &'a Satellite,
+    Option<&'a mut ManeuverPlan>,
+);
+
+/// An object in the screening catalog
+struct Screened {
+    entity: Entity,
+    elements: KeplerianElements,
+    /// Collision radius in km
+    radius: f64,
+}
+
+/// Most pressing conjunction found for a satellite
+struct Threat {
+    threat: Entity,
+    conjunction: Conjunction,
+    probability: f64,
+}
+
+/// A screening spread over several frames
+struct Screening {
+    /// Simulation time the catalog was taken, in seconds
+    time: f64,
+    catalog: Vec<Screened>,
+    /// Candidate (satellite, object) pairs as indices into the catalog
+    pairs: Vec<(usize, usize)>,
+    /// Number of pairs already searched
+    searched: usize,
+    /// Earliest conjunction over the threshold found so far for each satellite, by catalog index
+    threats: HashMap<usize, Threat>,
+}
+
+/// System to screen active satellites for conjunctions and plan avoidance burns
+///
+/// Every `screening_interval` the whole catalog is flown ahead along J2 mean orbits, and a coarse
+/// filter picks out the pairs that may pass within `screening_margin`. Their closest approaches
+/// are searched at most `max_pairs_per_frame` a frame. Once all are done each active satellite
+/// acts on its earliest conjunction over the threshold that is not already handled, scheduling
+/// a prograde burn in its `ManeuverPlan` (given one if it has none).
+pub fn collision_avoidance_system(
+    mut commands: Commands,
+    config: Res<AvoidanceConfig>,
+    mut avoidance_log: ResMut<AvoidanceLog>,
+    sim_time: Res<SimulationTime>,
+    constants: Res<Constants>,
+    mut satellite_query: Query<AvoidanceCandidate<'_>, Without<ScheduledForDeletion>>,
+    object_query: Query<(Entity, &OrbitalState, &PhysicsObject), Without<ScheduledForDeletion>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let now = sim_time.current;
+    let propagator = AnalyticalPropagator::new(&constants, GravityModel::J2, None);
+    avoidance_log.frame_searches = 0;
+
+    if avoidance_log.screening.is_none() {
+        if !avoidance_log.screening_due(now, config.screening_interval) {
+            return;
+        }
+        avoidance_log.expire(now);
+
+        let catalog: Vec<Screened> = object_query
+            .iter()
+            .filter_map(|(entity, orbital_state, physics_object)| {
+                let elements = propagator.mean_elements(orbital_state.position, orbital_state.velocity);
+                elements.is_bound().then(|| Screened {
+                    entity,
+                    elements,
+                    radius: Km::from(physics_object.collision_radius).0,
+                })
+            })
+            .collect();
+        let satellites: Vec<usize> = catalog
+            .iter()
+            .enumerate()
+            .filter(|(_, screened)| {
+                satellite_query.get(screened.entity).is_ok_and(|(_, _, _, satellite, _)| satellite.active)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let elements: Vec<KeplerianElements> = catalog.iter().map(|screened| screened.elements).collect();
+        let margin = config.screening_margin;
+        let pairs: Vec<(usize, usize)> =
+            coarse_screen(&propagator, &elements, &satellites, config.screening_window, config.screening_step, margin)
+                .into_iter()
+                .filter(|&(satellite, object)| {
+                    !avoidance_log.is_handled(catalog[satellite].entity, catalog[object].entity)
+                })
+                .filter(|&(satellite, object)| radial_ranges_overlap(&elements[satellite], &elements[object], margin))
+                .collect();
+        avoidance_log.candidate_pairs = pairs.len();
+        avoidance_log.screening = Some(Screening { time: now, catalog, pairs, searched: 0, threats: HashMap::new() });
+    }
+
+    let Some(screening) = avoidance_log.screening.as_mut() else {
+        return;
+    };
+    let end = (screening.searched + config.max_pairs_per_frame).min(screening.pairs.len());
+    let catalog = &screening.catalog;
+    let found: Vec<(usize, Threat)> = screening.pairs[screening.searched..end]
+        .par_iter()
+        .filter_map(|&(satellite, object)| {
+            let (satellite_screened, object_screened) = (&catalog[satellite], &catalog[object]);
+            let conjunction = closest_approach(
+                &propagator,
+                &satellite_screened.elements,
+                &object_screened.elements,
+                config.screening_window,
+                config.screening_step,
+            )?;
+            let combined_radius = (satellite_screened.radius + object_screened.radius).max(config.hard_body_radius);
+            config.requires_maneuver(&conjunction, combined_radius).then(|| {
+                let probability = config.probability(&conjunction, combined_radius);
+                (satellite, Threat { threat: object_screened.entity, conjunction, probability })
+            })
+        })
+        .collect();
+    let searches = end - screening.searched;
+    screening.searched = end;
+    for (satellite, threat) in found {
+        let earliest = screening.threats.get(&satellite).is_none_or(|current| {
+            threat.conjunction.time_to_closest_approach < current.conjunction.time_to_closest_approach
+        });
+        if earliest {
+            screening.threats.insert(satellite, threat);
+        }
+    }
+    let finished = screening.searched == screening.pairs.len();
+    avoidance_log.frame_searches = searches;
+    if !finished {
+        return;
+    }
+    let Some(screening) = avoidance_log.screening.take() else {
+        return;
+    };
+
+    let mu = constants.gravitational_parameter.0;
+    // Conjunctions were found against the catalog as it stood when the screening started
+    let elapsed = now - screening.time;
+    let mut threats: Vec<(usize, Threat)> = screening.threats.into_iter().collect();
+    threats.sort_unstable_by_key(|(satellite, _)| *satellite);
+    for (satellite, mut threat) in threats {
+        threat.conjunction.time_to_closest_approach -= elapsed;
+        if threat.conjunction.time_to_closest_approach < 0.0 {
+            continue;
+        }
+        let satellite = &screening.catalog[satellite];
+        let Ok((entity, orbital_state, _, info, plan)) = satellite_query.get_mut(satellite.entity) else {
+            continue;
+        };
+        if !info.active {
+            continue;
+        }
+
+        let closest_approach = now + threat.conjunction.time_to_closest_approach;
+        let mean_motion = satellite.elements.mean_motion(mu).unwrap_or(0.0);
+        let burn = config.plan_burn(now, &threat.conjunction, mean_motion);
+        match plan {
+            Some(mut plan) => plan.schedule(burn),
+            None => {
+                let mut plan = ManeuverPlan::for_spacecraft(orbital_state.mass);
+                plan.schedule(burn);
+                commands.entity(entity).insert(plan);
+            }
+        }
+
+        let maneuver = AvoidanceManeuver {
+            satellite: entity,
+            norad_id: info.norad_id,
+            threat: threat.threat,
+            time: now,
+            burn_time: burn.time,
+            closest_approach,
+            miss_distance: threat.conjunction.miss_distance,
+            probability: threat.probability,
+            delta_v: burn.delta_v.length(),
+        };
+        info!(
+            "Avoidance: {} plans {:.2} m/s at t={:.0}s for a {:.3} km miss (Pc {:.1e}) at t={:.0}s",
+            info.name,
+            maneuver.delta_v * METERS_PER_KM,
+            maneuver.burn_time,
+            maneuver.miss_distance,
+            maneuver.probability,
+            maneuver.closest_approach
+        );
+        avoidance_log.record(maneuver);
+    }
+}
@@ -110,7 +110,7 @@ pub fn visualize_collision_warnings_system(
         commands.spawn((
             Mesh3d(warning_mesh),
             MeshMaterial3d(warning_material),
-            Transform::from_translation(orbital_state.position / 1000.0),
+            Transform::from_translation(orbital_state.render_position() / 1000.0),
             CollisionWarningVisual { parent: entity },
         ));
     }
@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+use std::f64::consts::{PI, TAU};
+use std::path::Path;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::lifecycle::new_mission;
+use crate::systems::station_keeping::{SLOT_ALONG_TRACK_TOLERANCE, SLOT_ALTITUDE_TOLERANCE};
+use crate::utils::elements::KeplerianElements;
+use crate::utils::units::*;
+
+/// How the orbital planes of a Walker constellation are spread in right ascension
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WalkerPattern {
+    /// Planes spread over the full 360 degrees, for inclined shells (Walker delta)
+    Delta,
+    /// Planes spread over 180 degrees, for near-polar shells (Walker star)
+    Star,
+}
+
+impl WalkerPattern {
+    /// Right ascension spanned by the planes in radians
+    pub fn raan_spread(&self) -> f64 {
+        match self {
+            WalkerPattern::Delta => TAU,
+            WalkerPattern::Star => PI,
+        }
+    }
+}
+
+/// Walker constellation shell `i: T/P/F` of circular orbits
+///
+/// `total` satellites are spread evenly over `planes` planes of equal inclination, and
+/// evenly within each plane. Satellites in neighbouring planes are offset along-track by
+/// `phasing` times 360/`total` degrees.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct WalkerConstellation {
+    pub name: String,
+    pub total: u32,
+    pub planes: u32,
+    /// Relative phasing between neighbouring planes, from 0 to `planes` - 1
+    pub phasing: u32,
+    /// Altitude in km
+    pub altitude: f64,
+    /// Inclination in degrees
+    pub inclination: f64,
+    pub pattern: WalkerPattern,
+    /// Right ascension of the ascending node of the first plane in degrees
+    pub raan_offset: f64,
+    /// Mass of each satellite in kg
+    pub mass: f64,
+}
+
+impl Default for WalkerConstellation {
+    fn default() -> Self {
+        Self {
+            name: "Walker".to_string(),
+            total: 24,
+            planes: 3,
+            phasing: 1,
+            altitude: 550.0,
+            inclination: 53.0,
+            pattern: WalkerPattern::Delta,
+            raan_offset: 0.0,
+            mass: 260.0,
+        }
+    }
+}
+
+impl WalkerConstellation {
+    pub fn delta(name: &str, total: u32, planes: u32, phasing: u32, altitude: f64, inclination: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            total,
+            planes,
+            phasing,
+            altitude,
+            inclination,
+            ..Default::default()
+        }
+    }
+
+    pub fn star(name: &str, total: u32, planes: u32, phasing: u32, altitude: f64, inclination: f64) -> Self {
+        Self {
+            pattern: WalkerPattern::Star,
+            ..Self::delta(name, total, planes, phasing, altitude, inclination)
+        }
+    }
+
+    pub fn with_mass(mut self, mass: f64) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    pub fn satellites_per_plane(&self) -> u32 {
+        self.total / self.planes.max(1)
+    }
+
+    /// Check the shell describes a realisable Walker pattern
+    pub fn validate(&self) -> Result<(), String> {
+        if self.total == 0 || self.planes == 0 {
+            return Err(format!("Shell {} needs at least one satellite and one plane", self.name));
+        }
+        if self.total % self.planes != 0 {
+            return Err(format!(
+                "Shell {}: {} satellites do not divide evenly into {} planes",
+                self.name, self.total, self.planes
+            ));
+        }
+        if self.phasing >= self.planes {
+            return Err(format!(
+                "Shell {}: phasing {} must be less than the {} planes",
+                self.name, self.phasing, self.planes
+            ));
+        }
+        if self.altitude <= 0.0 {
+            return Err(format!("Shell {}: altitude {} km is below the surface", self.name, self.altitude));
+        }
+        Ok(())
+    }
+
+    /// Orbital elements of every satellite, plane by plane, at the shell's reference epoch
+    pub fn elements(&self, constants: &Constants) -> Vec<KeplerianElements> {
+        let per_plane = self.satellites_per_plane();
+        let plane_spacing = self.pattern.raan_spread() / self.planes as f64;
+        let slot_spacing = TAU / per_plane as f64;
+        let phase_offset = self.phasing as f64 * TAU / self.total as f64;
+
+        (0..self.planes)
+            .flat_map(|plane| {
+                (0..per_plane).map(move |slot| KeplerianElements {
+                    semi_major_axis: (constants.earth_radius + Km(self.altitude)).0,
+                    eccentricity: 0.0,
+                    inclination: self.inclination.to_radians(),
+                    raan: (self.raan_offset.to_radians() + plane as f64 * plane_spacing).rem_euclid(TAU),
+                    arg_perigee: 0.0,
+                    true_anomaly: (slot as f64 * slot_spacing + plane as f64 * phase_offset).rem_euclid(TAU),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A constellation of one or more Walker shells, like the mega-constellations filed with the ITU
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConstellationDefinition {
+    pub name: String,
+    pub shells: Vec<WalkerConstellation>,
+}
+
+impl ConstellationDefinition {
+    /// Five shells of the first-generation Starlink filing
+    pub fn starlink_like() -> Self {
+        Self {
+            name: "Starlink".to_string(),
+            shells: vec![
+                WalkerConstellation::delta("Starlink 550", 1584, 72, 17, 550.0, 53.0),
+                WalkerConstellation::delta("Starlink 540", 1584, 72, 17, 540.0, 53.2),
+                WalkerConstellation::delta("Starlink 570", 720, 36, 11, 570.0, 70.0),
+                WalkerConstellation::star("Starlink 560", 348, 6, 1, 560.0, 97.6),
+                WalkerConstellation::star("Starlink 560B", 172, 4, 1, 560.0, 97.6),
+            ],
+        }
+    }
+
+    /// The near-polar OneWeb Phase 1 shell
+    pub fn oneweb_like() -> Self {
+        Self {
+            name: "OneWeb".to_string(),
+            shells: vec![WalkerConstellation::star("OneWeb", 648, 18, 1, 1200.0, 87.9).with_mass(150.0)],
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.shells.iter().map(|shell| shell.total as usize).sum()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.shells.iter().try_for_each(WalkerConstellation::validate)
+    }
+}
+
+/// Parse a JSON constellation definition, checking every shell
+pub fn parse_constellation(content: &str) -> Result<ConstellationDefinition, String> {
+    let definition: ConstellationDefinition =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse constellation: {}", e))?;
+    definition.validate()?;
+    Ok(definition)
+}
+
+/// Load a JSON constellation definition file
+pub fn load_constellation(path: &Path) -> Result<ConstellationDefinition, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read constellation {}: {}", path.display(), e))?;
+    parse_constellation(&content)
+}
+
+/// Spawn every satellite of a Walker shell at simulation time `now`, numbered consecutively
+/// from `catalog`
+///
+/// Satellites are named by shell, plane and slot, hold the slot they are spawned in and start
+/// their missions at `now`. Returns the entities in plane order.
+pub fn spawn_walker_shell(
+    commands: &mut Commands,
+    shell: &WalkerConstellation,
+    catalog: &mut CatalogNumbers,
+    now: f64,
+    constants: &Constants,
+) -> Vec<Entity> {
+    let mu = constants.gravitational_parameter.0;
+    let per_plane = shell.satellites_per_plane() as usize;
+    let elements = shell.elements(constants);
+    let first_catalog_number = catalog.allocate(elements.len() as u32);
+
+    elements
+        .iter()
+        .enumerate()
+        .map(|(index, element)| {
+            let (position, velocity) = element.to_state(mu);
+            let orbital_state = OrbitalState::new(position, velocity, Kg(shell.mass));
+            let slot = StationKeeping::constellation_slot(
+                &orbital_state,
+                constants,
+                now,
+                SLOT_ALTITUDE_TOLERANCE,
+                SLOT_ALONG_TRACK_TOLERANCE,
+            );
+            let mission = new_mission(&orbital_state, false, now, constants);
+            commands
+                .spawn((
+                    Satellite::new(
+                        format!("{} {}-{}", shell.name, index / per_plane + 1, index % per_plane + 1),
+                        first_catalog_number + index as u32,
+                        true,
+                    ),
+                    orbital_state,
+                    PhysicsObject::satellite(Kg(shell.mass)),
+                    ObjectClass::Spacecraft,
+                    mission,
+                    slot,
+                    ManeuverPlan::for_spacecraft(Kg(shell.mass)),
+                    RenderAsSatellite,
+                    crate::components::trails::Trail::new(500, shell.altitude),
+                ))
+                .id()
+        })
+        .collect()
+}
+
+/// Spawn every shell of a constellation with consecutive catalog numbers from `catalog`,
+/// returning the entities shell by shell
+pub fn spawn_constellation(
+    commands: &mut Commands,
+    definition: &ConstellationDefinition,
+    catalog: &mut CatalogNumbers,
+    now: f64,
+    constants: &Constants,
+) -> Vec<Entity> {
+    let mut entities = Vec::with_capacity(definition.total());
+    for shell in &definition.shells {
+        entities.extend(spawn_walker_shell(commands, shell, catalog, now, constants));
+    }
+    info!(
+        "Constellation: spawned {} satellites of {} in {} shells",
+        entities.len(),
+        definition.name,
+        definition.shells.len()
+    );
+    entities
+}
@@ -1,3 +1,4 @@
+use bevy::math::DVec3;
 use bevy::prelude::*;
 #[cfg(not(target_arch = "wasm32"))]
 use reqwest;
@@ -7,6 +8,9 @@ use std::fs;
 use std::path::Path;
 use crate::utils::*;
 use crate::components::*;
+use crate::resources::{Constants, SimulationTime};
+use crate::systems::lifecycle::in_service_mission;
+use crate::systems::station_keeping::geostationary_slot;
 use crate::utils::sgp4_wrapper::*;
 
 /// System to fetch TLE data from Celestrak (non-WASM only)
@@ -63,6 +67,8 @@ pub struct TleFetchTask;
 pub fn process_tle_fetch_system(
     mut commands: Commands,
     mut tle_cache: ResMut<TleDataCache>,
+    mut sim_time: ResMut<SimulationTime>,
+    constants: Res<Constants>,
     query: Query<Entity, With<TleFetchTask>>,
 ) {
     for entity in query.iter() {
@@ -93,13 +99,14 @@ pub fn process_tle_fetch_system(
                 }
                 
                 // Spawn satellites from TLE data
-                spawn_satellites_from_records(&mut commands, &limited_records);
+                anchor_epoch_to_catalog(&mut sim_time, &limited_records);
+                spawn_satellites_from_records(&mut commands, &limited_records, &sim_time, &constants);
             }
             Err(e) => {
                 warn!("Failed to load TLE data: {} - Using test dataset instead", e);
                 
                 // Use expanded test dataset with 100 realistic satellites
-                create_extended_test_dataset(&mut commands, &mut tle_cache);
+                create_extended_test_dataset(&mut commands, &mut tle_cache, &mut sim_time, &constants);
             }
         }
     }
@@ -212,7 +219,12 @@ fn try_load_local_tle_data() -> Result<Vec<TleRecord>, String> {
 }
 
 /// Create extended test dataset with 100 realistic satellites
-fn create_extended_test_dataset(commands: &mut Commands, tle_cache: &mut ResMut<TleDataCache>) {
+fn create_extended_test_dataset(
+    commands: &mut Commands,
+    tle_cache: &mut ResMut<TleDataCache>,
+    sim_time: &mut SimulationTime,
+    constants: &Constants,
+) {
     let test_satellites = vec![
         // Low Earth Orbit satellites (50 total)
         create_test_satellite("ISS (ZARYA)", 25544, 408.0, 51.6),
@@ -327,20 +339,33 @@ fn create_extended_test_dataset(commands: &mut Commands, tle_cache: &mut ResMut<
     }
     
     // Spawn satellite entities
-    spawn_satellites_from_records(commands, &tle_cache.records);
+    anchor_epoch_to_catalog(sim_time, &tle_cache.records);
+    spawn_satellites_from_records(commands, &tle_cache.records, sim_time, constants);
     
     info!("Created test dataset with {} realistic satellites", test_satellites.len());
 }
 
-/// Spawn satellites from TLE records
-fn spawn_satellites_from_records(commands: &mut Commands, records: &[TleRecord]) {
+/// Start the simulation clock at the newest TLE epoch unless an epoch was configured
+pub fn anchor_epoch_to_catalog(sim_time: &mut SimulationTime, records: &[TleRecord]) {
+    if !sim_time.epoch_from_catalog {
+        return;
+    }
+    if let Some(newest) = newest_tle_epoch(records) {
+        sim_time.anchor_to(newest);
+        info!("Simulation epoch set to newest TLE epoch {}", newest.to_iso8601());
+    }
+}
+
+/// Spawn satellites from TLE records, propagated to the current simulation time
+fn spawn_satellites_from_records(commands: &mut Commands, records: &[TleRecord], sim_time: &SimulationTime, constants: &Constants) {
     let mut spawned_count = 0;
     let mut failed_count = 0;
     
+    let epoch = sim_time.now();
     for tle_record in records {
-        match create_satellite_from_tle(tle_record) {
+        match create_satellite_from_tle(tle_record, &epoch) {
             Ok(satellite_data) => {
-                spawn_satellite_entity(commands, satellite_data);
+                spawn_satellite_entity(commands, satellite_data, sim_time, constants);
                 spawned_count += 1;
             }
             Err(e) => {
@@ -385,20 +410,35 @@ fn create_test_satellite(name: &str, norad_id: u32, altitude_km: f64, inclinatio
     let orbital_velocity = (3.986004418e14 / (orbital_radius * 1000.0)).sqrt() / 1000.0; // km/s
 
     let orbital_state = OrbitalState::new(
-        Vec3::new(orbital_radius as f32, 0.0, 0.0),
-        Vec3::new(0.0, orbital_velocity as f32, 0.0),
-        1000.0, // 1000 kg mass
+        DVec3::new(orbital_radius, 0.0, 0.0),
+        DVec3::new(0.0, orbital_velocity, 0.0),
+        Kg(1000.0),
     );
 
     (tle_record, orbital_state)
 }
 
+/// Spawn a catalog satellite
+///
+/// Spacecraft are taken to be partway through their missions with a standard propellant
+/// load, and those in geostationary orbits hold the slot they are found in.
 fn spawn_satellite_entity(
     commands: &mut Commands,
     (tle_record, orbital_state): (TleRecord, OrbitalState),
+    sim_time: &SimulationTime,
+    constants: &Constants,
 ) {
-    let altitude_km = orbital_state.altitude();
-    commands.spawn((
+    let altitude_km = (orbital_state.radius() - constants.earth_radius).0;
+    let physics_object = PhysicsObject::from_bstar(tle_record.bstar, orbital_state.mass);
+    let class = ObjectClass::from_catalog_name(&tle_record.name);
+    let spacecraft = (class == ObjectClass::Spacecraft).then(|| {
+        let station_keeping = geostationary_slot(&orbital_state, &sim_time.now(), constants);
+        let geostationary = station_keeping.is_some();
+        let mission =
+            in_service_mission(&orbital_state, geostationary, sim_time.current, constants, &mut rand::thread_rng());
+        (mission, ManeuverPlan::for_spacecraft(orbital_state.mass), station_keeping)
+    });
+    let mut entity = commands.spawn((
         Satellite::new(tle_record.name.clone(), tle_record.norad_id, true),
         orbital_state,
         TleData::new(
@@ -406,24 +446,33 @@ fn spawn_satellite_entity(
             tle_record.name.clone(),
             tle_record.line1.clone(),
             tle_record.line2.clone(),
-            tle_record.epoch_day,
+            tle_epoch_julian_day(&tle_record),
         ),
-        PhysicsObject::satellite(1000.0),
+        physics_object,
+        class,
         RenderAsSatellite,
         crate::components::trails::Trail::new(500, altitude_km), // Add trail with 500 point capacity
     ));
+    if let Some((mission, plan, station_keeping)) = spacecraft {
+        entity.insert((mission, plan));
+        if let Some(station_keeping) = station_keeping {
+            entity.insert(station_keeping);
+        }
+    }
 }
 
-/// Create satellite from real TLE data using SGP4 conversion
-pub fn create_satellite_from_tle(tle_record: &TleRecord) -> Result<(TleRecord, OrbitalState), String> {
-    // Use SGP4 to convert TLE to position/velocity state vectors
-    let (position, velocity) = tle_to_state_vectors(tle_record)?;
+/// Create satellite from real TLE data using SGP4 propagation to the simulation epoch
+pub fn create_satellite_from_tle(tle_record: &TleRecord, epoch: &Epoch) -> Result<(TleRecord, OrbitalState), Sgp4Error> {
+    // Use SGP4/SDP4 to propagate the TLE from its epoch to the simulation's,
+    // then rotate its TEME output into the simulation's GCRF frame
+    let (position, velocity) = propagate_to_epoch(tle_record, epoch)?;
+    let (position, velocity) = teme_to_gcrf(position, velocity, epoch);
     
     // Estimate mass based on satellite type (this is a simplification)
     // In reality, mass would come from satellite databases
     let estimated_mass = estimate_satellite_mass(&tle_record.name);
     
-    let orbital_state = OrbitalState::new(position, velocity, estimated_mass);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(estimated_mass));
     
     Ok((tle_record.clone(), orbital_state))
 }
@@ -458,6 +507,8 @@ fn estimate_satellite_mass(name: &str) -> f64 {
 pub fn spawn_satellites_from_tle_data(
     mut commands: Commands,
     tle_cache: Res<TleDataCache>,
+    sim_time: Res<SimulationTime>,
+    constants: Res<Constants>,
     existing_sats: Query<&Satellite>,
 ) {
     // Check if we already have satellites spawned
@@ -473,10 +524,11 @@ pub fn spawn_satellites_from_tle_data(
     // In production, this could be configurable
     let max_satellites = 100;
     
+    let epoch = sim_time.now();
     for tle_record in tle_cache.records.iter().take(max_satellites) {
-        match create_satellite_from_tle(tle_record) {
+        match create_satellite_from_tle(tle_record, &epoch) {
             Ok(satellite_data) => {
-                spawn_satellite_entity(&mut commands, satellite_data);
+                spawn_satellite_entity(&mut commands, satellite_data, &sim_time, &constants);
                 spawned_count += 1;
             }
             Err(e) => {
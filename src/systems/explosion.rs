@@ -0,0 +1,145 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::collision::{breakup_parent, BreakupResources};
+use crate::systems::reentry::SECONDS_PER_YEAR;
+use crate::utils::breakup::*;
+
+/// Resource to control explosions and spontaneous fragmentations
+#[derive(Resource, Clone, Debug)]
+pub struct ExplosionConfig {
+    pub enabled: bool,
+    /// Annual probability rate of a spontaneous explosion for an unpassivated rocket body
+    pub rocket_body_rate: f64,
+    /// Annual probability rate of a spontaneous explosion for an unpassivated spacecraft
+    pub spacecraft_rate: f64,
+    /// Annual probability rate of a spontaneous fragmentation for debris
+    pub debris_rate: f64,
+    /// Explosion scaling factor S for the fragment count
+    pub scaling_factor: f64,
+}
+
+impl Default for ExplosionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rocket_body_rate: 1.0e-3, // A few upper-stage breakups a year across ~2000 stages
+            spacecraft_rate: 1.0e-4,
+            debris_rate: 0.0,
+            scaling_factor: 1.0,
+        }
+    }
+}
+
+impl ExplosionConfig {
+    /// Annual explosion rate for an object class
+    pub fn annual_rate(&self, class: ObjectClass) -> f64 {
+        match class {
+            ObjectClass::RocketBody => self.rocket_body_rate,
+            ObjectClass::Spacecraft => self.spacecraft_rate,
+            ObjectClass::Debris => self.debris_rate,
+        }
+    }
+
+    /// Probability that an object of the given class explodes within `dt` seconds
+    pub fn explosion_probability(&self, class: ObjectClass, dt: f64) -> f64 {
+        1.0 - (-self.annual_rate(class) * dt.max(0.0) / SECONDS_PER_YEAR).exp()
+    }
+}
+
+/// What triggered an explosion
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExplosionTrigger {
+    /// A `ScheduledExplosion` component came due
+    Scheduled,
+    /// Drawn from the per-class annual rate
+    Spontaneous,
+}
+
+/// Emitted once when an object explodes
+#[derive(Event, Clone, Debug)]
+pub struct ExplosionEvent {
+    pub entity: Entity,
+    pub class: ObjectClass,
+    pub trigger: ExplosionTrigger,
+    /// Simulation time of the explosion in seconds
+    pub time: f64,
+    /// Position in km
+    pub position: DVec3,
+    /// Mass of the exploded object in kg
+    pub mass: f64,
+    /// Number of tracked fragments spawned
+    pub fragments: usize,
+}
+
+/// Components read from each object that may explode
+type ExplosionCandidate<'a> = (
+    Entity,
+    &'a OrbitalState,
+    &'a PhysicsObject,
+    Option<&'a Satellite>,
+    Option<&'a ObjectClass>,
+    Option<&'a ScheduledExplosion>,
+);
+
+/// System to fragment objects whose scheduled explosion is due or that explode spontaneously
+pub fn explosion_system(
+    mut commands: Commands,
+    config: Res<ExplosionConfig>,
+    mut breakup_resources: BreakupResources,
+    mut explosion_events: EventWriter<ExplosionEvent>,
+    sim_time: Res<SimulationTime>,
+    object_query: Query<ExplosionCandidate<'_>, (Without<Passivated>, Without<ScheduledForDeletion>)>,
+    mut last_time: Local<Option<f64>>,
+) {
+    let dt = sim_time.current - last_time.unwrap_or(sim_time.current);
+    *last_time = Some(sim_time.current);
+
+    if !config.enabled {
+        return;
+    }
+
+    let breakup_config = breakup_resources.config();
+    let mut rng = rand::thread_rng();
+
+    for (entity, orbital_state, physics_object, satellite, class, scheduled) in object_query.iter() {
+        let parent = breakup_parent(orbital_state, physics_object, satellite, class);
+
+        let trigger = if scheduled.is_some_and(|s| sim_time.current >= s.time) {
+            ExplosionTrigger::Scheduled
+        } else if dt > 0.0 && rng.gen::<f64>() < config.explosion_probability(parent.class, dt) {
+            ExplosionTrigger::Spontaneous
+        } else {
+            continue;
+        };
+
+        let breakup = explosion_breakup(&breakup_config, &parent, config.scaling_factor, &mut rng);
+        breakup_resources.spawn_fragments(&mut commands, &breakup, orbital_state.position, 0.0, sim_time.current);
+
+        info!(
+            "Explosion: {:?} {:.0} kg {:?} produced {} fragments ({:.1} kg unresolved)",
+            trigger,
+            parent.mass,
+            parent.class,
+            breakup.fragments.len(),
+            breakup.unresolved_mass
+        );
+
+        explosion_events.write(ExplosionEvent {
+            entity,
+            class: parent.class,
+            trigger,
+            time: sim_time.current,
+            position: orbital_state.position,
+            mass: parent.mass,
+            fragments: breakup.fragments.len(),
+        });
+
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(ScheduledForDeletion);
+            entity_commands.despawn();
+        }
+    }
+}
@@ -6,6 +6,7 @@ use bevy::render::{
 };
 use crate::components::*;
 use crate::resources::*;
+use crate::utils::units::Kg;
 
 /// GPU-compatible orbital state data structure
 #[repr(C)]
@@ -18,18 +19,21 @@ pub struct GpuOrbitalState {
 }
 
 impl GpuOrbitalState {
+    /// Narrow the double-precision state to the f32 layout used by the compute shader
     pub fn from_orbital_state(state: &OrbitalState) -> Self {
+        let position = state.position.as_vec3();
+        let velocity = state.velocity.as_vec3();
         Self {
-            position: [state.position.x, state.position.y, state.position.z, state.mass as f32],
-            velocity: [state.velocity.x, state.velocity.y, state.velocity.z, 0.0],
+            position: [position.x, position.y, position.z, state.mass.0 as f32],
+            velocity: [velocity.x, velocity.y, velocity.z, 0.0],
         }
     }
 
     pub fn to_orbital_state(&self) -> OrbitalState {
         OrbitalState::new(
-            Vec3::new(self.position[0], self.position[1], self.position[2]),
-            Vec3::new(self.velocity[0], self.velocity[1], self.velocity[2]),
-            self.position[3] as f64,
+            Vec3::new(self.position[0], self.position[1], self.position[2]).as_dvec3(),
+            Vec3::new(self.velocity[0], self.velocity[1], self.velocity[2]).as_dvec3(),
+            Kg(self.position[3] as f64),
         )
     }
 }
@@ -38,14 +42,14 @@ impl GpuOrbitalState {
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct GpuPhysicsParams {
-    /// Gravitational parameter (GM) in m³/s²
+    /// Gravitational parameter (GM) in km³/s², matching positions in km
     pub gm: f32,
-    /// Time step in seconds
+    /// Fixed physics timestep in seconds
     pub dt: f32,
     /// Number of objects to process
     pub object_count: u32,
-    /// Padding for alignment
-    pub _padding: u32,
+    /// Sub-steps of `dt` to integrate, as drained from the accumulator this frame
+    pub steps: u32,
 }
 
 /// Resource to sync orbital state data from main app to render app
@@ -63,7 +67,6 @@ pub fn extract_gpu_physics_data(
     orbital_query: Query<&OrbitalState, (With<PhysicsObject>, Changed<OrbitalState>)>,
     constants: Option<Res<Constants>>,
     sim_time: Option<Res<SimulationTime>>,
-    time: Res<Time>,
     gpu_state: Option<Res<GpuPhysicsState>>,
 ) {
     // Initialize resource if it doesn't exist
@@ -89,12 +92,14 @@ pub fn extract_gpu_physics_data(
         gpu_data.orbital_states.push(GpuOrbitalState::from_orbital_state(orbital_state));
     }
 
-    // Set physics parameters
+    // Same fixed step and sub-step count as the CPU physics stage, never the frame time
     gpu_data.physics_params = Some(GpuPhysicsParams {
-        gm: constants.gravitational_parameter as f32,
-        dt: (time.delta_secs() as f64 * sim_time.speed_multiplier) as f32,
+        // km³/s², consistent with the km and km/s states above; no compute shader consumes
+        // these buffers yet, and one written for them must integrate in km, km/s and km³/s²
+        gm: constants.gravitational_parameter.0 as f32,
+        dt: sim_time.timestep as f32,
         object_count: gpu_data.orbital_states.len() as u32,
-        _padding: 0,
+        steps: sim_time.frame_steps,
     });
 
     gpu_data.needs_update = false;
@@ -39,13 +39,19 @@ pub fn hud_log_system(
     );
     println!("Collisions this frame: {}", collision_count);
     println!(
-        "Sim Speed: {:.0}x | Sim Time: {:.2}s",
-        sim_time.speed_multiplier, sim_time.current
+        "Sim Speed: {:.0}x | Sim Time: {:.2}s | {}",
+        sim_time.speed_multiplier, sim_time.current, sim_time.iso8601()
     );
     println!(
         "Status: {}",
         if sim_time.paused { "PAUSED" } else { "RUNNING" }
     );
+    if sim_time.behind {
+        println!(
+            "Physics behind: {} sub-steps/frame budget, {:.0}s simulated time dropped",
+            sim_time.max_substeps, sim_time.dropped_time
+        );
+    }
     if stress_config.enabled {
         println!(
             "Spawning: {}/{}",
@@ -0,0 +1,442 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::f64::consts::TAU;
+use std::path::Path;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::lifecycle::hohmann_transfer;
+use crate::systems::reentry::{yearly_totals, SECONDS_PER_YEAR};
+use crate::utils::elements::KeplerianElements;
+use crate::utils::units::*;
+
+/// Along-track spacing in km between objects released by one launch
+const DEPLOYMENT_SPACING: f64 = 1.0;
+/// Mass in kg of each mission-related object (adapters, covers, dispensers)
+const MISSION_RELATED_OBJECT_MASS: f64 = 5.0;
+
+/// One kind of launch: what it carries and where it delivers it
+///
+/// Payloads are released on a circular insertion orbit alongside the upper stage and any
+/// mission-related objects, then raise themselves to their circular operational orbit.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LaunchProfile {
+    pub name: String,
+    pub payloads: u32,
+    /// Mass of each payload in kg
+    pub payload_mass: f64,
+    /// Propellant each payload carries in kg, `None` for what orbit raising needs plus
+    /// `PROPELLANT_FRACTION` of the mass that arrives
+    pub propellant: Option<f64>,
+    /// Specific impulse of the payload thrusters in seconds
+    pub isp: f64,
+    /// Thrust in N of the payloads' orbit-raising engine, `None` for a Hohmann transfer
+    pub raising_thrust: Option<f64>,
+    /// Altitude of the insertion orbit in km
+    pub insertion_altitude: f64,
+    /// Altitude of the operational orbit in km
+    pub target_altitude: f64,
+    /// Inclination in degrees
+    pub inclination: f64,
+    /// Right ascension of the ascending node in degrees, random when `None`
+    pub raan: Option<f64>,
+    /// Mass of the upper stage left on the insertion orbit in kg, zero if it deorbits itself
+    pub upper_stage_mass: f64,
+    /// Adapters, covers and other objects released with the payloads
+    pub mission_related_objects: u32,
+    /// Planned mission length in seconds
+    pub mission_lifetime: f64,
+    pub disposal: DisposalStrategy,
+}
+
+impl Default for LaunchProfile {
+    fn default() -> Self {
+        Self {
+            name: "Launch".to_string(),
+            payloads: 1,
+            payload_mass: 1000.0,
+            propellant: None,
+            isp: HYDRAZINE_ISP,
+            raising_thrust: None,
+            insertion_altitude: 550.0,
+            target_altitude: 550.0,
+            inclination: 53.0,
+            raan: None,
+            upper_stage_mass: 0.0,
+            mission_related_objects: 0,
+            mission_lifetime: 5.0 * SECONDS_PER_YEAR,
+            disposal: DisposalStrategy::Deorbit { perigee_altitude: 200.0 },
+        }
+    }
+}
+
+/// A launch from a schedule file
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ScheduledLaunch {
+    /// Simulation time of the launch in seconds
+    pub time: f64,
+    #[serde(flatten)]
+    pub profile: LaunchProfile,
+}
+
+/// A launch profile and how often it is drawn relative to the others
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WeightedProfile {
+    pub weight: f64,
+    #[serde(flatten)]
+    pub profile: LaunchProfile,
+}
+
+/// Statistical launch traffic: Poisson launches at a growing annual rate, each drawn from a
+/// weighted mix of profiles
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LaunchModel {
+    /// Launches per year at the start of the simulation
+    pub launches_per_year: f64,
+    /// Fractional growth of the launch rate per year
+    pub growth_rate: f64,
+    pub profiles: Vec<WeightedProfile>,
+}
+
+impl Default for LaunchModel {
+    /// Traffic resembling the early 2020s: constellation deployment, rideshares, GNSS and GEO
+    fn default() -> Self {
+        let profile = |weight: f64, profile: LaunchProfile| WeightedProfile { weight, profile };
+        Self {
+            launches_per_year: 200.0,
+            growth_rate: 0.0,
+            profiles: vec![
+                profile(0.5, LaunchProfile {
+                    name: "Constellation".to_string(),
+                    payloads: 22,
+                    payload_mass: 800.0,
+                    isp: 1500.0, // Hall-effect thrusters
+                    raising_thrust: Some(0.05),
+                    insertion_altitude: 300.0,
+                    target_altitude: 550.0,
+                    inclination: 53.0,
+                    ..Default::default()
+                }),
+                profile(0.2, LaunchProfile {
+                    name: "Rideshare".to_string(),
+                    payloads: 20,
+                    payload_mass: 100.0,
+                    insertion_altitude: 525.0,
+                    target_altitude: 525.0,
+                    inclination: 97.5,
+                    upper_stage_mass: 4000.0,
+                    mission_related_objects: 2,
+                    disposal: DisposalStrategy::Abandon,
+                    ..Default::default()
+                }),
+                profile(0.1, LaunchProfile {
+                    name: "GNSS".to_string(),
+                    payload_mass: 1500.0,
+                    insertion_altitude: 20200.0,
+                    target_altitude: 20200.0,
+                    inclination: 55.0,
+                    upper_stage_mass: 2000.0,
+                    mission_lifetime: 12.0 * SECONDS_PER_YEAR,
+                    disposal: DisposalStrategy::Graveyard { raise: 500.0 },
+                    ..Default::default()
+                }),
+                profile(0.2, LaunchProfile {
+                    name: "GEO".to_string(),
+                    payload_mass: 4000.0,
+                    insertion_altitude: 500.0,
+                    target_altitude: 35786.0,
+                    inclination: 0.0,
+                    upper_stage_mass: 3000.0,
+                    mission_related_objects: 1,
+                    mission_lifetime: 15.0 * SECONDS_PER_YEAR,
+                    disposal: DisposalStrategy::geo_graveyard(),
+                    ..Default::default()
+                }),
+            ],
+        }
+    }
+}
+
+impl LaunchModel {
+    /// Launch rate in launches per second at simulation time `time`
+    pub fn rate(&self, time: f64) -> f64 {
+        self.launches_per_year * (1.0 + self.growth_rate).powf(time / SECONDS_PER_YEAR) / SECONDS_PER_YEAR
+    }
+}
+
+/// Parse a JSON launch schedule, sorted by launch time
+pub fn parse_launch_schedule(content: &str) -> Result<Vec<ScheduledLaunch>, String> {
+    let mut schedule: Vec<ScheduledLaunch> =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse launch schedule: {}", e))?;
+    schedule.sort_by(|a, b| a.time.total_cmp(&b.time));
+    Ok(schedule)
+}
+
+/// Load a JSON launch schedule file
+pub fn load_launch_schedule(path: &Path) -> Result<Vec<ScheduledLaunch>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read launch schedule {}: {}", path.display(), e))?;
+    parse_launch_schedule(&content)
+}
+
+/// Resource to control launch traffic
+#[derive(Resource, Clone, Debug)]
+pub struct LaunchConfig {
+    pub enabled: bool,
+    /// Launches at fixed times, earliest first
+    pub schedule: Vec<ScheduledLaunch>,
+    /// Statistical traffic on top of the schedule
+    pub model: Option<LaunchModel>,
+    /// Vent upper stages after payload release so they cannot explode
+    pub passivate_upper_stages: bool,
+}
+
+impl Default for LaunchConfig {
+    /// No traffic until a schedule or model is set
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            schedule: Vec::new(),
+            model: None,
+            passivate_upper_stages: false,
+        }
+    }
+}
+
+/// A launch that has happened
+#[derive(Clone, Debug)]
+pub struct LaunchRecord {
+    pub name: String,
+    /// Simulation time of the launch in seconds
+    pub time: f64,
+    pub payloads: u32,
+    pub upper_stage: bool,
+    pub mission_related_objects: u32,
+    /// Total mass placed in orbit in kg
+    pub mass: f64,
+}
+
+impl LaunchRecord {
+    /// Objects placed in orbit
+    pub fn objects(&self) -> usize {
+        self.payloads as usize + self.upper_stage as usize + self.mission_related_objects as usize
+    }
+}
+
+/// Resource logging every launch
+#[derive(Resource, Default, Debug)]
+pub struct LaunchLog {
+    pub launches: Vec<LaunchRecord>,
+}
+
+impl LaunchLog {
+    pub fn total_payloads(&self) -> usize {
+        self.launches.iter().map(|launch| launch.payloads as usize).sum()
+    }
+
+    pub fn total_objects(&self) -> usize {
+        self.launches.iter().map(LaunchRecord::objects).sum()
+    }
+
+    /// Launch count and objects placed in orbit for each simulated year
+    pub fn yearly_summary(&self) -> Vec<(usize, usize)> {
+        yearly_totals(&self.launches, |launch| launch.time, |year: &mut (usize, usize), launch| {
+            year.0 += 1;
+            year.1 += launch.objects();
+        })
+    }
+}
+
+/// Largest mean drawn in one go by the product-of-uniforms method; beyond a few hundred
+/// `exp(-mean)` loses precision and past about 745 it underflows to zero
+const POISSON_CHUNK: f64 = 500.0;
+
+/// Number of events of a Poisson process with the given mean
+///
+/// Large means, such as a multi-year analytical frame, are split into chunks of at most
+/// `POISSON_CHUNK` whose counts are summed, since a sum of Poisson draws is Poisson.
+pub fn poisson<R: Rng>(rng: &mut R, mean: f64) -> u32 {
+    let mut remaining = mean;
+    let mut count = 0;
+    while remaining > 0.0 {
+        let chunk = remaining.min(POISSON_CHUNK);
+        remaining -= chunk;
+        let limit = (-chunk).exp();
+        let mut product: f64 = rng.gen();
+        while product > limit {
+            count += 1;
+            product *= rng.gen::<f64>();
+        }
+    }
+    count
+}
+
+/// Spawn a launch's payloads, upper stage and mission-related objects at simulation time `now`
+///
+/// Objects are strung out along the insertion orbit from a random point. Payloads start their
+/// mission in orbit raising, with burns if they need to climb, and take up a station-keeping
+/// box once they reach their operational orbit. Payloads and the upper stage take catalog
+/// numbers from `catalog`.
+pub fn spawn_launch<R: Rng>(
+    commands: &mut Commands,
+    profile: &LaunchProfile,
+    now: f64,
+    catalog: &mut CatalogNumbers,
+    passivate_upper_stage: bool,
+    constants: &Constants,
+    rng: &mut R,
+) -> LaunchRecord {
+    let mu = constants.gravitational_parameter.0;
+    let insertion = (constants.earth_radius + Km(profile.insertion_altitude)).0;
+    let target = (constants.earth_radius + Km(profile.target_altitude)).0;
+    let raan = profile.raan.map_or_else(|| rng.gen_range(0.0..TAU), f64::to_radians);
+    let latitude_argument = rng.gen_range(0.0..TAU);
+    let state_at = |slot: f64, mass: f64| {
+        let elements = KeplerianElements {
+            semi_major_axis: insertion,
+            eccentricity: 0.0,
+            inclination: profile.inclination.to_radians(),
+            raan,
+            arg_perigee: 0.0,
+            true_anomaly: latitude_argument + slot * DEPLOYMENT_SPACING / insertion,
+        };
+        let (position, velocity) = elements.to_state(mu);
+        OrbitalState::new(position, velocity, Kg(mass))
+    };
+
+    let raising = if (target - insertion).abs() > DEPLOYMENT_SPACING {
+        match profile.raising_thrust {
+            Some(thrust) => {
+                // Slow tangential spiral: the delta-V is the difference in circular speeds,
+                // prograde to climb
+                let delta_v = (mu / insertion).sqrt() - (mu / target).sqrt();
+                vec![Burn::finite(now, DVec3::new(0.0, delta_v, 0.0), BurnFrame::Rtn, profile.isp, thrust)]
+            }
+            None => hohmann_transfer(insertion, target, now, mu, profile.isp).to_vec(),
+        }
+    } else {
+        Vec::new()
+    };
+    let propellant = profile.propellant.unwrap_or_else(|| {
+        let arrival = raising.iter().fold(profile.payload_mass, |mass, burn| mass - burn.propellant_mass(Kg(mass)).0);
+        profile.payload_mass - arrival + PROPELLANT_FRACTION * arrival
+    });
+
+    for payload in 0..profile.payloads {
+        let mut plan = ManeuverPlan::new(propellant);
+        raising.iter().for_each(|burn| plan.schedule(*burn));
+        let mission = Mission::new(now, profile.mission_lifetime, profile.disposal).with_orbit_raising();
+
+        commands.spawn((
+            Satellite::new(format!("{} {}", profile.name, payload + 1), catalog.allocate(1), true),
+            state_at(payload as f64, profile.payload_mass),
+            PhysicsObject::satellite(Kg(profile.payload_mass)),
+            ObjectClass::Spacecraft,
+            mission,
+            plan,
+            RenderAsSatellite,
+            crate::components::trails::Trail::new(500, profile.target_altitude),
+        ));
+    }
+
+    let mut slot = -1.0;
+    let upper_stage = profile.upper_stage_mass > 0.0;
+    if upper_stage {
+        let mut stage = commands.spawn((
+            Satellite::new(format!("{} R/B", profile.name), catalog.allocate(1), false),
+            state_at(slot, profile.upper_stage_mass),
+            PhysicsObject::satellite(Kg(profile.upper_stage_mass)),
+            ObjectClass::RocketBody,
+            RenderAsSatellite,
+        ));
+        if passivate_upper_stage {
+            stage.insert(Passivated);
+        }
+        slot -= 1.0;
+    }
+
+    for _ in 0..profile.mission_related_objects {
+        commands.spawn((
+            state_at(slot, MISSION_RELATED_OBJECT_MASS),
+            PhysicsObject::debris(Kg(MISSION_RELATED_OBJECT_MASS)),
+            Debris::new(None, 0, now),
+            ObjectClass::Debris,
+            RenderAsDebris,
+        ));
+        slot -= 1.0;
+    }
+
+    LaunchRecord {
+        name: profile.name.clone(),
+        time: now,
+        payloads: profile.payloads,
+        upper_stage,
+        mission_related_objects: profile.mission_related_objects,
+        mass: profile.payloads as f64 * profile.payload_mass
+            + profile.upper_stage_mass
+            + profile.mission_related_objects as f64 * MISSION_RELATED_OBJECT_MASS,
+    }
+}
+
+/// How far `launch_system` has got through the schedule and simulation time
+#[derive(Default)]
+pub struct LaunchProgress {
+    /// Index of the next scheduled launch
+    next_scheduled: usize,
+    /// Simulation time of the previous run
+    last_time: Option<f64>,
+}
+
+/// System to launch scheduled and statistically drawn traffic
+pub fn launch_system(
+    mut commands: Commands,
+    config: Res<LaunchConfig>,
+    mut log: ResMut<LaunchLog>,
+    mut catalog: ResMut<CatalogNumbers>,
+    sim_time: Res<SimulationTime>,
+    constants: Res<Constants>,
+    mut progress: Local<LaunchProgress>,
+) {
+    let now = sim_time.current;
+    let dt = now - progress.last_time.unwrap_or(now);
+    progress.last_time = Some(now);
+
+    if !config.enabled {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut due: Vec<&LaunchProfile> = Vec::new();
+    while let Some(launch) = config.schedule.get(progress.next_scheduled).filter(|launch| launch.time <= now) {
+        due.push(&launch.profile);
+        progress.next_scheduled += 1;
+    }
+    if let Some(model) = config.model.as_ref().filter(|_| dt > 0.0) {
+        for _ in 0..poisson(&mut rng, model.rate(now - 0.5 * dt) * dt) {
+            if let Ok(weighted) = model.profiles.choose_weighted(&mut rng, |p| p.weight) {
+                due.push(&weighted.profile);
+            }
+        }
+    }
+
+    for profile in due {
+        let record = spawn_launch(
+            &mut commands,
+            profile,
+            now,
+            &mut catalog,
+            config.passivate_upper_stages,
+            &constants,
+            &mut rng,
+        );
+        info!(
+            "Launch: {} placed {} objects ({:.0} kg) in orbit",
+            record.name,
+            record.objects(),
+            record.mass
+        );
+        log.launches.push(record);
+    }
+}
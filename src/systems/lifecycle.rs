@@ -0,0 +1,284 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use rand::Rng;
+use std::f64::consts::{PI, TAU};
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::reentry::{ReentryConfig, SECONDS_PER_YEAR};
+use crate::systems::station_keeping::operational_station_keeping;
+use crate::utils::analytical::AnalyticalPropagator;
+use crate::utils::atmosphere::AtmosphereModel;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::gravity::GravityModel;
+use crate::utils::units::*;
+
+/// Eccentricity below which a deorbit burn fires at once rather than waiting for apogee
+const CIRCULAR_ECCENTRICITY: f64 = 1e-3;
+
+/// Top of low Earth orbit in km of altitude
+const LEO_CEILING: f64 = 2000.0;
+
+/// Resource to control end-of-life disposal
+#[derive(Resource, Clone, Debug)]
+pub struct LifecycleConfig {
+    pub enabled: bool,
+    /// Fraction of disposal attempts that succeed; the rest die in orbit at end of life
+    pub disposal_success_rate: f64,
+    /// Residual orbital lifetime in seconds a satellite may be left with: 25 years under the
+    /// IADC guideline, 5 under the FCC rule
+    pub compliance_lifetime: f64,
+    /// Density model used to estimate residual orbital lifetime
+    pub atmosphere: AtmosphereModel,
+    /// Specific impulse of the disposal thrusters in seconds
+    pub isp: f64,
+}
+
+impl Default for LifecycleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            disposal_success_rate: 0.9,
+            compliance_lifetime: 25.0 * SECONDS_PER_YEAR,
+            atmosphere: AtmosphereModel::Exponential,
+            isp: HYDRAZINE_ISP,
+        }
+    }
+}
+
+/// Resource totalling how missions have ended
+#[derive(Resource, Default, Debug)]
+pub struct LifecycleStatistics {
+    pub natural_decay: usize,
+    pub disposed: usize,
+    pub disposal_failed: usize,
+    pub abandoned: usize,
+    /// Delta-V planned for disposals in km/s
+    pub disposal_delta_v: f64,
+}
+
+impl LifecycleStatistics {
+    pub fn record(&mut self, outcome: MissionOutcome) {
+        match outcome {
+            MissionOutcome::NaturalDecay => self.natural_decay += 1,
+            MissionOutcome::Disposed => self.disposed += 1,
+            MissionOutcome::DisposalFailed => self.disposal_failed += 1,
+            MissionOutcome::Abandoned => self.abandoned += 1,
+        }
+    }
+
+    pub fn missions_ended(&self) -> usize {
+        self.natural_decay + self.disposed + self.disposal_failed + self.abandoned
+    }
+
+    /// Fraction of ended missions that met disposal guidelines, `None` before any ended
+    pub fn compliance_rate(&self) -> Option<f64> {
+        let ended = self.missions_ended();
+        (ended > 0).then(|| (self.natural_decay + self.disposed) as f64 / ended as f64)
+    }
+}
+
+/// Tangential burns moving between circular orbits of radius `from` and `to` km, the second
+/// half a transfer orbit after the first at `now`
+pub fn hohmann_transfer(from: f64, to: f64, now: f64, mu: f64, isp: f64) -> [Burn; 2] {
+    let transfer = 0.5 * (from + to);
+    let transfer_time = PI * (transfer.powi(3) / mu).sqrt();
+    let departure = (mu * (2.0 / from - 1.0 / transfer)).sqrt() - (mu / from).sqrt();
+    let arrival = (mu / to).sqrt() - (mu * (2.0 / to - 1.0 / transfer)).sqrt();
+    [tangential_burn(now, departure, isp), tangential_burn(now + transfer_time, arrival, isp)]
+}
+
+/// Impulsive burn of `delta_v` km/s along the velocity
+fn tangential_burn(time: f64, delta_v: f64, isp: f64) -> Burn {
+    Burn::impulsive(time, DVec3::new(0.0, delta_v, 0.0), BurnFrame::Rtn, isp)
+}
+
+/// Burns carrying out a disposal strategy from the current state, empty if nothing needs doing
+///
+/// A deorbit lowers perigee with one retrograde burn at apogee. A graveyard raise is a
+/// Hohmann transfer from the mean orbit, circularising half a transfer orbit later.
+pub fn disposal_burns(
+    strategy: &DisposalStrategy,
+    orbital_state: &OrbitalState,
+    now: f64,
+    constants: &Constants,
+    isp: f64,
+) -> Vec<Burn> {
+    let propagator = AnalyticalPropagator::new(constants, GravityModel::J2, None);
+    let mu = propagator.mu;
+    let (position, velocity) = (orbital_state.position, orbital_state.velocity);
+
+    match *strategy {
+        DisposalStrategy::Deorbit { perigee_altitude } => {
+            let elements = KeplerianElements::from_state(position, velocity, mu);
+            let perigee = (constants.earth_radius + Km(perigee_altitude)).0;
+            let Some(mean_motion) = elements.mean_motion(mu) else {
+                return Vec::new();
+            };
+            if elements.perigee_radius() <= perigee {
+                return Vec::new();
+            }
+
+            let to_apogee = if elements.eccentricity < CIRCULAR_ECCENTRICITY {
+                0.0
+            } else {
+                (PI - elements.mean_anomaly()).rem_euclid(TAU) / mean_motion
+            };
+            let Some(at_apogee) = propagator.propagate(&elements, to_apogee, 0.0) else {
+                return Vec::new();
+            };
+            let (apogee_position, apogee_velocity) = at_apogee.to_state(mu);
+            let apogee = apogee_position.length();
+            let speed = (2.0 * mu * perigee / (apogee * (apogee + perigee))).sqrt();
+            vec![tangential_burn(now + to_apogee, speed - apogee_velocity.length(), isp)]
+        }
+        DisposalStrategy::Graveyard { raise } => {
+            let inner = propagator.mean_semi_major_axis(position, velocity);
+            hohmann_transfer(inner, inner + raise, now, mu, isp).to_vec()
+        }
+        DisposalStrategy::Abandon => Vec::new(),
+    }
+}
+
+/// Mission of a satellite starting operations at `now`, with a typical lifetime and disposal
+/// for its orbit
+///
+/// Geostationary satellites serve 15 years before a graveyard raise and others above low
+/// Earth orbit 12 years before a smaller raise; low Earth orbit satellites serve 5 years
+/// before deorbiting.
+pub fn new_mission(orbital_state: &OrbitalState, geostationary: bool, now: f64, constants: &Constants) -> Mission {
+    let (lifetime, disposal) = if geostationary {
+        (15.0, DisposalStrategy::geo_graveyard())
+    } else if (orbital_state.radius() - constants.earth_radius).0 > LEO_CEILING {
+        (12.0, DisposalStrategy::Graveyard { raise: 500.0 })
+    } else {
+        (5.0, DisposalStrategy::Deorbit { perigee_altitude: 200.0 })
+    };
+    Mission::new(now, lifetime * SECONDS_PER_YEAR, disposal)
+}
+
+/// Mission of a satellite already in service at `now`, drawn partway through the typical
+/// lifetime of `new_mission`
+pub fn in_service_mission<R: Rng>(
+    orbital_state: &OrbitalState,
+    geostationary: bool,
+    now: f64,
+    constants: &Constants,
+    rng: &mut R,
+) -> Mission {
+    let mission = new_mission(orbital_state, geostationary, now, constants);
+    Mission { start: now - rng.gen_range(0.0..mission.lifetime), ..mission }
+}
+
+/// Components read from each satellite with a mission
+type MissionItem<'a> = (
+    Entity,
+    &'a OrbitalState,
+    &'a PhysicsObject,
+    &'a mut Satellite,
+    &'a mut Mission,
+    Option<&'a mut ManeuverPlan>,
+);
+
+/// System to end missions and carry out their disposal
+///
+/// Satellites start operations once their orbit-raising burns are done, holding the
+/// station-keeping box they arrive in. At end of life station-keeping stops. A satellite
+/// whose orbit decays within the compliance lifetime needs no disposal; otherwise it
+/// carries out its strategy, failing at the configured rate. Satellites that die in service
+/// never dispose. Compliant satellites are passivated, so only failed and abandoned ones keep
+/// exploding at the spacecraft rate.
+pub fn lifecycle_system(
+    mut commands: Commands,
+    config: Res<LifecycleConfig>,
+    mut statistics: ResMut<LifecycleStatistics>,
+    sim_time: Res<SimulationTime>,
+    constants: Res<Constants>,
+    reentry_config: Option<Res<ReentryConfig>>,
+    mut mission_query: Query<MissionItem<'_>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let now = sim_time.current;
+    let epoch = sim_time.now();
+    let interface_altitude = reentry_config.map_or(120.0, |reentry| reentry.interface_altitude_km);
+    let propagator = AnalyticalPropagator::new(&constants, GravityModel::J2, Some(config.atmosphere));
+    let mut rng = rand::thread_rng();
+
+    for (entity, orbital_state, physics_object, mut satellite, mut mission, mut plan) in mission_query.iter_mut() {
+        let outcome = match mission.phase {
+            MissionPhase::Ended(_) => continue,
+            MissionPhase::OrbitRaising if !satellite.active => MissionOutcome::DisposalFailed,
+            MissionPhase::OrbitRaising => {
+                if plan.as_ref().is_none_or(|p| p.is_idle()) {
+                    mission.phase = MissionPhase::Operational;
+                    mission.start = now;
+                    commands.entity(entity).insert(operational_station_keeping(orbital_state, now, &epoch, &constants));
+                    info!("Lifecycle: {} reached its operational orbit", satellite.name);
+                }
+                continue;
+            }
+            MissionPhase::Disposing => {
+                if plan.as_ref().is_some_and(|p| !p.is_idle()) {
+                    continue;
+                }
+                // A plan that ran dry may have cut the last burn short
+                if plan.as_ref().is_some_and(|p| p.propellant <= 0.0) {
+                    MissionOutcome::DisposalFailed
+                } else {
+                    MissionOutcome::Disposed
+                }
+            }
+            MissionPhase::Operational if !satellite.active => MissionOutcome::DisposalFailed,
+            MissionPhase::Operational if now < mission.end_of_life() => continue,
+            MissionPhase::Operational => {
+                commands.entity(entity).remove::<StationKeeping>();
+                if let Some(plan) = plan.as_mut() {
+                    plan.cancel_pending();
+                }
+
+                let semi_major_axis = propagator.mean_semi_major_axis(orbital_state.position, orbital_state.velocity);
+                let ballistic_coefficient = physics_object.ballistic_coefficient(orbital_state.mass);
+                let lifetime = propagator.orbital_lifetime(semi_major_axis, ballistic_coefficient, interface_altitude);
+                if lifetime <= config.compliance_lifetime {
+                    MissionOutcome::NaturalDecay
+                } else if mission.disposal == DisposalStrategy::Abandon {
+                    MissionOutcome::Abandoned
+                } else if rng.gen::<f64>() >= config.disposal_success_rate {
+                    MissionOutcome::DisposalFailed
+                } else {
+                    let burns = disposal_burns(&mission.disposal, orbital_state, now, &constants, config.isp);
+                    if burns.is_empty() {
+                        MissionOutcome::Disposed
+                    } else {
+                        statistics.disposal_delta_v += burns.iter().map(|b| b.delta_v.length()).sum::<f64>();
+                        match plan {
+                            Some(mut plan) => burns.into_iter().for_each(|burn| plan.schedule(burn)),
+                            None => {
+                                let mut plan = ManeuverPlan::for_spacecraft(orbital_state.mass);
+                                burns.into_iter().for_each(|burn| plan.schedule(burn));
+                                commands.entity(entity).insert(plan);
+                            }
+                        }
+                        mission.phase = MissionPhase::Disposing;
+                        info!("Lifecycle: {} began {:?} disposal", satellite.name, mission.disposal);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        mission.phase = MissionPhase::Ended(outcome);
+        satellite.active = false;
+        if outcome.is_compliant() {
+            commands.entity(entity).insert(Passivated);
+        }
+        statistics.record(outcome);
+        info!(
+            "Lifecycle: {} mission ended ({:?}) after {:.1} years",
+            satellite.name,
+            outcome,
+            (now - mission.start) / SECONDS_PER_YEAR
+        );
+    }
+}
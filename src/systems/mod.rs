@@ -5,10 +5,17 @@ pub mod gpu_physics;
 pub mod gpu_instancing;
 pub mod simple_gpu_render;
 pub mod collision;
+pub mod collision_avoidance;
 pub mod analytics;
 pub mod rendering;
 pub mod stress_test;
 pub mod debris_mechanics;
+pub mod reentry;
+pub mod station_keeping;
+pub mod lifecycle;
+pub mod launch;
+pub mod constellation;
+pub mod explosion;
 pub mod tracking_ui;
 pub mod hud;
 pub mod materials;
@@ -26,10 +33,17 @@ pub use optimized_physics::*;
 pub use gpu_physics::*;
 pub use gpu_instancing::*;
 pub use collision::*;
+pub use collision_avoidance::*;
 pub use analytics::*;
 pub use rendering::*;
 pub use stress_test::*;
 pub use debris_mechanics::*;
+pub use reentry::*;
+pub use station_keeping::*;
+pub use lifecycle::*;
+pub use launch::*;
+pub use constellation::*;
+pub use explosion::*;
 pub use tracking_ui::*;
 pub use trail_rendering::*;
 pub use ui::*;
@@ -1,232 +1,227 @@
+use bevy::math::DVec3;
 use bevy::prelude::*;
-use crate::components::*;
-use crate::resources::*;
-
-/// Optimized orbital state using aligned data structures for better cache performance
-#[repr(C, align(32))] // 32-byte alignment for SIMD operations
-#[derive(Clone, Copy)]
-pub struct OptimizedOrbitalState {
-    pub position: [f32; 4],  // x, y, z, mass (aligned for SIMD)
-    pub velocity: [f32; 4],  // vx, vy, vz, padding
+use crate::utils::integrators::{FrameIntegration, IntegratorConfig};
+use crate::utils::forces::ForceCoefficients;
+
+/// Generation-checked handle to an object's slot in the packed store
+///
+/// Handles stay valid while the object is in the store no matter how the dense arrays are reordered,
+/// and stop resolving once it is removed, even if the slot is reused.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PackedHandle {
+    index: u32,
+    generation: u32,
 }
 
-/// Resource to hold optimized physics data
-#[derive(Resource)]
-pub struct OptimizedPhysicsData {
-    pub states: Vec<OptimizedOrbitalState>,
-    pub entity_map: Vec<Entity>,
-    pub dirty: bool,
+#[derive(Clone, Copy, Default)]
+struct Slot {
+    generation: u32,
+    dense: Option<u32>,
 }
 
-impl Default for OptimizedPhysicsData {
-    fn default() -> Self {
-        Self {
-            states: Vec::new(),
-            entity_map: Vec::new(),
-            dirty: true,
+/// Persistent structure-of-arrays store for the parallel physics backend
+///
+/// Objects are added and removed incrementally; removal swap-removes from the dense arrays
+/// and the slot table keeps every other handle pointing at its moved entry.
+#[derive(Default)]
+pub struct PackedPhysicsStore {
+    slots: Vec<Slot>,
+    free_slots: Vec<u32>,
+    /// Slot owning each dense entry
+    dense_slots: Vec<u32>,
+    /// Entries visited since the last `begin_sync`
+    seen: Vec<bool>,
+    entities: Vec<Entity>,
+    /// Positions in km
+    positions: Vec<DVec3>,
+    /// Velocities in km/s
+    velocities: Vec<DVec3>,
+    /// Drag and radiation pressure coefficients
+    coefficients: Vec<ForceCoefficients>,
+    /// Previous adaptive step proposals in seconds
+    adaptive_steps: Vec<Option<f64>>,
+}
+
+impl PackedPhysicsStore {
+    pub fn insert(
+        &mut self,
+        entity: Entity,
+        position: DVec3,
+        velocity: DVec3,
+        coefficients: ForceCoefficients,
+        adaptive_step: Option<f64>,
+    ) -> PackedHandle {
+        let dense = self.entities.len() as u32;
+        let index = match self.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot::default());
+                self.slots.len() as u32 - 1
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.dense = Some(dense);
+        let generation = slot.generation;
+
+        self.dense_slots.push(index);
+        self.seen.push(true);
+        self.entities.push(entity);
+        self.positions.push(position);
+        self.velocities.push(velocity);
+        self.coefficients.push(coefficients);
+        self.adaptive_steps.push(adaptive_step);
+
+        PackedHandle { index, generation }
+    }
+
+    /// Dense index of a live handle
+    pub fn index(&self, handle: PackedHandle) -> Option<usize> {
+        self.slots
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.dense)
+            .map(|dense| dense as usize)
+    }
+
+    pub fn contains(&self, handle: PackedHandle) -> bool {
+        self.index(handle).is_some()
+    }
+
+    /// Remove an object, returning whether the handle was still live
+    pub fn remove(&mut self, handle: PackedHandle) -> bool {
+        match self.index(handle) {
+            Some(dense) => {
+                self.swap_remove(dense);
+                true
+            }
+            None => false,
         }
     }
-}
 
-/// Component to mark entities for optimized physics processing
-#[derive(Component)]
-pub struct OptimizedPhysics {
-    pub index: usize,
-}
+    /// Remove every object, invalidating all outstanding handles
+    pub fn clear(&mut self) {
+        while !self.entities.is_empty() {
+            self.swap_remove(self.entities.len() - 1);
+        }
+    }
 
-/// System to prepare optimized physics data
-pub fn prepare_optimized_physics_system(
-    mut optimized_data: ResMut<OptimizedPhysicsData>,
-    orbital_query: Query<(Entity, &OrbitalState), (With<PhysicsObject>, Without<OptimizedPhysics>)>,
-    mut commands: Commands,
-) {
-    // Check if we have new entities to optimize
-    if orbital_query.is_empty() {
-        return;
-    }
-
-    info!("Preparing {} objects for optimized physics", orbital_query.iter().count());
-
-    // Clear and rebuild optimized data
-    optimized_data.states.clear();
-    optimized_data.entity_map.clear();
-
-    for (entity, orbital_state) in orbital_query.iter() {
-        let index = optimized_data.states.len();
-        
-        // Convert to optimized format
-        let optimized_state = OptimizedOrbitalState {
-            position: [
-                orbital_state.position.x,
-                orbital_state.position.y,
-                orbital_state.position.z,
-                orbital_state.mass as f32,
-            ],
-            velocity: [
-                orbital_state.velocity.x,
-                orbital_state.velocity.y,
-                orbital_state.velocity.z,
-                0.0,
-            ],
-        };
+    fn swap_remove(&mut self, dense: usize) {
+        let index = self.dense_slots.swap_remove(dense);
+        self.seen.swap_remove(dense);
+        self.entities.swap_remove(dense);
+        self.positions.swap_remove(dense);
+        self.velocities.swap_remove(dense);
+        self.coefficients.swap_remove(dense);
+        self.adaptive_steps.swap_remove(dense);
+
+        // The generation bump is what makes stale handles stop resolving
+        let slot = &mut self.slots[index as usize];
+        slot.generation = slot.generation.wrapping_add(1);
+        slot.dense = None;
+        self.free_slots.push(index);
+
+        if let Some(&moved) = self.dense_slots.get(dense) {
+            self.slots[moved as usize].dense = Some(dense as u32);
+        }
+    }
 
-        optimized_data.states.push(optimized_state);
-        optimized_data.entity_map.push(entity);
+    /// Start a sync pass; entries not marked seen by `end_sync` are dropped
+    pub fn begin_sync(&mut self) {
+        self.seen.fill(false);
+    }
 
-        // Mark entity as optimized
-        commands.entity(entity).insert(OptimizedPhysics { index });
+    pub fn mark_seen(&mut self, dense: usize) {
+        self.seen[dense] = true;
     }
 
-    optimized_data.dirty = true;
-    info!("Optimized physics prepared for {} objects", optimized_data.states.len());
-}
+    /// Drop entries whose entities were not seen since `begin_sync`, returning how many
+    pub fn end_sync(&mut self) -> usize {
+        let mut removed = 0;
+        for dense in (0..self.entities.len()).rev() {
+            if !self.seen[dense] {
+                self.swap_remove(dense);
+                removed += 1;
+            }
+        }
+        removed
+    }
 
-/// High-performance parallel physics system using SIMD and multithreading
-pub fn optimized_physics_system(
-    mut optimized_data: ResMut<OptimizedPhysicsData>,
-    constants: Res<Constants>,
-    sim_time: Res<SimulationTime>,
-) {
-    // Don't run if paused or no objects
-    if sim_time.paused || optimized_data.states.is_empty() {
-        return;
+    pub fn set_state(&mut self, dense: usize, position: DVec3, velocity: DVec3) {
+        self.positions[dense] = position;
+        self.velocities[dense] = velocity;
     }
 
-    let gm = constants.gravitational_parameter as f32;
-    let dt = sim_time.timestep as f32;
+    pub fn set_coefficients(&mut self, dense: usize, coefficients: ForceCoefficients) {
+        self.coefficients[dense] = coefficients;
+    }
 
-    // Compute physics directly using parallel processing
-    compute_physics_parallel(&mut optimized_data.states, gm, dt);
-    optimized_data.dirty = true;
-}
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
 
-/// Parallel physics computation using chunked processing
-fn compute_physics_parallel(
-    states: &mut [OptimizedOrbitalState],
-    gm: f32,
-    dt: f32,
-) {
-    use rayon::prelude::*;
+    pub fn position(&self, dense: usize) -> DVec3 {
+        self.positions[dense]
+    }
 
-    // Process physics in parallel chunks
-    states.par_iter_mut().for_each(|state| {
-        compute_orbital_physics_simd(state, gm, dt);
-    });
-}
+    pub fn velocity(&self, dense: usize) -> DVec3 {
+        self.velocities[dense]
+    }
 
-/// SIMD-optimized orbital physics computation for a single object
-#[inline(always)]
-fn compute_orbital_physics_simd(state: &mut OptimizedOrbitalState, gm: f32, dt: f32) {
-    // Load position and velocity
-    let pos = [state.position[0], state.position[1], state.position[2]];
-    let vel = [state.velocity[0], state.velocity[1], state.velocity[2]];
-    let mass = state.position[3];
-
-    // Skip invalid objects
-    if mass <= 0.0 {
-        return;
-    }
-
-    // Calculate distance from Earth center (in km)
-    let r_mag_km = (pos[0] * pos[0] + pos[1] * pos[1] + pos[2] * pos[2]).sqrt();
-    
-    if r_mag_km <= 0.0 {
-        return;
-    }
-
-    // MATCH ORIGINAL PHYSICS: Use r² instead of r³ (the original has this "bug" but it works)
-    // Convert to meters for gravitational calculation
-    let r_mag_m = r_mag_km * 1000.0;
-    
-    // Use original formula: a = -GM/r² * r̂ (matches original physics.rs line 38)
-    let acc_magnitude = -gm / (r_mag_m * r_mag_m);  // Note: r² not r³, matching original
-    
-    // Unit vector components
-    let r_unit_x = pos[0] / r_mag_km;
-    let r_unit_y = pos[1] / r_mag_km;
-    let r_unit_z = pos[2] / r_mag_km;
-    
-    // Acceleration in km/s² (matches original physics.rs lines 46-49)
-    let acc_km_s2 = acc_magnitude / 1000.0;
-    let acc_x = r_unit_x * acc_km_s2;
-    let acc_y = r_unit_y * acc_km_s2;
-    let acc_z = r_unit_z * acc_km_s2;
-
-    // Euler integration
-    let new_vel_x = vel[0] + acc_x * dt;
-    let new_vel_y = vel[1] + acc_y * dt;
-    let new_vel_z = vel[2] + acc_z * dt;
-
-    let new_pos_x = pos[0] + new_vel_x * dt;
-    let new_pos_y = pos[1] + new_vel_y * dt;
-    let new_pos_z = pos[2] + new_vel_z * dt;
-
-    // Store results
-    state.position[0] = new_pos_x;
-    state.position[1] = new_pos_y;
-    state.position[2] = new_pos_z;
-    
-    state.velocity[0] = new_vel_x;
-    state.velocity[1] = new_vel_y;
-    state.velocity[2] = new_vel_z;
-}
+    pub fn adaptive_step(&self, dense: usize) -> Option<f64> {
+        self.adaptive_steps[dense]
+    }
 
-/// System to apply optimized results back to ECS components
-pub fn apply_optimized_physics_system(
-    optimized_data: Res<OptimizedPhysicsData>,
-    mut orbital_query: Query<&mut OrbitalState, With<OptimizedPhysics>>,
-    optimized_query: Query<&OptimizedPhysics>,
-) {
-    if !optimized_data.dirty {
-        return;
-    }
-
-    // Apply results back to ECS components
-    for (optimized_physics, &entity) in optimized_query.iter().zip(optimized_data.entity_map.iter()) {
-        if let Ok(mut orbital_state) = orbital_query.get_mut(entity) {
-            let index = optimized_physics.index;
-            if index < optimized_data.states.len() {
-                let state = &optimized_data.states[index];
-                
-                // Update ECS component
-                orbital_state.position = Vec3::new(
-                    state.position[0],
-                    state.position[1],
-                    state.position[2],
-                );
-                orbital_state.velocity = Vec3::new(
-                    state.velocity[0],
-                    state.velocity[1],
-                    state.velocity[2],
-                );
-                orbital_state.mass = state.position[3] as f64;
-            }
-        }
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Integrate every packed object through the frame in parallel
+    pub fn integrate_parallel(&mut self, frame: &FrameIntegration) {
+        use rayon::prelude::*;
+
+        self.positions
+            .par_iter_mut()
+            .zip(self.velocities.par_iter_mut())
+            .zip(self.adaptive_steps.par_iter_mut())
+            .zip(self.coefficients.par_iter())
+            .for_each(|(((position, velocity), adaptive_step), &coefficients)| {
+                let (new_position, new_velocity, next_step) =
+                    frame.integrate(*position, *velocity, coefficients, *adaptive_step);
+                *position = new_position;
+                *velocity = new_velocity;
+                *adaptive_step = next_step;
+            });
     }
 }
 
-/// Performance monitoring system for optimized physics
+/// Performance monitoring system for the physics stage
 pub fn optimized_physics_monitor_system(
-    optimized_data: Res<OptimizedPhysicsData>,
+    orbital_query: Query<(), With<crate::components::OrbitalState>>,
+    integrator_config: Option<Res<IntegratorConfig>>,
     time: Res<Time>,
     mut last_report: Local<f32>,
 ) {
     let current_time = time.elapsed_secs();
-    
+
     // Report performance every 5 seconds
     if current_time - *last_report > 5.0 {
         *last_report = current_time;
-        
-        let object_count = optimized_data.states.len();
+
+        let object_count = orbital_query.iter().count();
         let fps = 1.0 / time.delta_secs();
-        
+        let backend = integrator_config.map(|c| c.backend).unwrap_or_default();
+
         if object_count > 0 {
-            info!("Optimized Physics: {} objects @ {:.1} FPS", object_count, fps);
-            
+            info!("Physics ({:?} backend): {} objects @ {:.1} FPS", backend, object_count, fps);
+
             // Estimate performance scaling - CORRECT: more objects = fewer FPS
             let scaling_factor = 1000.0 / object_count.max(1) as f32;
             let estimated_1k = fps / scaling_factor;
             info!("Estimated 1000-object performance: {:.1} FPS", estimated_1k);
         }
     }
-}
\ No newline at end of file
+}
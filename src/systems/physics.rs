@@ -1,47 +1,299 @@
+use bevy::math::DVec3;
 use bevy::prelude::*;
 use crate::components::*;
 use crate::resources::*;
+use crate::systems::optimized_physics::{PackedHandle, PackedPhysicsStore};
+use crate::utils::forces::ForceCoefficients;
 use crate::utils::integrators::*;
 
-/// Main physics system implementing 2-body orbital mechanics
+/// Components the physics stage reads and integrates
+type PhysicsItem<'a> = (
+    Entity,
+    &'a mut OrbitalState,
+    Option<&'a PhysicsObject>,
+    Option<&'a mut AdaptiveStep>,
+    Option<&'a PackedHandle>,
+    Option<&'a mut MeanElements>,
+    Option<&'a mut ManeuverPlan>,
+);
+
+/// Main physics system implementing orbital mechanics with zonal harmonics and drag
+/// The only place orbits are integrated; `IntegratorConfig::backend` picks how
 pub fn physics_system(
-    mut orbital_query: Query<&mut OrbitalState>,
+    mut commands: Commands,
+    mut orbital_query: Query<PhysicsItem<'_>>,
     mut sim_time: ResMut<SimulationTime>,
     time: Res<Time>,
     integrator_config: Option<Res<IntegratorConfig>>,
     constants: Res<Constants>,
+    mut packed: Local<PackedPhysicsStore>,
 ) {
-    // Update simulation time
+    let default_config;
+    let config = match integrator_config.as_deref() {
+        Some(config) => config,
+        None => {
+            default_config = IntegratorConfig::default();
+            &default_config
+        }
+    };
+
+    // Accumulate simulated time and take the whole steps that are due
     sim_time.advance(time.delta_secs());
+    let was_behind = sim_time.behind;
+    let budget = match config.backend {
+        PhysicsBackend::Analytical => u32::MAX,
+        _ => sim_time.max_substeps,
+    };
+    let steps = sim_time.consume_steps_up_to(budget);
+    if sim_time.behind && !was_behind {
+        warn!(
+            "Physics falling behind: more than {} sub-steps of {:.1}s due per frame, dropping simulated time",
+            sim_time.max_substeps, sim_time.timestep
+        );
+    }
 
-    // Don't run physics if paused
-    if sim_time.paused {
+    // Don't run physics if paused or no whole step is due yet
+    if steps == 0 {
         return;
     }
 
+    let frame_epoch = sim_time.now().plus_seconds(-sim_time.frame_duration());
+    let frame = config.frame(&constants, &frame_epoch, sim_time.timestep, steps);
+    let frame_start = sim_time.current - sim_time.frame_duration();
     let dt = sim_time.timestep;
-    let gm = constants.gravitational_parameter;
-    let use_rk4 = integrator_config.map(|c| c.use_rk4).unwrap_or(false);
-
-    for mut orbital_state in orbital_query.iter_mut() {
-        let (new_position, new_velocity) = if use_rk4 {
-            RK4Integrator::integrate(
-                orbital_state.position,
-                orbital_state.velocity,
-                dt,
-                gm,
-            )
-        } else {
-            EulerIntegrator::integrate(
-                orbital_state.position,
-                orbital_state.velocity,
-                dt,
-                gm,
-            )
+    // Objects with a burn due this frame are flown separately below
+    let maneuvering = |plan: Option<&ManeuverPlan>| {
+        plan.and_then(|p| p.next_event())
+            .is_some_and(|time| firing_step(time, frame_start, dt) < steps)
+    };
+
+    // The packed copy goes stale while another backend runs
+    if config.backend != PhysicsBackend::Packed {
+        packed.clear();
+    }
+
+    match config.backend {
+        PhysicsBackend::Ecs => {
+            for (entity, mut orbital_state, physics_object, adaptive_step, _, _, plan) in orbital_query.iter_mut() {
+                if maneuvering(plan.as_deref()) {
+                    continue;
+                }
+                let (position, velocity, next_step) = frame.integrate(
+                    orbital_state.position,
+                    orbital_state.velocity,
+                    force_coefficients(&orbital_state, physics_object),
+                    adaptive_step.as_ref().map(|s| s.0),
+                );
+                orbital_state.position = position;
+                orbital_state.velocity = velocity;
+                store_adaptive_step(&mut commands, entity, adaptive_step, next_step);
+            }
+        }
+        PhysicsBackend::Packed => {
+            sync_packed_store(&mut commands, &mut orbital_query, &mut packed);
+            packed.integrate_parallel(&frame);
+
+            for (i, &entity) in packed.entities().iter().enumerate() {
+                if let Ok((_, mut orbital_state, _, adaptive_step, _, _, plan)) = orbital_query.get_mut(entity) {
+                    if maneuvering(plan.as_deref()) {
+                        continue;
+                    }
+                    orbital_state.position = packed.position(i);
+                    orbital_state.velocity = packed.velocity(i);
+                    store_adaptive_step(&mut commands, entity, adaptive_step, packed.adaptive_step(i));
+                }
+            }
+        }
+        PhysicsBackend::Analytical => {
+            let propagator = config.analytical(&constants);
+            let now = sim_time.current;
+
+            for (entity, mut orbital_state, physics_object, adaptive_step, _, mean_elements, plan) in
+                orbital_query.iter_mut()
+            {
+                if maneuvering(plan.as_deref()) {
+                    continue;
+                }
+                let coefficients = force_coefficients(&orbital_state, physics_object);
+
+                // Re-anchor objects that are new, were moved by another system, or whose anchor
+                // was not advanced last frame because a different backend was running
+                let anchor = match mean_elements.as_deref() {
+                    Some(mean) if !orbital_state.is_changed() && (mean.epoch - frame_start).abs() < 1e-6 => *mean,
+                    _ => MeanElements {
+                        elements: propagator.mean_elements(orbital_state.position, orbital_state.velocity),
+                        epoch: frame_start,
+                    },
+                };
+
+                let Some(elements) =
+                    propagator.propagate(&anchor.elements, now - anchor.epoch, coefficients.ballistic_coefficient)
+                else {
+                    // Escape orbits have no closed form here; integrate them numerically instead
+                    let (position, velocity, next_step) = frame.integrate(
+                        orbital_state.position,
+                        orbital_state.velocity,
+                        coefficients,
+                        adaptive_step.as_ref().map(|s| s.0),
+                    );
+                    orbital_state.position = position;
+                    orbital_state.velocity = velocity;
+                    store_adaptive_step(&mut commands, entity, adaptive_step, next_step);
+                    continue;
+                };
+
+                (orbital_state.position, orbital_state.velocity) = propagator.osculating_state(&elements);
+                let anchor = MeanElements { elements, epoch: now };
+                match mean_elements {
+                    Some(mut mean) => *mean = anchor,
+                    None => {
+                        commands.entity(entity).insert(anchor);
+                    }
+                }
+            }
+        }
+    }
+
+    // Maneuvering objects coast with the same backend between the sub-steps where they fire;
+    // the analytical backend re-anchors them next frame since their anchor was not advanced
+    let propagator = (config.backend == PhysicsBackend::Analytical).then(|| config.analytical(&constants));
+    for (entity, mut orbital_state, physics_object, adaptive_step, handle, _, plan) in orbital_query.iter_mut() {
+        let Some(mut plan) = plan.filter(|p| maneuvering(Some(p))) else {
+            continue;
         };
+        let coefficients = force_coefficients(&orbital_state, physics_object);
+        let mut next_step = adaptive_step.as_ref().map(|s| s.0);
 
-        orbital_state.position = new_position;
-        orbital_state.velocity = new_velocity;
+        let coast = |position: DVec3, velocity: DVec3, coast_steps: u32| {
+            if let Some(propagator) = &propagator {
+                let elements = propagator.mean_elements(position, velocity);
+                let duration = coast_steps as f64 * dt;
+                let ballistic_coefficient = coefficients.ballistic_coefficient;
+                if let Some(elements) = propagator.propagate(&elements, duration, ballistic_coefficient) {
+                    return propagator.osculating_state(&elements);
+                }
+            }
+            let (position, velocity, step) =
+                frame.with_steps(coast_steps).integrate(position, velocity, coefficients, next_step);
+            next_step = step.or(next_step);
+            (position, velocity)
+        };
+        fly_maneuvers(&mut plan, &mut orbital_state, frame_start, dt, steps, coast);
+
+        // Objects added to the store this frame get their handle only once commands apply
+        let index = handle
+            .and_then(|h| packed.index(*h))
+            .or_else(|| packed.entities().iter().position(|&e| e == entity));
+        if let Some(i) = index {
+            packed.set_state(i, orbital_state.position, orbital_state.velocity);
+        }
+        store_adaptive_step(&mut commands, entity, adaptive_step, next_step);
+    }
+}
+
+/// Index of the first sub-step boundary at or after simulation time `time`, counted from the frame start
+fn firing_step(time: f64, frame_start: f64, dt: f64) -> u32 {
+    // Tolerate rounding so a burn timed exactly on a boundary fires there
+    ((time - frame_start) / dt - 1e-9).ceil().max(0.0) as u32
+}
+
+/// Fly an object through `steps` sub-steps of `dt` seconds, firing its plan at each due boundary
+/// `coast` advances a state by a whole number of sub-steps.
+fn fly_maneuvers(
+    plan: &mut ManeuverPlan,
+    orbital_state: &mut OrbitalState,
+    frame_start: f64,
+    dt: f64,
+    steps: u32,
+    mut coast: impl FnMut(DVec3, DVec3, u32) -> (DVec3, DVec3),
+) {
+    let mut step = 0;
+    while let Some(firing) = plan.next_event().map(|time| firing_step(time, frame_start, dt).max(step)) {
+        if firing >= steps {
+            break;
+        }
+        if firing > step {
+            (orbital_state.position, orbital_state.velocity) =
+                coast(orbital_state.position, orbital_state.velocity, firing - step);
+            step = firing;
+        }
+        let time = frame_start + step as f64 * dt;
+        let delta_v = plan.fire(time, orbital_state.position, orbital_state.velocity, &mut orbital_state.mass, dt);
+        orbital_state.velocity += delta_v;
+    }
+    if step < steps {
+        (orbital_state.position, orbital_state.velocity) =
+            coast(orbital_state.position, orbital_state.velocity, steps - step);
+    }
+}
+
+/// Bring the packed store in line with the ECS before integrating
+///
+/// New objects are inserted and handed a `PackedHandle`, objects whose state was changed outside
+/// the physics stage are copied in, and entries for despawned objects are swap-removed.
+fn sync_packed_store(
+    commands: &mut Commands,
+    orbital_query: &mut Query<PhysicsItem<'_>>,
+    packed: &mut PackedPhysicsStore,
+) {
+    packed.begin_sync();
+    for (entity, orbital_state, physics_object, adaptive_step, handle, _, _) in orbital_query.iter() {
+        let coefficients = force_coefficients(orbital_state, physics_object);
+        let live = handle
+            .and_then(|h| packed.index(*h))
+            .filter(|&i| packed.entities()[i] == entity);
+
+        match live {
+            Some(i) => {
+                packed.mark_seen(i);
+                packed.set_coefficients(i, coefficients);
+            }
+            None => {
+                let handle = packed.insert(
+                    entity,
+                    orbital_state.position,
+                    orbital_state.velocity,
+                    coefficients,
+                    adaptive_step.map(|s| s.0),
+                );
+                commands.entity(entity).insert(handle);
+            }
+        }
+    }
+    packed.end_sync();
+
+    // The stage's own writes are not seen as changes, so this only picks up other systems' edits
+    for (_, orbital_state, _, _, handle, _, _) in orbital_query.iter_mut() {
+        if let Some(i) = handle.and_then(|h| packed.index(*h)) {
+            if orbital_state.is_changed() {
+                packed.set_state(i, orbital_state.position, orbital_state.velocity);
+            }
+        }
+    }
+}
+
+/// Drag and radiation pressure coefficients; objects without physical properties feel neither
+fn force_coefficients(orbital_state: &OrbitalState, physics_object: Option<&PhysicsObject>) -> ForceCoefficients {
+    physics_object
+        .map(|p| p.force_coefficients(orbital_state.mass))
+        .unwrap_or_default()
+}
+
+/// Keep the adaptive integrator's step proposal on the entity for its next frame
+fn store_adaptive_step(
+    commands: &mut Commands,
+    entity: Entity,
+    current: Option<Mut<AdaptiveStep>>,
+    next_step: Option<f64>,
+) {
+    let Some(next_step) = next_step else {
+        return;
+    };
+    match current {
+        Some(mut step) => step.0 = next_step,
+        None => {
+            commands.entity(entity).insert(AdaptiveStep(next_step));
+        }
     }
 }
 
@@ -95,7 +347,7 @@ pub fn debug_orbital_system(
                     if count >= 3 { // Limit to first 3 satellites
                         break;
                     }
-                    let altitude = orbital_state.altitude() - constants.earth_radius;
+                    let altitude = (orbital_state.radius() - constants.earth_radius).0;
                     let speed = orbital_state.speed();
                     let energy = orbital_state.total_energy(constants.gravitational_parameter);
                     
@@ -65,17 +65,9 @@ pub fn record_simulation_system(
 
         objects.push(ObjectSnapshot {
             entity_id: id,
-            position: [
-                orbital_state.position.x as f64,
-                orbital_state.position.y as f64,
-                orbital_state.position.z as f64,
-            ],
-            velocity: [
-                orbital_state.velocity.x as f64,
-                orbital_state.velocity.y as f64,
-                orbital_state.velocity.z as f64,
-            ],
-            mass: orbital_state.mass,
+            position: orbital_state.position.to_array(),
+            velocity: orbital_state.velocity.to_array(),
+            mass: orbital_state.mass.0,
             is_satellite: satellite.is_some(),
             name,
         });
@@ -89,6 +81,7 @@ pub fn record_simulation_system(
 
     let frame = SimulationFrame {
         timestamp: sim_time.current,
+        utc: sim_time.iso8601(),
         objects,
         collisions,
         debris_created,
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::resources::*;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::frames::*;
+use crate::utils::units::*;
+
+/// Seconds in a Julian year, used to bin reentries per year
+pub const SECONDS_PER_YEAR: f64 = 365.25 * 86400.0;
+
+/// Per-year totals of logged `items`, starting at year 0: `add` folds each item into the
+/// bucket of the simulated year its `time` falls in, and empty years keep the default
+pub fn yearly_totals<T, B: Clone + Default>(items: &[T], time: impl Fn(&T) -> f64, add: impl Fn(&mut B, &T)) -> Vec<B> {
+    let mut summary: Vec<B> = Vec::new();
+    for item in items {
+        let year = (time(item).max(0.0) / SECONDS_PER_YEAR) as usize;
+        if summary.len() <= year {
+            summary.resize(year + 1, B::default());
+        }
+        add(&mut summary[year], item);
+    }
+    summary
+}
+
+/// Condition that marks an object as reentered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReentryCriterion {
+    /// The object's current altitude is below the interface
+    Altitude,
+    /// The object's perigee is below the interface; the orbit can no longer survive,
+    /// so it is counted immediately rather than waiting for the final plunge
+    Perigee,
+}
+
+/// Resource to control reentry detection
+#[derive(Resource)]
+pub struct ReentryConfig {
+    pub enabled: bool,
+    /// Altitude of the reentry interface in km (120 km is the conventional entry interface, 0 is the surface)
+    pub interface_altitude_km: f64,
+    pub criterion: ReentryCriterion,
+}
+
+impl Default for ReentryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interface_altitude_km: 120.0,
+            criterion: ReentryCriterion::Altitude,
+        }
+    }
+}
+
+impl ReentryConfig {
+    /// Check whether an orbital state has crossed the reentry interface
+    pub fn has_reentered(&self, orbital_state: &OrbitalState, constants: &Constants) -> bool {
+        let interface_radius = constants.earth_radius + Km(self.interface_altitude_km);
+        match self.criterion {
+            ReentryCriterion::Altitude => orbital_state.radius() < interface_radius,
+            ReentryCriterion::Perigee => {
+                let mu = constants.gravitational_parameter.0;
+                let elements = KeplerianElements::from_state(orbital_state.position, orbital_state.velocity, mu);
+                Km(elements.perigee_radius()) < interface_radius
+            }
+        }
+    }
+}
+
+/// Emitted once when an object crosses the reentry interface
+#[derive(Event, Clone, Debug)]
+pub struct ReentryEvent {
+    pub entity: Entity,
+    /// NORAD catalog number for tracked satellites, `None` for debris
+    pub norad_id: Option<u32>,
+    /// Simulation time of the reentry in seconds
+    pub time: f64,
+    /// Geodetic latitude in degrees
+    pub latitude: f64,
+    /// Longitude in degrees
+    pub longitude: f64,
+    /// Mass in kg
+    pub mass: f64,
+}
+
+impl ReentryEvent {
+    /// Build the event for an object reentering at the current simulation time
+    ///
+    /// The ground point comes from rotating the inertial position into the Earth-fixed
+    /// frame at the simulation's UTC instant.
+    pub fn new(
+        entity: Entity,
+        norad_id: Option<u32>,
+        orbital_state: &OrbitalState,
+        sim_time: &SimulationTime,
+    ) -> Self {
+        let geodetic = ground_point(orbital_state.position, &sim_time.now());
+
+        Self {
+            entity,
+            norad_id,
+            time: sim_time.current,
+            latitude: geodetic.latitude,
+            longitude: geodetic.longitude,
+            mass: orbital_state.mass.0,
+        }
+    }
+}
+
+/// Resource recording every reentry for analytics
+#[derive(Resource, Default)]
+pub struct ReentryLog {
+    pub events: Vec<ReentryEvent>,
+}
+
+impl ReentryLog {
+    pub fn record(&mut self, event: ReentryEvent) {
+        self.events.push(event);
+    }
+
+    /// Total number of recorded reentries
+    pub fn total(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Total mass removed by reentry in kg
+    pub fn total_mass(&self) -> f64 {
+        self.events.iter().map(|e| e.mass).sum()
+    }
+
+    /// Number of reentries and removed mass (kg) for each simulated year, starting at year 0
+    pub fn yearly_summary(&self) -> Vec<(usize, f64)> {
+        yearly_totals(&self.events, |event| event.time, |year: &mut (usize, f64), event| {
+            year.0 += 1;
+            year.1 += event.mass;
+        })
+    }
+
+    /// Average reentries per year over the elapsed simulation time
+    pub fn rate_per_year(&self, elapsed_seconds: f64) -> f64 {
+        if elapsed_seconds <= 0.0 {
+            return 0.0;
+        }
+        self.events.len() as f64 * SECONDS_PER_YEAR / elapsed_seconds
+    }
+}
+
+/// System to remove objects that cross the reentry interface and record the reentry
+pub fn reentry_detection_system(
+    mut commands: Commands,
+    config: Res<ReentryConfig>,
+    mut reentry_log: ResMut<ReentryLog>,
+    mut reentry_events: EventWriter<ReentryEvent>,
+    sim_time: Res<SimulationTime>,
+    orbital_query: Query<(Entity, &OrbitalState, Option<&Satellite>), Without<ScheduledForDeletion>>,
+    constants: Res<Constants>,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    let mut reentered_count = 0;
+
+    for (entity, orbital_state, satellite) in orbital_query.iter() {
+        if !config.has_reentered(orbital_state, &constants) {
+            continue;
+        }
+
+        let event = ReentryEvent::new(
+            entity,
+            satellite.map(|s| s.norad_id),
+            orbital_state,
+            &sim_time,
+        );
+        reentry_log.record(event.clone());
+        reentry_events.write(event);
+
+        // Mark before despawning so no other system removes and counts it again
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(ScheduledForDeletion);
+            entity_commands.despawn();
+        }
+        reentered_count += 1;
+    }
+
+    if reentered_count > 0 {
+        info!(
+            "Reentry: {} objects crossed the {:.0} km interface ({} total)",
+            reentered_count,
+            config.interface_altitude_km,
+            reentry_log.total()
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use crate::components::*;
+use crate::resources::SimulationTime;
+use crate::utils::frames::gcrf_to_earth_fixed_matrix;
 use crate::systems::materials::MaterialsCache;
 use crate::systems::render_mode::RenderMode;
 use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
 use bevy::math::primitives::Sphere;
+use bevy::math::{DMat3, DQuat};
 use bevy::prelude::*;
 
 /// Marker component to track objects that have been rendered
@@ -93,7 +96,7 @@ pub fn satellite_rendering_system(
             break;
         }
 
-        let scaled_position = orbital_state.position / 1000.0;
+        let scaled_position = orbital_state.render_position() / 1000.0;
         let mesh = meshes.add(Sphere::new(0.05).mesh().ico(5).unwrap());
 
         commands
@@ -158,7 +161,7 @@ pub fn debris_rendering_system(
             .entity(entity)
             .insert(Mesh3d(mesh))
             .insert(MeshMaterial3d(materials_cache.debris_material.clone()))
-            .insert(Transform::from_translation(orbital_state.position / 1000.0))
+            .insert(Transform::from_translation(orbital_state.render_position() / 1000.0))
             .insert(RenderedObject);
 
         count += 1;
@@ -243,6 +246,25 @@ pub fn update_positions_system(
 ) {
     for (mut transform, orbital_state) in query.iter_mut() {
         // Scale down position to match rendering scale (km to render units)
-        transform.translation = orbital_state.position / 1000.0;
+        transform.translation = orbital_state.render_position() / 1000.0;
+    }
+}
+
+/// System to turn the Earth mesh so its texture sits under the simulation's current UTC instant
+pub fn earth_orientation_system(
+    sim_time: Res<SimulationTime>,
+    mut earth_query: Query<&mut Transform, With<EarthBody>>,
+) {
+    if !sim_time.is_changed() {
+        return;
+    }
+
+    // The UV sphere's seam (texture longitude -180°) lies on its +X axis, half a turn from Greenwich
+    let earth_fixed_to_inertial = gcrf_to_earth_fixed_matrix(&sim_time.now()).transpose();
+    let mesh_to_earth_fixed = DMat3::from_rotation_z(std::f64::consts::PI);
+    let rotation = DQuat::from_mat3(&(earth_fixed_to_inertial * mesh_to_earth_fixed)).as_quat();
+
+    for mut transform in earth_query.iter_mut() {
+        transform.rotation = rotation;
     }
 }
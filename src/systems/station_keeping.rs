@@ -0,0 +1,291 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use std::f64::consts::{PI, TAU};
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::lifecycle::disposal_burns;
+use crate::utils::analytical::AnalyticalPropagator;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::frames::{ecef_to_eci, ground_point, EARTH_ROTATION_RATE};
+use crate::utils::gravity::GravityModel;
+use crate::utils::time::Epoch;
+use crate::utils::units::*;
+
+/// Burns smaller than this many km/s are not worth planning
+const MIN_STATION_KEEPING_DELTA_V: f64 = 1e-7;
+
+/// Largest distance in km between the mean semi-major axis and the geosynchronous radius of
+/// an orbit that holds a geostationary slot, the altitude band of the IADC protected GEO region
+const GEOSYNCHRONOUS_MARGIN: f64 = 200.0;
+
+/// Orbits more eccentric than this are not held in a geostationary slot
+const GEOSTATIONARY_ECCENTRICITY: f64 = 0.01;
+
+/// Orbits inclined more than this many degrees are geosynchronous but not geostationary
+const GEOSTATIONARY_INCLINATION: f64 = 5.0;
+
+/// Half-width in degrees of the longitude deadband given to geostationary satellites
+pub const GEO_LONGITUDE_TOLERANCE: f64 = 0.1;
+
+/// Inclination limit in degrees given to geostationary satellites
+pub const GEO_MAX_INCLINATION: f64 = 0.1;
+
+/// Half-width in km of the altitude deadband given to constellation satellites
+pub const SLOT_ALTITUDE_TOLERANCE: f64 = 1.0;
+
+/// Half-width in km of the along-track deadband given to constellation satellites
+pub const SLOT_ALONG_TRACK_TOLERANCE: f64 = 10.0;
+
+/// Resource to control station-keeping
+#[derive(Resource, Clone, Debug)]
+pub struct StationKeepingConfig {
+    pub enabled: bool,
+    /// Simulation seconds between box checks
+    pub check_interval: f64,
+    /// Time in seconds an east-west or along-track correction takes to drift the satellite
+    /// back to the middle of its box
+    pub phasing_time: f64,
+    /// Specific impulse of the station-keeping thrusters in seconds
+    pub isp: f64,
+}
+
+impl Default for StationKeepingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval: 3600.0,
+            phasing_time: 2.0 * 86400.0,
+            isp: HYDRAZINE_ISP,
+        }
+    }
+}
+
+/// Resource totalling station-keeping activity across all satellites
+#[derive(Resource, Default, Debug)]
+pub struct StationKeepingStatistics {
+    pub maneuvers: usize,
+    /// Delta-V planned in km/s
+    pub delta_v: f64,
+    /// Satellites whose missions ended early, down to the propellant their disposal needs
+    pub retired_out_of_propellant: usize,
+}
+
+/// Angle wrapped into [-π, π)
+fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Geosynchronous orbit radius in km
+pub fn geosynchronous_radius(mu: f64) -> f64 {
+    (mu / (EARTH_ROTATION_RATE * EARTH_ROTATION_RATE)).cbrt()
+}
+
+/// Geostationary slot at the longitude a satellite is over at `epoch`, or `None` unless its
+/// orbit is near-circular, near-geosynchronous and near-equatorial
+///
+/// A satellite already inclined beyond `GEO_MAX_INCLINATION` is held at its current
+/// inclination rather than made to pay for a plane change at once.
+pub fn geostationary_slot(orbital_state: &OrbitalState, epoch: &Epoch, constants: &Constants) -> Option<StationKeeping> {
+    let propagator = AnalyticalPropagator::new(constants, GravityModel::J2, None);
+    let (position, velocity) = (orbital_state.position, orbital_state.velocity);
+    let semi_major_axis = propagator.mean_semi_major_axis(position, velocity);
+    if (semi_major_axis - geosynchronous_radius(propagator.mu)).abs() > GEOSYNCHRONOUS_MARGIN {
+        return None;
+    }
+    if KeplerianElements::from_state(position, velocity, propagator.mu).eccentricity > GEOSTATIONARY_ECCENTRICITY {
+        return None;
+    }
+
+    // Against the equator of date, as station_keeping_burns measures it
+    let pole = ecef_to_eci(DVec3::Z, epoch);
+    let inclination = position.cross(velocity).angle_between(pole).to_degrees();
+    if inclination > GEOSTATIONARY_INCLINATION {
+        return None;
+    }
+    let longitude = ground_point(position, epoch).longitude;
+    Some(StationKeeping::geostationary(longitude, GEO_LONGITUDE_TOLERANCE, inclination.max(GEO_MAX_INCLINATION)))
+}
+
+/// Box a satellite starting operations at simulation time `now` holds: the geostationary slot
+/// it is in, or else the constellation slot
+pub fn operational_station_keeping(
+    orbital_state: &OrbitalState,
+    now: f64,
+    epoch: &Epoch,
+    constants: &Constants,
+) -> StationKeeping {
+    geostationary_slot(orbital_state, epoch, constants).unwrap_or_else(|| {
+        StationKeeping::constellation_slot(orbital_state, constants, now, SLOT_ALTITUDE_TOLERANCE, SLOT_ALONG_TRACK_TOLERANCE)
+    })
+}
+
+/// Tangential burn resetting the mean semi-major axis so an along-track error of `error` km
+/// (positive ahead of the slot) drifts back over `phasing_time`
+///
+/// A semi-major axis offset Δa drifts a near-circular orbit along-track at -3/2·n·Δa, and a
+/// tangential burn of n·Δa/2 produces it. `max_offset` bounds the target offset in km.
+fn phasing_burn(
+    now: f64,
+    semi_major_axis: f64,
+    reference: f64,
+    mean_motion: f64,
+    error: f64,
+    max_offset: f64,
+    config: &StationKeepingConfig,
+) -> Option<Burn> {
+    let offset = (2.0 * error / (3.0 * mean_motion * config.phasing_time)).clamp(-max_offset, max_offset);
+    let delta_v = 0.5 * mean_motion * (reference + offset - semi_major_axis);
+    (delta_v.abs() > MIN_STATION_KEEPING_DELTA_V)
+        .then(|| Burn::impulsive(now, DVec3::new(0.0, delta_v, 0.0), BurnFrame::Rtn, config.isp))
+}
+
+/// Burns needed to bring a satellite back into its box, empty while it is inside
+///
+/// East-west and along-track errors are corrected at once with a tangential burn; a
+/// geostationary satellite over its inclination limit gets a plane change at the next node.
+pub fn station_keeping_burns(
+    station: &StationKeepingBox,
+    orbital_state: &OrbitalState,
+    now: f64,
+    epoch: &Epoch,
+    constants: &Constants,
+    config: &StationKeepingConfig,
+) -> Vec<Burn> {
+    let propagator = AnalyticalPropagator::new(constants, GravityModel::J2, None);
+    let (position, velocity) = (orbital_state.position, orbital_state.velocity);
+    let semi_major_axis = propagator.mean_semi_major_axis(position, velocity);
+    let elements = KeplerianElements::from_state(position, velocity, propagator.mu);
+    let mut burns = Vec::new();
+
+    match *station {
+        StationKeepingBox::Geostationary { longitude, longitude_tolerance, max_inclination } => {
+            let reference = geosynchronous_radius(propagator.mu);
+            let drift = wrap_angle((ground_point(position, epoch).longitude - longitude).to_radians());
+            if drift.abs() > longitude_tolerance.to_radians() {
+                let error = reference * drift;
+                let rate = EARTH_ROTATION_RATE;
+                burns.extend(phasing_burn(now, semi_major_axis, reference, rate, error, f64::INFINITY, config));
+            }
+
+            // Inclination is held against the equator of date, not the GCRF equator of J2000
+            let pole = ecef_to_eci(DVec3::Z, epoch);
+            let normal = position.cross(velocity).normalize();
+            if normal.angle_between(pole) > max_inclination.to_radians() {
+                // Next node crossing, where a burn across the plane rotates it about the node line
+                let node = pole.cross(normal).normalize();
+                let latitude_argument = node.cross(position).dot(normal).atan2(node.dot(position));
+                let to_node = (PI - latitude_argument.rem_euclid(PI)) / EARTH_ROTATION_RATE;
+                if let Some(at_node) = propagator.propagate(&elements, to_node, 0.0) {
+                    let (node_position, node_velocity) = at_node.to_state(propagator.mu);
+                    let equatorial = pole.cross(node_position).normalize() * node_velocity.length();
+                    let delta_v = equatorial - node_velocity;
+                    burns.push(Burn::impulsive(now + to_node, delta_v, BurnFrame::Inertial, config.isp));
+                }
+            }
+        }
+        StationKeepingBox::Constellation {
+            semi_major_axis: reference,
+            latitude_argument,
+            latitude_rate,
+            epoch: slot_epoch,
+            altitude_tolerance,
+            along_track_tolerance,
+        } => {
+            let slot = latitude_argument + latitude_rate * (now - slot_epoch);
+            let error = semi_major_axis * wrap_angle(elements.argument_of_latitude() - slot);
+            let altitude_error = semi_major_axis - reference;
+            if altitude_error.abs() > altitude_tolerance || error.abs() > along_track_tolerance {
+                let mean_motion = (propagator.mu / reference.powi(3)).sqrt();
+                let max_offset = 0.5 * altitude_tolerance;
+                burns.extend(phasing_burn(now, semi_major_axis, reference, mean_motion, error, max_offset, config));
+            }
+        }
+    }
+
+    burns
+}
+
+/// Components read from each station-keeping satellite
+type StationKeeper<'a> = (
+    Entity,
+    &'a OrbitalState,
+    &'a Satellite,
+    &'a mut Mission,
+    &'a mut StationKeeping,
+    Option<&'a mut ManeuverPlan>,
+);
+
+/// System to hold operational satellites in their boxes and retire them when they can no longer
+///
+/// Only satellites with an operational `Mission` are kept; at end of life `lifecycle_system`
+/// takes over. Every `check_interval` a satellite with nothing else planned gets whatever
+/// burns bring it back into its box, paid for from a standard propellant load if it has no
+/// plan yet. Burns that would eat into the propellant its disposal needs are not planned:
+/// the mission ends there instead, leaving that reserve for `lifecycle_system` to dispose with.
+pub fn station_keeping_system(
+    mut commands: Commands,
+    config: Res<StationKeepingConfig>,
+    mut statistics: ResMut<StationKeepingStatistics>,
+    sim_time: Res<SimulationTime>,
+    constants: Res<Constants>,
+    mut keeper_query: Query<StationKeeper<'_>>,
+    mut last_check: Local<Option<f64>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let now = sim_time.current;
+    if last_check.is_some_and(|last| now - last < config.check_interval) {
+        return;
+    }
+    *last_check = Some(now);
+    let epoch = sim_time.now();
+
+    for (entity, orbital_state, satellite, mut mission, mut station_keeping, plan) in keeper_query.iter_mut() {
+        if !satellite.active || !mission.is_operational() || now >= mission.end_of_life() {
+            continue;
+        }
+        if plan.as_ref().is_some_and(|p| !p.is_idle()) {
+            continue;
+        }
+
+        let burns = station_keeping_burns(&station_keeping.station, orbital_state, now, &epoch, &constants, &config);
+        let propellant = plan.as_ref().map_or(PROPELLANT_FRACTION * orbital_state.mass.0, |p| p.propellant);
+        let needed = propellant_for(&burns, orbital_state.mass);
+        let disposal = disposal_burns(&mission.disposal, orbital_state, now, &constants, config.isp);
+        let reserve = propellant_for(&disposal, orbital_state.mass - needed);
+        if propellant - needed.0 <= reserve.0 {
+            mission.lifetime = now - mission.start;
+            if let Some(mut plan) = plan {
+                plan.cancel_pending();
+            }
+            commands.entity(entity).remove::<StationKeeping>();
+            statistics.retired_out_of_propellant += 1;
+            info!(
+                "Station-keeping: {} down to its disposal reserve after {} maneuvers, {:.1} m/s",
+                satellite.name,
+                station_keeping.maneuvers,
+                station_keeping.delta_v * METERS_PER_KM
+            );
+            continue;
+        }
+        if burns.is_empty() {
+            continue;
+        }
+
+        let delta_v: f64 = burns.iter().map(|b| b.delta_v.length()).sum();
+        station_keeping.maneuvers += burns.len() as u32;
+        station_keeping.delta_v += delta_v;
+        statistics.maneuvers += burns.len();
+        statistics.delta_v += delta_v;
+
+        match plan {
+            Some(mut plan) => burns.into_iter().for_each(|burn| plan.schedule(burn)),
+            None => {
+                let mut plan = ManeuverPlan::for_spacecraft(orbital_state.mass);
+                burns.into_iter().for_each(|burn| plan.schedule(burn));
+                commands.entity(entity).insert(plan);
+            }
+        }
+    }
+}
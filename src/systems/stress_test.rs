@@ -2,6 +2,9 @@ use bevy::prelude::*;
 use rand::prelude::*;
 
 use crate::components::*;
+use crate::resources::*;
+use crate::systems::constellation::*;
+use crate::utils::units::Kg;
 
 #[derive(Component)]
 pub struct StressTestObject {
@@ -23,6 +26,17 @@ pub enum OrbitType {
 }
 
 impl OrbitType {
+    /// Orbit type of a circular orbit at `altitude` km
+    pub fn from_altitude(altitude: f64) -> Self {
+        if altitude < 2000.0 {
+            OrbitType::LEO
+        } else if altitude < 35786.0 {
+            OrbitType::MEO
+        } else {
+            OrbitType::GEO
+        }
+    }
+
     fn altitude_range(&self) -> (f32, f32) {
         match self {
             OrbitType::LEO => (160.0, 2000.0),
@@ -47,6 +61,8 @@ pub struct StressTestConfig {
     pub current_leo: usize,
     pub current_meo: usize,
     pub current_geo: usize,
+    /// Walker constellation to spawn in place of randomly placed satellites
+    pub constellation: Option<ConstellationDefinition>,
 }
 
 impl Default for StressTestConfig {
@@ -62,6 +78,7 @@ impl Default for StressTestConfig {
             current_leo: 0,
             current_meo: 0,
             current_geo: 0,
+            constellation: None,
         }
     }
 }
@@ -71,6 +88,9 @@ pub fn stress_test_spawn_system(
     mut commands: Commands,
     mut config: ResMut<StressTestConfig>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    constants: Res<Constants>,
+    sim_time: Res<SimulationTime>,
+    mut catalog: ResMut<CatalogNumbers>,
     existing_objects: Query<&StressTestObject>,
 ) {
     // Toggle stress test with 'T' key
@@ -86,6 +106,13 @@ pub fn stress_test_spawn_system(
         }
     }
 
+    // Spawn a Starlink-like constellation with 'W' key
+    if keyboard.just_pressed(KeyCode::KeyW) {
+        config.constellation = Some(ConstellationDefinition::starlink_like());
+        config.enabled = true;
+        info!("Stress test: spawning a Starlink-like Walker constellation");
+    }
+
     // Adjust target object count - keep the same distribution ratios
     if keyboard.just_pressed(KeyCode::Digit5) {
         config.target_objects = 500;
@@ -178,6 +205,35 @@ pub fn stress_test_spawn_system(
         return; // Don't spawn more
     }
 
+    // A configured constellation is spawned whole, once, and becomes the target
+    if let Some(definition) = config.constellation.take() {
+        let now = sim_time.current;
+        let spawned = spawn_constellation(&mut commands, &definition, &mut catalog, now, &constants);
+        let orbit_types = definition.shells.iter().flat_map(|shell| {
+            std::iter::repeat_n(OrbitType::from_altitude(shell.altitude), shell.total as usize)
+        });
+        for (entity, orbit_type) in spawned.into_iter().zip(orbit_types) {
+            commands.entity(entity).insert(StressTestObject::new(orbit_type));
+            match orbit_type {
+                OrbitType::LEO => config.current_leo += 1,
+                OrbitType::MEO => config.current_meo += 1,
+                OrbitType::GEO => config.current_geo += 1,
+            }
+        }
+        config.target_leo = config.current_leo;
+        config.target_meo = config.current_meo;
+        config.target_geo = config.current_geo;
+        config.current_objects = config.current_leo + config.current_meo + config.current_geo;
+        config.target_objects = config.current_objects;
+        info!(
+            "Spawned {} constellation satellites of {} - {} objects in total",
+            definition.total(),
+            definition.name,
+            config.current_objects
+        );
+        return;
+    }
+
     // Spawn objects if we haven't reached targets
     let mut spawned = 0;
 
@@ -287,8 +343,8 @@ fn spawn_orbital_satellite(commands: &mut Commands, orbit_type: OrbitType) {
 
     // Spawn the satellite entity
     commands.spawn((
-        OrbitalState::new(position, velocity, mass),
-        PhysicsObject::satellite(mass),
+        OrbitalState::new(position.as_dvec3(), velocity.as_dvec3(), Kg(mass)),
+        PhysicsObject::satellite(Kg(mass)),
         Satellite::new(format!("{:?} Satellite", orbit_type), 0, true),
         StressTestObject::new(orbit_type),
         RenderAsSatellite, // Render as green satellite
@@ -1,5 +1,6 @@
 use crate::components::*;
 use crate::resources::*;
+use crate::utils::frames::ground_point;
 use bevy::prelude::*;
 use bevy::prelude::*;
 
@@ -68,7 +69,7 @@ pub fn satellite_selection_system(
         // Deselect previous selection
         if let Some(prev_entity) = selection.selected_entity {
             if let Ok(mut entity_commands) = commands.get_entity(prev_entity) {
-                entity_commands.remove::<SelectedSatellite>();
+                entity_commands.remove::<(SelectedSatellite, OrbitalElements)>();
             }
         }
 
@@ -92,10 +93,14 @@ pub fn satellite_selection_system(
     }
 }
 
+/// Selected satellite details, with its orbital elements once derived
+type SelectedInfo<'a> = (Entity, &'a OrbitalState, &'a Satellite, &'a TleData, Option<&'a OrbitalElements>);
+
 /// System to display satellite information (would be used with HTML overlay)
 pub fn satellite_info_display_system(
+    mut commands: Commands,
     selection: Res<SatelliteSelection>,
-    satellite_query: Query<(&OrbitalState, &Satellite, &TleData), With<SelectedSatellite>>,
+    satellite_query: Query<SelectedInfo<'_>, With<SelectedSatellite>>,
     constants: Res<Constants>,
     sim_time: Res<SimulationTime>,
 ) {
@@ -103,11 +108,21 @@ pub fn satellite_info_display_system(
         return;
     }
 
-    if let Ok((orbital_state, satellite, tle_data)) = satellite_query.get_single() {
-        let altitude = orbital_state.altitude() - constants.earth_radius;
+    if let Ok((entity, orbital_state, satellite, tle_data, elements)) = satellite_query.get_single() {
+        let altitude = (orbital_state.radius() - constants.earth_radius).0;
         let speed = orbital_state.speed();
         let energy = orbital_state.total_energy(constants.gravitational_parameter);
 
+        // Elements are derived on demand for the selected satellite only
+        let Some(OrbitalElements(elements)) = elements else {
+            let mu = constants.gravitational_parameter.0;
+            commands.entity(entity).insert(OrbitalElements::from_state(orbital_state, mu));
+            return;
+        };
+        let apogee = elements.apogee_radius().map_or(f64::INFINITY, |r| r - constants.earth_radius.0);
+        let period = elements.period(constants.gravitational_parameter.0).unwrap_or(f64::INFINITY);
+        let ground = ground_point(orbital_state.position, &sim_time.now());
+
         // Log satellite info (in a real implementation, this would update HTML elements)
         debug!(
             "=== SATELLITE INFO ===\n\
@@ -117,6 +132,12 @@ pub fn satellite_info_display_system(
             Speed: {:.2} km/s\n\
             Mass: {:.2} kg\n\
             Total Energy: {:.2e} J\n\
+            Semi-major Axis: {:.2} km\n\
+            Eccentricity: {:.5}\n\
+            Inclination: {:.3}°\n\
+            Perigee/Apogee: {:.1} / {:.1} km\n\
+            Period: {:.2} min\n\
+            Ground Point: {:.3}°, {:.3}°\n\
             Simulation Time: {:.2} s",
             satellite.name,
             tle_data.norad_id,
@@ -124,6 +145,14 @@ pub fn satellite_info_display_system(
             speed,
             orbital_state.mass,
             energy,
+            elements.semi_major_axis,
+            elements.eccentricity,
+            elements.inclination.to_degrees(),
+            elements.perigee_radius() - constants.earth_radius.0,
+            apogee,
+            period / 60.0,
+            ground.latitude,
+            ground.longitude,
             sim_time.current
         );
     }
@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 use crate::components::*;
 use crate::components::trails::*;
+use crate::resources::*;
 
 /// System to update trail positions
 pub fn update_trail_system(
     mut trail_query: Query<(&mut Trail, &OrbitalState)>,
     trail_config: Res<TrailConfig>,
+    constants: Res<Constants>,
 ) {
     if !trail_config.enabled {
         return;
@@ -13,13 +15,13 @@ pub fn update_trail_system(
 
     for (mut trail, orbital_state) in trail_query.iter_mut() {
         // Convert position from km to render units
-        let scaled_position = orbital_state.position / 1000.0;
+        let scaled_position = orbital_state.render_position() / 1000.0;
         
         // Add position to trail
         trail.add_position(scaled_position);
         
         // Update altitude band if needed
-        let altitude_km = orbital_state.altitude();
+        let altitude_km = (orbital_state.radius() - constants.earth_radius).0;
         trail.update_altitude_band(altitude_km);
     }
 }
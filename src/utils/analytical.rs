@@ -0,0 +1,247 @@
+// Analytical propagation: Kepler's equation plus secular J2 drift and optional drag decay
+// Cost per object is independent of how far it jumps, so long-term runs can take huge steps
+
+use std::f64::consts::TAU;
+use bevy::math::DVec3;
+use crate::resources::Constants;
+use crate::utils::atmosphere::AtmosphereModel;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::gravity::GravityModel;
+use crate::utils::sgp4_wrapper::{solve_keplers_equation, Sgp4Error};
+use crate::utils::units::*;
+
+/// Largest semi-major axis change in km taken in one drag chunk
+const MAX_DECAY_PER_CHUNK: f64 = 1.0;
+/// Most chunks a single jump is split into while decaying
+const MAX_DECAY_CHUNKS: u32 = 1000;
+/// Semi-major axis band in km integrated at a time when estimating orbital lifetime
+const LIFETIME_STEP: f64 = 1.0;
+/// Fixed-point iterations inverting the short-period terms; each gains a factor of about J2
+const MEAN_ELEMENT_ITERATIONS: usize = 4;
+
+/// Closed-form propagator for mean Keplerian elements
+///
+/// Mean elements advance at the J2 secular rates. Convert states with [`Self::mean_elements`]
+/// and back with [`Self::osculating_state`], which add and remove the first-order J2
+/// short-period terms; propagating osculating elements directly drifts along-track by tens
+/// of km per hour in LEO.
+#[derive(Clone, Copy, Debug)]
+pub struct AnalyticalPropagator {
+    /// Gravitational parameter in km³/s²
+    pub mu: f64,
+    /// Reference radius for J2
+    pub equatorial_radius: Km,
+    /// Mean Earth radius used for drag altitudes
+    pub earth_radius: Km,
+    /// J2 coefficient, zero for pure two-body motion
+    pub j2: f64,
+    /// Density model for semi-major axis decay, `None` disables drag
+    pub atmosphere: Option<AtmosphereModel>,
+}
+
+impl AnalyticalPropagator {
+    /// Build the propagator from the simulation constants
+    /// Any zonal gravity model contributes its J2 secular terms; higher zonals are ignored.
+    pub fn new(constants: &Constants, gravity_model: GravityModel, atmosphere: Option<AtmosphereModel>) -> Self {
+        Self {
+            mu: constants.gravitational_parameter.0,
+            equatorial_radius: constants.earth_equatorial_radius,
+            earth_radius: constants.earth_radius,
+            j2: match gravity_model {
+                GravityModel::PointMass => 0.0,
+                _ => constants.j2,
+            },
+            atmosphere,
+        }
+    }
+
+    /// Secular rates (RAAN, argument of perigee, mean anomaly) in rad/s
+    pub fn secular_rates(&self, elements: &KeplerianElements) -> (f64, f64, f64) {
+        let n = (self.mu / elements.semi_major_axis.powi(3)).sqrt();
+        let e2 = elements.eccentricity * elements.eccentricity;
+        let cos_i = elements.inclination.cos();
+        let k = 0.75 * n * self.j2 * (self.equatorial_radius.0 / elements.semi_latus_rectum()).powi(2);
+
+        let raan_rate = -2.0 * k * cos_i;
+        let arg_perigee_rate = k * (5.0 * cos_i * cos_i - 1.0);
+        let mean_anomaly_rate = n + k * (1.0 - e2).sqrt() * (3.0 * cos_i * cos_i - 1.0);
+        (raan_rate, arg_perigee_rate, mean_anomaly_rate)
+    }
+
+    /// Secular rate of the argument of latitude of a circular orbit in rad/s
+    pub fn latitude_argument_rate(&self, semi_major_axis: f64, inclination: f64) -> f64 {
+        let circular = KeplerianElements {
+            semi_major_axis,
+            eccentricity: 0.0,
+            inclination,
+            raan: 0.0,
+            arg_perigee: 0.0,
+            true_anomaly: 0.0,
+        };
+        let (_, arg_perigee_rate, mean_anomaly_rate) = self.secular_rates(&circular);
+        arg_perigee_rate + mean_anomaly_rate
+    }
+
+    /// Mean semi-major axis in km of a near-circular orbit through a position (km) and velocity (km/s)
+    ///
+    /// The osculating value swings by tens of km around a LEO orbit. The energy including the J2
+    /// potential does not, and averaging it over a circular orbit gives the mean value to first order.
+    pub fn mean_semi_major_axis(&self, position: DVec3, velocity: DVec3) -> f64 {
+        let r = position.length();
+        let sin_latitude = position.z / r;
+        let j2_term = self.mu * self.j2 * self.equatorial_radius.0.powi(2) / r.powi(3);
+        let energy =
+            0.5 * velocity.length_squared() - self.mu / r + 0.5 * j2_term * (3.0 * sin_latitude * sin_latitude - 1.0);
+        let energy_axis = -self.mu / (2.0 * energy);
+
+        // The orbit average of P2(sin φ) is 3/4 sin²i - 1/2
+        let sin_i_squared = 1.0 - position.cross(velocity).normalize().z.powi(2);
+        energy_axis - 2.0 * self.j2 * self.equatorial_radius.0.powi(2) / energy_axis * (0.75 * sin_i_squared - 0.5)
+    }
+
+    /// Mean elements of the orbit through a position (km) and velocity (km/s)
+    ///
+    /// Inverts [`Self::osculating_state`] by fixed-point iteration on a state with the
+    /// short-period terms removed. Escape orbits keep their osculating elements.
+    pub fn mean_elements(&self, position: DVec3, velocity: DVec3) -> KeplerianElements {
+        let mut mean = KeplerianElements::from_state(position, velocity, self.mu);
+        if self.j2 == 0.0 || !mean.is_bound() {
+            return mean;
+        }
+
+        let (mut mean_position, mut mean_velocity) = (position, velocity);
+        for _ in 0..MEAN_ELEMENT_ITERATIONS {
+            let (osculating_position, osculating_velocity) = self.osculating_state(&mean);
+            mean_position += position - osculating_position;
+            mean_velocity += velocity - osculating_velocity;
+            mean = KeplerianElements::from_state(mean_position, mean_velocity, self.mu);
+        }
+        mean
+    }
+
+    /// Position (km) and velocity (km/s) of mean elements with the J2 short-period terms added
+    ///
+    /// First-order terms in the form SGP4 uses, applied to the radius, argument of latitude,
+    /// node, inclination and their rates.
+    pub fn osculating_state(&self, mean: &KeplerianElements) -> (DVec3, DVec3) {
+        let (position, velocity) = mean.to_state(self.mu);
+        if self.j2 == 0.0 {
+            return (position, velocity);
+        }
+
+        let p = mean.semi_latus_rectum();
+        let n = (self.mu / mean.semi_major_axis.powi(3)).sqrt();
+        let beta = (1.0 - mean.eccentricity * mean.eccentricity).sqrt();
+        let gamma = 0.5 * self.j2 * (self.equatorial_radius.0 / p).powi(2);
+        let (sin_i, cos_i) = mean.inclination.sin_cos();
+        let (sin_i2, cos_i2) = (sin_i * sin_i, cos_i * cos_i);
+        let latitude_argument = mean.argument_of_latitude();
+        let (sin_2u, cos_2u) = (2.0 * latitude_argument).sin_cos();
+
+        let r = position.length();
+        let radius = r * (1.0 - 1.5 * gamma * beta * (3.0 * cos_i2 - 1.0)) + 0.5 * gamma * p * sin_i2 * cos_2u;
+        let radial_speed = position.dot(velocity) / r - n * p * gamma * sin_i2 * sin_2u;
+        let transverse_speed =
+            position.cross(velocity).length() / r + n * p * gamma * (sin_i2 * cos_2u + 1.5 * (3.0 * cos_i2 - 1.0));
+        let latitude_argument = latitude_argument - 0.25 * gamma * (7.0 * cos_i2 - 1.0) * sin_2u;
+        let raan = mean.raan + 1.5 * gamma * cos_i * sin_2u;
+        let inclination = mean.inclination + 1.5 * gamma * cos_i * sin_i * cos_2u;
+
+        // Radial and along-track directions of the corrected orbit plane
+        let (sin_o, cos_o) = raan.sin_cos();
+        let (sin_i, cos_i) = inclination.sin_cos();
+        let (sin_u, cos_u) = latitude_argument.sin_cos();
+        let node = DVec3::new(cos_o, sin_o, 0.0);
+        let normal_to_node = DVec3::new(-sin_o * cos_i, cos_o * cos_i, sin_i);
+        let radial = node * cos_u + normal_to_node * sin_u;
+        let along_track = normal_to_node * cos_u - node * sin_u;
+        (radius * radial, radial_speed * radial + transverse_speed * along_track)
+    }
+
+    /// Semi-major axis decay rate in km/s for a near-circular orbit
+    /// `ballistic_coefficient` is Cd·A/m in m²/kg
+    pub fn decay_rate(&self, semi_major_axis: f64, ballistic_coefficient: f64) -> f64 {
+        let Some(atmosphere) = &self.atmosphere else {
+            return 0.0;
+        };
+        let density = atmosphere.density(semi_major_axis - self.earth_radius.0);
+        // ρ·B is per metre; scale to per km to match sqrt(μa) in km²/s
+        -density * ballistic_coefficient * METERS_PER_KM * (self.mu * semi_major_axis).sqrt()
+    }
+
+    /// Seconds for a near-circular orbit to decay from `semi_major_axis` down to `interface_altitude` km
+    ///
+    /// Integrates the inverse decay rate band by band; infinite without an atmosphere.
+    pub fn orbital_lifetime(&self, semi_major_axis: f64, ballistic_coefficient: f64, interface_altitude: f64) -> f64 {
+        let interface_radius = self.earth_radius.0 + interface_altitude;
+        if semi_major_axis <= interface_radius {
+            return 0.0;
+        }
+        if self.atmosphere.is_none() || ballistic_coefficient <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        let bands = ((semi_major_axis - interface_radius) / LIFETIME_STEP).ceil();
+        let band = (semi_major_axis - interface_radius) / bands;
+        (0..bands as u32)
+            .map(|i| band / -self.decay_rate(interface_radius + (i as f64 + 0.5) * band, ballistic_coefficient))
+            .sum()
+    }
+
+    /// Elements `dt` seconds later, jumping directly without intermediate steps
+    ///
+    /// Returns `None` for escape orbits or if Kepler's equation fails to converge.
+    pub fn propagate(
+        &self,
+        elements: &KeplerianElements,
+        dt: f64,
+        ballistic_coefficient: f64,
+    ) -> Option<KeplerianElements> {
+        if !elements.is_bound() {
+            return None;
+        }
+
+        let mut result = *elements;
+        let mut mean_anomaly = elements.mean_anomaly();
+        let mut remaining = dt;
+
+        // Without drag the rates are constant and the whole jump is a single chunk
+        for _ in 0..MAX_DECAY_CHUNKS {
+            if remaining == 0.0 {
+                break;
+            }
+            let decay_rate = self.decay_rate(result.semi_major_axis, ballistic_coefficient);
+            let chunk = if decay_rate == 0.0 {
+                remaining
+            } else {
+                let limit = (MAX_DECAY_PER_CHUNK / decay_rate.abs()).max(dt.abs() / MAX_DECAY_CHUNKS as f64);
+                remaining.clamp(-limit, limit)
+            };
+
+            // Rates at the chunk midpoint keep the decaying mean motion second-order accurate
+            let midpoint = KeplerianElements {
+                semi_major_axis: result.semi_major_axis + 0.5 * decay_rate * chunk,
+                ..result
+            };
+            let (raan_rate, arg_perigee_rate, mean_anomaly_rate) = self.secular_rates(&midpoint);
+
+            // Decay stops at the surface; reentry detection removes the object well before that
+            result.semi_major_axis = (result.semi_major_axis + decay_rate * chunk).max(self.earth_radius.0);
+            result.raan = (result.raan + raan_rate * chunk).rem_euclid(TAU);
+            result.arg_perigee = (result.arg_perigee + arg_perigee_rate * chunk).rem_euclid(TAU);
+            mean_anomaly = (mean_anomaly + mean_anomaly_rate * chunk).rem_euclid(TAU);
+            remaining -= chunk;
+        }
+
+        result.true_anomaly = true_anomaly_from_mean(mean_anomaly, result.eccentricity).ok()?;
+        Some(result)
+    }
+}
+
+/// True anomaly in radians, [0, 2π), for an elliptic orbit's mean anomaly
+pub fn true_anomaly_from_mean(mean_anomaly: f64, eccentricity: f64) -> Result<f64, Sgp4Error> {
+    let eccentric_anomaly = solve_keplers_equation(mean_anomaly, eccentricity)?;
+    let (sin_half, cos_half) = (0.5 * eccentric_anomaly).sin_cos();
+    let true_anomaly = 2.0 * ((1.0 + eccentricity).sqrt() * sin_half).atan2((1.0 - eccentricity).sqrt() * cos_half);
+    Ok(true_anomaly.rem_euclid(TAU))
+}
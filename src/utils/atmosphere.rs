@@ -0,0 +1,199 @@
+// Atmospheric density models and the drag acceleration they drive
+// Densities are in kg/m³ as a function of altitude above the reference ellipsoid radius (km)
+
+use bevy::math::DVec3;
+use crate::utils::units::Km;
+
+/// Boltzmann constant in J/K
+const BOLTZMANN: f64 = 1.380649e-23;
+/// Atomic mass unit in kg
+const ATOMIC_MASS_UNIT: f64 = 1.66053907e-27;
+/// Standard gravity in m/s², also converting specific impulse to exhaust velocity
+pub const STANDARD_GRAVITY: f64 = 9.80665;
+
+/// Exponential atmosphere table (Vallado, Table 8-4, CIRA-72 based):
+/// base altitude (km), base density (kg/m³), scale height (km)
+const EXPONENTIAL_TABLE: [(f64, f64, f64); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// Lower boundary of the thermosphere model in km
+const THERMOSPHERE_BASE_ALTITUDE: f64 = 120.0;
+/// Temperature at the lower boundary in K
+const THERMOSPHERE_BASE_TEMPERATURE: f64 = 380.0;
+/// Temperature gradient at the lower boundary in K/km
+const THERMOSPHERE_BASE_GRADIENT: f64 = 12.0;
+
+/// Constituents in diffusive equilibrium above 120 km:
+/// molecular mass (amu), number density at 120 km (m⁻³), thermal diffusion factor
+const THERMOSPHERE_SPECIES: [(f64, f64, f64); 4] = [
+    (28.0134, 4.16e17, 0.0),  // N2
+    (31.9988, 5.0e16, 0.0),   // O2
+    (15.9994, 9.0e16, 0.0),   // O
+    (4.0026, 2.5e13, -0.38),  // He
+];
+
+/// Solar and geomagnetic activity driving the thermosphere
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpaceWeather {
+    /// Daily 10.7 cm solar radio flux (sfu)
+    pub f107: f64,
+    /// 81-day centered average of F10.7 (sfu)
+    pub f107_average: f64,
+    /// Daily planetary geomagnetic index
+    pub ap: f64,
+}
+
+impl Default for SpaceWeather {
+    fn default() -> Self {
+        // Moderate solar activity
+        Self {
+            f107: 150.0,
+            f107_average: 150.0,
+            ap: 15.0,
+        }
+    }
+}
+
+impl SpaceWeather {
+    /// Global exospheric temperature in K (Jacchia 1970/1971 relations)
+    pub fn exospheric_temperature(&self) -> f64 {
+        let solar = 379.0 + 3.24 * self.f107_average + 1.3 * (self.f107 - self.f107_average);
+        let geomagnetic = self.ap + 100.0 * (1.0 - (-0.08 * self.ap).exp());
+        solar + geomagnetic
+    }
+}
+
+/// Atmospheric density model used for drag
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AtmosphereModel {
+    /// Static piecewise-exponential table, independent of solar activity
+    Exponential,
+    /// Jacchia-style thermosphere: exospheric temperature from F10.7/Ap, Bates temperature
+    /// profile and diffusive equilibrium of N2, O2, O and He above 120 km.
+    /// Falls back to the exponential table below 120 km.
+    Jacchia(SpaceWeather),
+}
+
+impl AtmosphereModel {
+    /// Mass density in kg/m³ at the given altitude in km
+    pub fn density(&self, altitude_km: f64) -> f64 {
+        match self {
+            AtmosphereModel::Exponential => exponential_density(altitude_km),
+            AtmosphereModel::Jacchia(weather) => {
+                if altitude_km < THERMOSPHERE_BASE_ALTITUDE {
+                    exponential_density(altitude_km)
+                } else {
+                    thermosphere_density(altitude_km, weather.exospheric_temperature())
+                }
+            }
+        }
+    }
+}
+
+/// Density from the exponential table, extrapolating the last band above 1000 km
+fn exponential_density(altitude_km: f64) -> f64 {
+    let altitude = altitude_km.max(0.0);
+    let (base, rho0, scale_height) = EXPONENTIAL_TABLE
+        .iter()
+        .rev()
+        .find(|(base, _, _)| altitude >= *base)
+        .copied()
+        .unwrap_or(EXPONENTIAL_TABLE[0]);
+
+    rho0 * (-(altitude - base) / scale_height).exp()
+}
+
+/// Density above 120 km from the Bates-Walker temperature profile
+fn thermosphere_density(altitude_km: f64, exospheric_temperature: f64) -> f64 {
+    // Keep the exospheric temperature above the boundary value so the profile stays monotonic
+    let t_inf = exospheric_temperature.max(THERMOSPHERE_BASE_TEMPERATURE + 1.0);
+    let t_base = THERMOSPHERE_BASE_TEMPERATURE;
+    let earth_radius = 6356.766; // km, radius used for geopotential altitude
+
+    // Shape parameter of the temperature profile (1/km)
+    let sigma = THERMOSPHERE_BASE_GRADIENT / (t_inf - t_base);
+
+    // Geopotential height above the boundary accounts for gravity falling off with altitude
+    let zeta = (altitude_km - THERMOSPHERE_BASE_ALTITUDE) * (earth_radius + THERMOSPHERE_BASE_ALTITUDE)
+        / (earth_radius + altitude_km);
+    let decay = (-sigma * zeta).exp();
+    let temperature = t_inf - (t_inf - t_base) * decay;
+
+    let base_ratio = earth_radius / (earth_radius + THERMOSPHERE_BASE_ALTITUDE);
+    let gravity_base = STANDARD_GRAVITY * base_ratio * base_ratio;
+
+    THERMOSPHERE_SPECIES
+        .iter()
+        .map(|&(mass_amu, base_number_density, thermal_diffusion)| {
+            let mass = mass_amu * ATOMIC_MASS_UNIT;
+            // Dimensionless ratio of the species scale height to the profile length
+            let gamma = mass * gravity_base / (sigma * 1e-3 * BOLTZMANN * t_inf);
+            let number_density = base_number_density
+                * (t_base / temperature).powf(1.0 + gamma + thermal_diffusion)
+                * (-sigma * gamma * zeta).exp();
+            mass * number_density
+        })
+        .sum()
+}
+
+/// Atmospheric drag on an object moving through an atmosphere that co-rotates with the Earth
+#[derive(Clone, Copy, Debug)]
+pub struct AtmosphericDrag {
+    pub model: AtmosphereModel,
+    /// Radius subtracted from the geocentric distance to get altitude
+    pub earth_radius: Km,
+    /// Earth rotation rate in rad/s
+    pub earth_rotation_rate: f64,
+}
+
+impl AtmosphericDrag {
+    /// Drag acceleration in km/s² for a position (km) and inertial velocity (km/s)
+    ///
+    /// `ballistic_coefficient` is Cd·A/m in m²/kg; zero disables drag for the object.
+    pub fn acceleration_f64(&self, position: DVec3, velocity: DVec3, ballistic_coefficient: f64) -> DVec3 {
+        if ballistic_coefficient <= 0.0 {
+            return DVec3::ZERO;
+        }
+
+        let altitude = (Km::length(position) - self.earth_radius).0;
+        let density = self.model.density(altitude);
+        if density <= 0.0 {
+            return DVec3::ZERO;
+        }
+
+        // Velocity relative to the rotating atmosphere
+        let relative_velocity = velocity - DVec3::new(0.0, 0.0, self.earth_rotation_rate).cross(position);
+        let speed = relative_velocity.length();
+
+        // ρ [kg/m³] · B [m²/kg] · v² [km²/s²] gives 1e6 m/s² per unit, i.e. 1e3 km/s²
+        -0.5 * density * ballistic_coefficient * speed * relative_velocity * 1e3
+    }
+}
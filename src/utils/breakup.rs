@@ -0,0 +1,409 @@
+// NASA Standard Breakup Model (EVOLVE 4.0, Johnson et al. 2001)
+// Fragment sizes follow characteristic-length power laws, area-to-mass ratios follow the
+// spacecraft / rocket-body log-normal mixtures, and ejection speeds depend on A/M
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::ObjectClass;
+use crate::utils::units::*;
+
+/// Specific impact energy above which a collision is catastrophic, in J/kg (40 J/g)
+pub const CATASTROPHIC_ENERGY_THRESHOLD: f64 = 40_000.0;
+
+/// Characteristic length above which fragments use the large-object A/M distributions, in m
+const LARGE_FRAGMENT_LENGTH: f64 = 0.11;
+/// Characteristic length below which fragments use the small-object A/M distribution, in m
+const SMALL_FRAGMENT_LENGTH: f64 = 0.08;
+
+/// Resource configuring fragment generation
+#[derive(Resource, Clone, Debug)]
+pub struct BreakupConfig {
+    /// Smallest fragment characteristic length that is generated, in m
+    pub min_characteristic_length: f64,
+    /// Upper bound on generated fragments per event; the largest fragments are kept
+    pub max_fragments: usize,
+}
+
+impl Default for BreakupConfig {
+    fn default() -> Self {
+        Self {
+            min_characteristic_length: 0.1, // Trackable-debris threshold
+            max_fragments: 2000,
+        }
+    }
+}
+
+/// Resource accumulating breakup totals over the run
+#[derive(Resource, Default, Debug)]
+pub struct BreakupStatistics {
+    pub breakups: usize,
+    pub fragments: usize,
+    /// Mass in kg of fragments too small to track individually
+    pub unresolved_mass: f64,
+}
+
+impl BreakupStatistics {
+    pub fn record(&mut self, result: &BreakupResult) {
+        self.breakups += 1;
+        self.fragments += result.fragments.len();
+        self.unresolved_mass += result.unresolved_mass;
+    }
+}
+
+/// Fragmentation mechanism, which selects the delta-V distribution
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakupKind {
+    Collision,
+    Explosion,
+}
+
+/// Object taking part in a breakup
+#[derive(Clone, Copy, Debug)]
+pub struct BreakupParent {
+    /// Mass in kg
+    pub mass: f64,
+    /// Velocity in km/s
+    pub velocity: DVec3,
+    /// Characteristic length (largest dimension) in m
+    pub characteristic_length: f64,
+    pub class: ObjectClass,
+}
+
+/// Fragment produced by the breakup model
+#[derive(Clone, Copy, Debug)]
+pub struct Fragment {
+    /// Characteristic length in m
+    pub characteristic_length: f64,
+    /// Area-to-mass ratio in m²/kg
+    pub area_to_mass: f64,
+    /// Average cross-sectional area in m²
+    pub area: f64,
+    /// Mass in kg
+    pub mass: f64,
+    /// Velocity in km/s (parent velocity plus ejection delta-V)
+    pub velocity: DVec3,
+}
+
+/// Outcome of a single breakup event
+///
+/// Mass is conserved exactly: fragments, the unresolved bucket and the remnant add up to the
+/// parents' total mass. Momentum is conserved to floating-point precision once the unresolved
+/// bucket and the remnant are assigned `center_of_mass_velocity`.
+#[derive(Clone, Debug)]
+pub struct BreakupResult {
+    pub fragments: Vec<Fragment>,
+    /// Whether the collision exceeded the catastrophic energy threshold (always true for explosions)
+    pub catastrophic: bool,
+    /// Mass in kg of the target left intact by a non-catastrophic collision
+    pub remnant_mass: f64,
+    /// Mass in kg of fragments below the size threshold or dropped by the fragment cap
+    pub unresolved_mass: f64,
+    /// Velocity in km/s of the parents' center of mass, carried by the remnant and unresolved mass
+    pub center_of_mass_velocity: DVec3,
+}
+
+impl BreakupResult {
+    /// Total mass in kg of the generated fragments
+    pub fn fragment_mass(&self) -> f64 {
+        self.fragments.iter().map(|f| f.mass).sum()
+    }
+
+    /// Total mass in kg accounted for by fragments, unresolved mass and remnant
+    pub fn total_mass(&self) -> f64 {
+        self.fragment_mass() + self.unresolved_mass + self.remnant_mass
+    }
+
+    /// Total linear momentum in kg·km/s of fragments, unresolved mass and remnant
+    pub fn total_momentum(&self) -> DVec3 {
+        let fragments: DVec3 = self.fragments.iter().map(|f| f.velocity * f.mass).sum();
+        fragments + self.center_of_mass_velocity * (self.unresolved_mass + self.remnant_mass)
+    }
+}
+
+/// Linear momentum in kg·km/s of a set of parents
+pub fn parent_momentum(parents: &[&BreakupParent]) -> DVec3 {
+    parents.iter().map(|p| p.velocity * p.mass).sum()
+}
+
+/// Specific energy of the projectile relative to the target mass in J/kg
+pub fn specific_impact_energy(target_mass: f64, projectile_mass: f64, relative_speed_km_s: f64) -> f64 {
+    let relative_speed = MetersPerSec::from(KmPerSec(relative_speed_km_s));
+    0.5 * projectile_mass * relative_speed.0 * relative_speed.0 / target_mass
+}
+
+/// Number of collision fragments larger than `characteristic_length` (m)
+/// `reference_mass` is the total mass for catastrophic collisions, or m_p·v² (kg·km²/s²) otherwise
+pub fn collision_fragment_count(reference_mass: f64, characteristic_length: f64) -> f64 {
+    0.1 * reference_mass.powf(0.75) * characteristic_length.powf(-1.71)
+}
+
+/// Number of explosion fragments larger than `characteristic_length` (m)
+/// `scaling_factor` is the dimensionless S factor: 1 for a typical upper stage, lower for
+/// smaller or less energetic bodies
+pub fn explosion_fragment_count(scaling_factor: f64, characteristic_length: f64) -> f64 {
+    6.0 * scaling_factor * characteristic_length.powf(-1.6)
+}
+
+/// Average cross-sectional area in m² of a fragment with the given characteristic length (m)
+pub fn fragment_area(characteristic_length: f64) -> f64 {
+    if characteristic_length < 0.00167 {
+        0.540424 * characteristic_length * characteristic_length
+    } else {
+        0.556945 * characteristic_length.powf(2.0047077)
+    }
+}
+
+/// Fragment a target and projectile that collided at the relative speed of their velocities
+pub fn collision_breakup<R: Rng>(
+    config: &BreakupConfig,
+    first: &BreakupParent,
+    second: &BreakupParent,
+    rng: &mut R,
+) -> BreakupResult {
+    // The heavier object is the target
+    let (target, projectile) = if first.mass >= second.mass { (first, second) } else { (second, first) };
+    let relative_speed = KmPerSec::length(target.velocity - projectile.velocity).0;
+
+    let energy = specific_impact_energy(target.mass, projectile.mass, relative_speed);
+    let catastrophic = energy >= CATASTROPHIC_ENERGY_THRESHOLD;
+
+    let total_mass = target.mass + projectile.mass;
+    let (reference_mass, fragment_mass_budget) = if catastrophic {
+        (total_mass, total_mass)
+    } else {
+        let cratering_mass = projectile.mass * relative_speed * relative_speed;
+        (cratering_mass, cratering_mass.min(total_mass))
+    };
+
+    let max_length = target.characteristic_length.max(projectile.characteristic_length);
+    let count = sample_count(
+        collision_fragment_count(reference_mass, config.min_characteristic_length)
+            - collision_fragment_count(reference_mass, max_length),
+        rng,
+    );
+
+    let mut fragments = Vec::with_capacity(count);
+    let mut fragment_mass = 0.0;
+    for _ in 0..count {
+        let characteristic_length =
+            sample_characteristic_length(config.min_characteristic_length, max_length, 1.71, rng);
+
+        // Catastrophic fragments come from either body in proportion to mass; cratering ejecta from the target
+        let parent = if catastrophic && rng.gen::<f64>() * total_mass < projectile.mass {
+            projectile
+        } else {
+            target
+        };
+
+        let fragment =
+            sample_fragment(characteristic_length, parent, BreakupKind::Collision, KmPerSec(relative_speed), rng);
+        if fragment_mass + fragment.mass > fragment_mass_budget {
+            continue;
+        }
+        fragment_mass += fragment.mass;
+        fragments.push(fragment);
+    }
+
+    limit_fragments(&mut fragments, config.max_fragments);
+
+    let center_of_mass_velocity = parent_momentum(&[target, projectile]) / total_mass;
+    conserve_momentum(&mut fragments, center_of_mass_velocity);
+
+    let resolved_mass: f64 = fragments.iter().map(|f| f.mass).sum();
+    BreakupResult {
+        fragments,
+        catastrophic,
+        remnant_mass: if catastrophic { 0.0 } else { total_mass - fragment_mass_budget },
+        unresolved_mass: (fragment_mass_budget - resolved_mass).max(0.0),
+        center_of_mass_velocity,
+    }
+}
+
+/// Fragment a single object in an explosion; the whole mass is fragmented
+pub fn explosion_breakup<R: Rng>(
+    config: &BreakupConfig,
+    parent: &BreakupParent,
+    scaling_factor: f64,
+    rng: &mut R,
+) -> BreakupResult {
+    let max_length = parent.characteristic_length;
+    let count = sample_count(
+        explosion_fragment_count(scaling_factor, config.min_characteristic_length)
+            - explosion_fragment_count(scaling_factor, max_length),
+        rng,
+    );
+
+    let mut fragments = Vec::with_capacity(count);
+    let mut fragment_mass = 0.0;
+    for _ in 0..count {
+        let characteristic_length =
+            sample_characteristic_length(config.min_characteristic_length, max_length, 1.6, rng);
+        let fragment =
+            sample_fragment(characteristic_length, parent, BreakupKind::Explosion, KmPerSec(f64::INFINITY), rng);
+        if fragment_mass + fragment.mass > parent.mass {
+            continue;
+        }
+        fragment_mass += fragment.mass;
+        fragments.push(fragment);
+    }
+
+    limit_fragments(&mut fragments, config.max_fragments);
+    conserve_momentum(&mut fragments, parent.velocity);
+
+    let resolved_mass: f64 = fragments.iter().map(|f| f.mass).sum();
+    BreakupResult {
+        fragments,
+        catastrophic: true,
+        remnant_mass: 0.0,
+        unresolved_mass: (parent.mass - resolved_mass).max(0.0),
+        center_of_mass_velocity: parent.velocity,
+    }
+}
+
+/// Shift fragment velocities uniformly so the fragments' mass-weighted mean velocity
+/// equals `center_of_mass_velocity`, removing the net momentum of the random ejection kicks
+/// while keeping each fragment's velocity relative to the others
+fn conserve_momentum(fragments: &mut [Fragment], center_of_mass_velocity: DVec3) {
+    let mass: f64 = fragments.iter().map(|f| f.mass).sum();
+    if mass <= 0.0 {
+        return;
+    }
+    let momentum: DVec3 = fragments.iter().map(|f| f.velocity * f.mass).sum();
+    let correction = center_of_mass_velocity - momentum / mass;
+    for fragment in fragments {
+        fragment.velocity += correction;
+    }
+}
+
+/// Sample one fragment's A/M, area, mass and velocity
+/// The log-normal delta-V tail is capped at `max_delta_v` so no fragment leaves faster
+/// than the energy of the event allows
+fn sample_fragment<R: Rng>(
+    characteristic_length: f64,
+    parent: &BreakupParent,
+    kind: BreakupKind,
+    max_delta_v: KmPerSec,
+    rng: &mut R,
+) -> Fragment {
+    let area_to_mass = sample_area_to_mass(characteristic_length, parent.class, rng);
+    let area = fragment_area(characteristic_length);
+    let delta_v = KmPerSec::from(sample_delta_v(area_to_mass, kind, rng)).0.min(max_delta_v.0);
+
+    Fragment {
+        characteristic_length,
+        area_to_mass,
+        area,
+        mass: area / area_to_mass,
+        velocity: parent.velocity + random_direction(rng) * delta_v,
+    }
+}
+
+/// Round an expected count stochastically so the mean is preserved
+fn sample_count<R: Rng>(expected: f64, rng: &mut R) -> usize {
+    if expected <= 0.0 {
+        return 0;
+    }
+    let whole = expected.floor();
+    whole as usize + usize::from(rng.gen::<f64>() < expected - whole)
+}
+
+/// Sample a characteristic length from the truncated power law N(>Lc) ∝ Lc^-exponent
+pub fn sample_characteristic_length<R: Rng>(min: f64, max: f64, exponent: f64, rng: &mut R) -> f64 {
+    if max <= min {
+        return min;
+    }
+    let low = min.powf(-exponent);
+    let high = max.powf(-exponent);
+    (low - rng.gen::<f64>() * (low - high)).powf(-1.0 / exponent)
+}
+
+/// Sample an area-to-mass ratio in m²/kg for a fragment of the given characteristic length (m)
+pub fn sample_area_to_mass<R: Rng>(characteristic_length: f64, class: ObjectClass, rng: &mut R) -> f64 {
+    let lambda = characteristic_length.log10();
+
+    // Between 8 and 11 cm the small and large distributions are blended by log-size
+    let use_large = if characteristic_length >= LARGE_FRAGMENT_LENGTH {
+        true
+    } else if characteristic_length <= SMALL_FRAGMENT_LENGTH {
+        false
+    } else {
+        let blend = (lambda - SMALL_FRAGMENT_LENGTH.log10())
+            / (LARGE_FRAGMENT_LENGTH.log10() - SMALL_FRAGMENT_LENGTH.log10());
+        rng.gen::<f64>() < blend
+    };
+
+    let chi = if !use_large {
+        let mean = piecewise(lambda, -1.75, -0.3, -1.25, -1.0);
+        let sigma = if lambda <= -3.5 { 0.2 } else { 0.2 + 0.1333 * (lambda + 3.5) };
+        sample_normal(mean, sigma, rng)
+    } else {
+        let (alpha, mean1, sigma1, mean2, sigma2) = match class {
+            ObjectClass::RocketBody => (
+                piecewise(lambda, -1.4, 1.0, 0.0, 0.5),
+                piecewise(lambda, -0.5, -0.45, 0.0, -0.9),
+                0.55,
+                -0.9,
+                piecewise(lambda, -1.0, 0.28, 0.1, 0.1),
+            ),
+            ObjectClass::Spacecraft | ObjectClass::Debris => (
+                piecewise(lambda, -1.95, 0.0, 0.55, 1.0),
+                piecewise(lambda, -1.1, -0.6, 0.0, -0.95),
+                piecewise(lambda, -1.3, 0.1, -0.3, 0.3),
+                piecewise(lambda, -0.7, -1.2, -0.1, -2.0),
+                piecewise(lambda, -0.5, 0.5, -0.3, 0.3),
+            ),
+        };
+        if rng.gen::<f64>() < alpha {
+            sample_normal(mean1, sigma1, rng)
+        } else {
+            sample_normal(mean2, sigma2, rng)
+        }
+    };
+
+    10f64.powf(chi)
+}
+
+/// Sample an ejection speed for a fragment with the given A/M ratio (m²/kg)
+pub fn sample_delta_v<R: Rng>(area_to_mass: f64, kind: BreakupKind, rng: &mut R) -> MetersPerSec {
+    let chi = area_to_mass.log10();
+    let mean = match kind {
+        BreakupKind::Collision => 0.9 * chi + 2.9,
+        BreakupKind::Explosion => 0.2 * chi + 1.85,
+    };
+    MetersPerSec(10f64.powf(sample_normal(mean, 0.4, rng)))
+}
+
+/// Keep only the `max` largest fragments
+fn limit_fragments(fragments: &mut Vec<Fragment>, max: usize) {
+    if fragments.len() > max {
+        fragments.sort_by(|a, b| b.characteristic_length.total_cmp(&a.characteristic_length));
+        fragments.truncate(max);
+    }
+}
+
+/// Linear ramp between (x0, y0) and (x1, y1), clamped outside
+fn piecewise(x: f64, x0: f64, y0: f64, x1: f64, y1: f64) -> f64 {
+    if x <= x0 {
+        y0
+    } else if x >= x1 {
+        y1
+    } else {
+        y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+    }
+}
+
+/// Normal deviate via the Box-Muller transform
+fn sample_normal<R: Rng>(mean: f64, sigma: f64, rng: &mut R) -> f64 {
+    let u1 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2 = rng.gen::<f64>();
+    mean + sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Uniformly distributed unit vector
+fn random_direction<R: Rng>(rng: &mut R) -> DVec3 {
+    let z = rng.gen_range(-1.0..1.0f64);
+    let azimuth = rng.gen::<f64>() * 2.0 * std::f64::consts::PI;
+    let radial = (1.0 - z * z).sqrt();
+    DVec3::new(radial * azimuth.cos(), radial * azimuth.sin(), z)
+}
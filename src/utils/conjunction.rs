@@ -0,0 +1,243 @@
+// Conjunction screening: closest approach search, collision probability and avoidance geometry
+// Objects are flown along their mean orbits with the analytical propagator, so screening a
+// day ahead costs the same per sample as screening a minute ahead
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use bevy::math::DVec3;
+use rayon::prelude::*;
+use crate::utils::analytical::AnalyticalPropagator;
+use crate::utils::elements::KeplerianElements;
+use crate::utils::frames::rtn_to_inertial;
+
+/// Newton iterations refining the time of closest approach
+const CLOSEST_APPROACH_REFINEMENTS: usize = 4;
+/// Radial and angular cells used to integrate the collision probability
+const PROBABILITY_RINGS: usize = 32;
+const PROBABILITY_SECTORS: usize = 64;
+
+/// A predicted close approach between two objects
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Conjunction {
+    /// Seconds from the screening time to the time of closest approach
+    pub time_to_closest_approach: f64,
+    /// Separation at closest approach in km
+    pub miss_distance: f64,
+    /// Relative speed at closest approach in km/s
+    pub relative_speed: f64,
+    /// First object's position (km) and velocity (km/s) at closest approach
+    pub position: DVec3,
+    pub velocity: DVec3,
+    /// Second object's velocity relative to the first at closest approach, in km/s
+    pub relative_velocity: DVec3,
+}
+
+impl Conjunction {
+    /// Miss distance opened per km/s of prograde delta-V on the first object, `coast` seconds
+    /// before closest approach
+    ///
+    /// Only the offset across the relative velocity counts: motion along it just shifts the
+    /// time of closest approach.
+    pub fn avoidance_response(&self, mean_motion: f64, coast: f64) -> f64 {
+        let offset = rtn_to_inertial(self.position, self.velocity, along_track_response(mean_motion, coast));
+        let direction = self.relative_velocity.normalize_or_zero();
+        (offset - offset.dot(direction) * direction).length()
+    }
+}
+
+/// Apogee/perigee filter: whether two orbits' radial ranges come within `margin` km of each other
+pub fn radial_ranges_overlap(a: &KeplerianElements, b: &KeplerianElements, margin: f64) -> bool {
+    let a_apogee = a.apogee_radius().unwrap_or(f64::INFINITY);
+    let b_apogee = b.apogee_radius().unwrap_or(f64::INFINITY);
+    a.perigee_radius() - margin <= b_apogee && b.perigee_radius() - margin <= a_apogee
+}
+
+/// Pairs of objects that may pass within `distance` km of each other in the next `window`
+/// seconds, as (primary, other) indices into `objects`, sorted and without duplicates
+///
+/// Every object is flown to samples `step` seconds apart and binned into a grid whose cells are
+/// wide enough that a pair closing within `distance` in the half-step either side of a sample
+/// sits in neighbouring cells at it. Only pairs whose straight-line relative motion over that
+/// half-step, padded for the curvature of both orbits, comes within `distance` are kept.
+/// Objects that cannot be propagated are never paired.
+pub fn coarse_screen(
+    propagator: &AnalyticalPropagator,
+    objects: &[KeplerianElements],
+    primaries: &[usize],
+    window: f64,
+    step: f64,
+    distance: f64,
+) -> Vec<(usize, usize)> {
+    let half_step = 0.5 * step.min(window);
+    // Gravity bends each orbit away from a straight line by at most g·t²/2 over t seconds
+    let lowest = objects.iter().map(|o| o.perigee_radius()).fold(f64::INFINITY, f64::min);
+    let padding = propagator.mu / (lowest * lowest) * half_step * half_step;
+    let reach = distance + padding;
+
+    let samples = (window / step).ceil().max(0.0) as usize;
+    let mut pairs: Vec<(usize, usize)> = (0..=samples)
+        .into_par_iter()
+        .flat_map_iter(|sample| {
+            let time = (sample as f64 * step).min(window);
+            let states: Vec<Option<(DVec3, DVec3)>> = objects
+                .iter()
+                .map(|o| propagator.propagate(o, time, 0.0).map(|mean| propagator.osculating_state(&mean)))
+                .collect();
+
+            // Objects up to 2·v·t/2 + reach apart at the sample can close within reach
+            let fastest = states.iter().flatten().map(|(_, v)| v.length()).fold(0.0, f64::max);
+            let cell_size = fastest * 2.0 * half_step + reach;
+            let cell = |position: DVec3| (position / cell_size).floor().as_i64vec3().to_array();
+            let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+            for (index, state) in states.iter().enumerate() {
+                if let Some((position, _)) = state {
+                    grid.entry(cell(*position)).or_default().push(index);
+                }
+            }
+
+            let earliest = -half_step.min(time);
+            let latest = half_step.min(window - time);
+            let mut found = Vec::new();
+            for &primary in primaries {
+                let Some((position, velocity)) = states[primary] else {
+                    continue;
+                };
+                for neighbour in neighbouring_cells(cell(position)) {
+                    for &other in grid.get(&neighbour).into_iter().flatten() {
+                        if other == primary {
+                            continue;
+                        }
+                        let Some((other_position, other_velocity)) = states[other] else {
+                            continue;
+                        };
+                        let relative_position = other_position - position;
+                        let relative_velocity = other_velocity - velocity;
+                        let offset =
+                            linear_closest_approach(relative_position, relative_velocity).clamp(earliest, latest);
+                        if (relative_position + relative_velocity * offset).length() <= reach {
+                            found.push((primary, other));
+                        }
+                    }
+                }
+            }
+            found
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs.dedup();
+    pairs
+}
+
+/// A grid cell and the 26 cells around it
+fn neighbouring_cells([x, y, z]: [i64; 3]) -> impl Iterator<Item = [i64; 3]> {
+    (-1..=1).flat_map(move |dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| [x + dx, y + dy, z + dz])))
+}
+
+/// Time in seconds from now at which two objects in straight-line relative motion are closest
+fn linear_closest_approach(relative_position: DVec3, relative_velocity: DVec3) -> f64 {
+    let speed_squared = relative_velocity.length_squared();
+    if speed_squared < 1e-20 {
+        return 0.0;
+    }
+    -relative_position.dot(relative_velocity) / speed_squared
+}
+
+/// Closest approach between two objects within `window` seconds, sampling every `step` seconds
+///
+/// Relative motion is treated as a straight line between samples to bracket the minimum, then
+/// the time is refined against the propagated orbits. Returns `None` if either orbit cannot be
+/// propagated.
+pub fn closest_approach(
+    propagator: &AnalyticalPropagator,
+    a: &KeplerianElements,
+    b: &KeplerianElements,
+    window: f64,
+    step: f64,
+) -> Option<Conjunction> {
+    let states = |dt: f64| -> Option<((DVec3, DVec3), (DVec3, DVec3))> {
+        Some((
+            propagator.osculating_state(&propagator.propagate(a, dt, 0.0)?),
+            propagator.osculating_state(&propagator.propagate(b, dt, 0.0)?),
+        ))
+    };
+    let relative_state = |dt: f64| -> Option<(DVec3, DVec3)> {
+        let ((position_a, velocity_a), (position_b, velocity_b)) = states(dt)?;
+        Some((position_b - position_a, velocity_b - velocity_a))
+    };
+
+    let mut best_time = 0.0;
+    let mut best_distance = f64::INFINITY;
+    let samples = (window / step).ceil().max(1.0) as usize;
+    for sample in 0..samples {
+        let time = sample as f64 * step;
+        let (position, velocity) = relative_state(time)?;
+        let offset = linear_closest_approach(position, velocity).clamp(0.0, step.min(window - time));
+        let distance = (position + velocity * offset).length();
+        if distance < best_distance {
+            best_distance = distance;
+            best_time = time + offset;
+        }
+    }
+
+    // Each correction is held within one sample so it cannot jump to a different pass
+    for _ in 0..CLOSEST_APPROACH_REFINEMENTS {
+        let (position, velocity) = relative_state(best_time)?;
+        let correction = linear_closest_approach(position, velocity).clamp(-step, step);
+        best_time = (best_time + correction).clamp(0.0, window);
+    }
+
+    let ((position_a, velocity_a), (position_b, velocity_b)) = states(best_time)?;
+    let relative_velocity = velocity_b - velocity_a;
+    Some(Conjunction {
+        time_to_closest_approach: best_time,
+        miss_distance: (position_b - position_a).length(),
+        relative_speed: relative_velocity.length(),
+        position: position_a,
+        velocity: velocity_a,
+        relative_velocity,
+    })
+}
+
+/// Probability that two objects collide at a conjunction
+///
+/// The combined position uncertainty in the encounter plane is an isotropic Gaussian with
+/// standard deviation `sigma` km per axis, and the objects collide if they pass within
+/// `combined_radius` km. The Gaussian is integrated numerically over the hard-body disc.
+pub fn collision_probability(miss_distance: f64, combined_radius: f64, sigma: f64) -> f64 {
+    if combined_radius <= 0.0 {
+        return 0.0;
+    }
+    if sigma <= 0.0 {
+        return if miss_distance <= combined_radius { 1.0 } else { 0.0 };
+    }
+
+    let ring_width = combined_radius / PROBABILITY_RINGS as f64;
+    let sector_angle = TAU / PROBABILITY_SECTORS as f64;
+    let two_variance = 2.0 * sigma * sigma;
+    let mut sum = 0.0;
+    for ring in 0..PROBABILITY_RINGS {
+        let r = (ring as f64 + 0.5) * ring_width;
+        for sector in 0..PROBABILITY_SECTORS {
+            let angle = (sector as f64 + 0.5) * sector_angle;
+            let distance_squared = r * r + miss_distance * miss_distance - 2.0 * r * miss_distance * angle.cos();
+            sum += r * (-distance_squared / two_variance).exp();
+        }
+    }
+    (sum * ring_width * sector_angle / (TAU * sigma * sigma)).min(1.0)
+}
+
+/// Offset in RTN components (km) per km/s of prograde delta-V, `coast` seconds after the burn
+///
+/// Clohessy-Wiltshire solution for a circular orbit of mean motion `mean_motion` rad/s: the
+/// burn opens a radial offset of 2(1 - cos nt)/n and an along-track drift of 4 sin(nt)/n - 3t.
+pub fn along_track_response(mean_motion: f64, coast: f64) -> DVec3 {
+    if mean_motion <= 0.0 {
+        return DVec3::new(0.0, coast, 0.0);
+    }
+    let angle = mean_motion * coast;
+    DVec3::new(
+        2.0 * (1.0 - angle.cos()) / mean_motion,
+        4.0 * angle.sin() / mean_motion - 3.0 * coast,
+        0.0,
+    )
+}
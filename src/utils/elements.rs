@@ -0,0 +1,244 @@
+// Classical Keplerian and modified equinoctial orbital elements
+// Distances in km, angles in radians, gravitational parameters in km³/s²
+
+use bevy::math::DVec3;
+use std::f64::consts::TAU;
+
+/// Eccentricities and inclination sines below this are treated as exactly circular or equatorial
+pub const ELEMENT_SINGULARITY_TOLERANCE: f64 = 1e-11;
+
+/// Classical Keplerian orbital elements
+///
+/// Angles that are undefined for circular or equatorial orbits follow the usual conventions:
+/// a circular orbit has zero argument of perigee and its true anomaly is measured from the
+/// ascending node (argument of latitude); an equatorial orbit has zero RAAN and its argument of
+/// perigee is measured from the X axis (longitude of perigee); a circular equatorial orbit
+/// puts everything in the true anomaly (true longitude).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeplerianElements {
+    /// Semi-major axis in km, negative for hyperbolic orbits
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    /// Inclination in radians, [0, π]
+    pub inclination: f64,
+    /// Right ascension of the ascending node in radians, [0, 2π)
+    pub raan: f64,
+    /// Argument of perigee in radians, [0, 2π)
+    pub arg_perigee: f64,
+    /// True anomaly in radians, [0, 2π)
+    pub true_anomaly: f64,
+}
+
+impl KeplerianElements {
+    /// Elements of the orbit through a position (km) and velocity (km/s)
+    pub fn from_state(position: DVec3, velocity: DVec3, mu: f64) -> Self {
+        let r = position.length();
+        let angular_momentum = position.cross(velocity);
+        let h_hat = angular_momentum.normalize();
+        let node = DVec3::Z.cross(angular_momentum);
+        let eccentricity_vector =
+            ((velocity.length_squared() - mu / r) * position - position.dot(velocity) * velocity) / mu;
+        let eccentricity = eccentricity_vector.length();
+        let energy = 0.5 * velocity.length_squared() - mu / r;
+
+        let equatorial = node.length() < ELEMENT_SINGULARITY_TOLERANCE * angular_momentum.length();
+        let circular = eccentricity < ELEMENT_SINGULARITY_TOLERANCE;
+
+        // Angles are measured about the orbit normal from the node line, or the X axis if there is none
+        let reference = if equatorial { DVec3::X } else { node.normalize() };
+        let angle_from = |from: DVec3, to: DVec3| from.cross(to).dot(h_hat).atan2(from.dot(to)).rem_euclid(TAU);
+        let periapsis = if circular { reference } else { eccentricity_vector };
+
+        Self {
+            semi_major_axis: -mu / (2.0 * energy),
+            eccentricity,
+            inclination: h_hat.z.clamp(-1.0, 1.0).acos(),
+            raan: if equatorial { 0.0 } else { node.y.atan2(node.x).rem_euclid(TAU) },
+            arg_perigee: if circular { 0.0 } else { angle_from(reference, eccentricity_vector) },
+            true_anomaly: angle_from(periapsis, position),
+        }
+    }
+
+    /// Position (km) and velocity (km/s) on the orbit
+    pub fn to_state(&self, mu: f64) -> (DVec3, DVec3) {
+        let p = self.semi_latus_rectum();
+        let (sin_nu, cos_nu) = self.true_anomaly.sin_cos();
+        let r = p / (1.0 + self.eccentricity * cos_nu);
+        let speed = (mu / p).sqrt();
+
+        let perifocal_position = DVec3::new(r * cos_nu, r * sin_nu, 0.0);
+        let perifocal_velocity = DVec3::new(-speed * sin_nu, speed * (self.eccentricity + cos_nu), 0.0);
+        (self.perifocal_to_inertial(perifocal_position), self.perifocal_to_inertial(perifocal_velocity))
+    }
+
+    /// Rotate a vector from the perifocal frame (X to perigee, Z along the orbit normal)
+    fn perifocal_to_inertial(&self, v: DVec3) -> DVec3 {
+        let (sin_o, cos_o) = self.raan.sin_cos();
+        let (sin_w, cos_w) = self.arg_perigee.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+
+        let x = DVec3::new(
+            cos_o * cos_w - sin_o * sin_w * cos_i,
+            sin_o * cos_w + cos_o * sin_w * cos_i,
+            sin_w * sin_i,
+        );
+        let y = DVec3::new(
+            -cos_o * sin_w - sin_o * cos_w * cos_i,
+            -sin_o * sin_w + cos_o * cos_w * cos_i,
+            cos_w * sin_i,
+        );
+        x * v.x + y * v.y
+    }
+
+    /// Semi-latus rectum a(1 - e²) in km
+    pub fn semi_latus_rectum(&self) -> f64 {
+        self.semi_major_axis * (1.0 - self.eccentricity * self.eccentricity)
+    }
+
+    /// Perigee distance from Earth's centre in km
+    pub fn perigee_radius(&self) -> f64 {
+        self.semi_latus_rectum() / (1.0 + self.eccentricity)
+    }
+
+    /// Apogee distance from Earth's centre in km, `None` for escape orbits
+    pub fn apogee_radius(&self) -> Option<f64> {
+        self.is_bound().then_some(self.semi_major_axis * (1.0 + self.eccentricity))
+    }
+
+    /// Orbital period in seconds, `None` for escape orbits
+    pub fn period(&self, mu: f64) -> Option<f64> {
+        self.is_bound().then(|| TAU * (self.semi_major_axis.powi(3) / mu).sqrt())
+    }
+
+    /// Mean motion in rad/s, `None` for escape orbits
+    pub fn mean_motion(&self, mu: f64) -> Option<f64> {
+        self.is_bound().then(|| (mu / self.semi_major_axis.powi(3)).sqrt())
+    }
+
+    pub fn is_bound(&self) -> bool {
+        self.eccentricity < 1.0 && self.semi_major_axis > 0.0
+    }
+
+    /// Eccentric anomaly in radians, [0, 2π), for elliptic orbits
+    pub fn eccentric_anomaly(&self) -> f64 {
+        let e = self.eccentricity;
+        let (sin_nu, cos_nu) = self.true_anomaly.sin_cos();
+        ((1.0 - e * e).sqrt() * sin_nu).atan2(e + cos_nu).rem_euclid(TAU)
+    }
+
+    /// Argument of latitude in radians, [0, 2π): angle from the ascending node to the object
+    pub fn argument_of_latitude(&self) -> f64 {
+        (self.arg_perigee + self.true_anomaly).rem_euclid(TAU)
+    }
+
+    /// Mean anomaly in radians, [0, 2π), for elliptic orbits
+    pub fn mean_anomaly(&self) -> f64 {
+        let eccentric_anomaly = self.eccentric_anomaly();
+        (eccentric_anomaly - self.eccentricity * eccentric_anomaly.sin()).rem_euclid(TAU)
+    }
+
+    pub fn to_equinoctial(&self) -> EquinoctialElements {
+        let longitude_of_perigee = self.raan + self.arg_perigee;
+        let tan_half_i = (0.5 * self.inclination).tan();
+        EquinoctialElements {
+            semi_latus_rectum: self.semi_latus_rectum(),
+            f: self.eccentricity * longitude_of_perigee.cos(),
+            g: self.eccentricity * longitude_of_perigee.sin(),
+            h: tan_half_i * self.raan.cos(),
+            k: tan_half_i * self.raan.sin(),
+            true_longitude: (longitude_of_perigee + self.true_anomaly).rem_euclid(TAU),
+        }
+    }
+}
+
+/// Modified equinoctial orbital elements
+///
+/// Non-singular for circular and equatorial orbits; only exactly retrograde equatorial orbits
+/// (inclination π) cannot be represented.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EquinoctialElements {
+    /// Semi-latus rectum in km
+    pub semi_latus_rectum: f64,
+    /// e·cos(ω + Ω)
+    pub f: f64,
+    /// e·sin(ω + Ω)
+    pub g: f64,
+    /// tan(i/2)·cos Ω
+    pub h: f64,
+    /// tan(i/2)·sin Ω
+    pub k: f64,
+    /// True longitude Ω + ω + ν in radians, [0, 2π)
+    pub true_longitude: f64,
+}
+
+impl EquinoctialElements {
+    pub fn from_state(position: DVec3, velocity: DVec3, mu: f64) -> Self {
+        let angular_momentum = position.cross(velocity);
+        let h_hat = angular_momentum.normalize();
+        let h = -h_hat.y / (1.0 + h_hat.z);
+        let k = h_hat.x / (1.0 + h_hat.z);
+
+        let elements = Self {
+            semi_latus_rectum: angular_momentum.length_squared() / mu,
+            f: 0.0,
+            g: 0.0,
+            h,
+            k,
+            true_longitude: 0.0,
+        };
+        let (f_hat, g_hat) = elements.basis();
+        let r = position.length();
+        let eccentricity_vector =
+            ((velocity.length_squared() - mu / r) * position - position.dot(velocity) * velocity) / mu;
+
+        Self {
+            f: eccentricity_vector.dot(f_hat),
+            g: eccentricity_vector.dot(g_hat),
+            true_longitude: position.dot(g_hat).atan2(position.dot(f_hat)).rem_euclid(TAU),
+            ..elements
+        }
+    }
+
+    pub fn to_state(&self, mu: f64) -> (DVec3, DVec3) {
+        let (f_hat, g_hat) = self.basis();
+        let (sin_l, cos_l) = self.true_longitude.sin_cos();
+        let r = self.semi_latus_rectum / (1.0 + self.f * cos_l + self.g * sin_l);
+        let speed = (mu / self.semi_latus_rectum).sqrt();
+
+        let position = r * (cos_l * f_hat + sin_l * g_hat);
+        let velocity = speed * (-(sin_l + self.g) * f_hat + (cos_l + self.f) * g_hat);
+        (position, velocity)
+    }
+
+    /// In-plane unit vectors of the equinoctial frame
+    fn basis(&self) -> (DVec3, DVec3) {
+        let (h, k) = (self.h, self.k);
+        let s2 = 1.0 + h * h + k * k;
+        let f_hat = DVec3::new(1.0 - k * k + h * h, 2.0 * h * k, -2.0 * k) / s2;
+        let g_hat = DVec3::new(2.0 * h * k, 1.0 + k * k - h * h, 2.0 * h) / s2;
+        (f_hat, g_hat)
+    }
+
+    pub fn eccentricity(&self) -> f64 {
+        (self.f * self.f + self.g * self.g).sqrt()
+    }
+
+    pub fn to_keplerian(&self) -> KeplerianElements {
+        let eccentricity = self.eccentricity();
+        let tan_half_i = (self.h * self.h + self.k * self.k).sqrt();
+        let equatorial = tan_half_i < ELEMENT_SINGULARITY_TOLERANCE;
+        let circular = eccentricity < ELEMENT_SINGULARITY_TOLERANCE;
+
+        let raan = if equatorial { 0.0 } else { self.k.atan2(self.h).rem_euclid(TAU) };
+        let longitude_of_perigee = if circular { raan } else { self.g.atan2(self.f) };
+
+        KeplerianElements {
+            semi_major_axis: self.semi_latus_rectum / (1.0 - eccentricity * eccentricity),
+            eccentricity,
+            inclination: 2.0 * tan_half_i.atan(),
+            raan,
+            arg_perigee: (longitude_of_perigee - raan).rem_euclid(TAU),
+            true_anomaly: (self.true_longitude - longitude_of_perigee).rem_euclid(TAU),
+        }
+    }
+}
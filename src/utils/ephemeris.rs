@@ -0,0 +1,125 @@
+// Low-precision analytical Sun and Moon ephemerides and their third-body gravity
+// Series from Montenbruck & Gill, Satellite Orbits §3.3.2, referred to the J2000 ecliptic and
+// rotated into GCRF; good to about 0.1° for the Sun and a few tenths of a degree for the Moon
+
+use bevy::math::DVec3;
+use crate::resources::Constants;
+use crate::utils::time::Epoch;
+use crate::utils::units::Km3PerSec2;
+
+/// Obliquity of the J2000 ecliptic in degrees
+const J2000_OBLIQUITY_DEG: f64 = 23.43929111;
+
+/// Angle in arcseconds to radians
+fn arcsec(value: f64) -> f64 {
+    (value / 3600.0).to_radians()
+}
+
+/// Rotate ecliptic longitude, latitude (radians) and distance (km) into GCRF
+fn ecliptic_to_gcrf(longitude: f64, latitude: f64, distance: f64) -> DVec3 {
+    let (sin_lon, cos_lon) = longitude.sin_cos();
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let (sin_eps, cos_eps) = J2000_OBLIQUITY_DEG.to_radians().sin_cos();
+    let ecliptic = distance * DVec3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat);
+    DVec3::new(
+        ecliptic.x,
+        cos_eps * ecliptic.y - sin_eps * ecliptic.z,
+        sin_eps * ecliptic.y + cos_eps * ecliptic.z,
+    )
+}
+
+/// Geocentric position of the Sun in km (GCRF)
+pub fn sun_position(epoch: &Epoch) -> DVec3 {
+    let t = epoch.julian_centuries_tt();
+    let mean_anomaly = (357.5256 + 35999.049 * t).to_radians();
+    let longitude = 282.9400_f64.to_radians()
+        + mean_anomaly
+        + arcsec(6892.0 * mean_anomaly.sin() + 72.0 * (2.0 * mean_anomaly).sin());
+    let distance = (149.619 - 2.499 * mean_anomaly.cos() - 0.021 * (2.0 * mean_anomaly).cos()) * 1e6;
+    ecliptic_to_gcrf(longitude, 0.0, distance)
+}
+
+/// Geocentric position of the Moon in km (GCRF)
+pub fn moon_position(epoch: &Epoch) -> DVec3 {
+    let t = epoch.julian_centuries_tt();
+    // Mean longitude less precession since J2000, and the Delaunay arguments
+    let mean_longitude = (218.31617 + 481267.88088 * t - 1.3972 * t).to_radians();
+    let l = (134.96292 + 477198.86753 * t).to_radians();
+    let lp = (357.52543 + 35999.04944 * t).to_radians();
+    let f = (93.27283 + 483202.01873 * t).to_radians();
+    let d = (297.85027 + 445267.11135 * t).to_radians();
+
+    let longitude = mean_longitude
+        + arcsec(
+            22640.0 * l.sin() + 769.0 * (2.0 * l).sin() - 4586.0 * (l - 2.0 * d).sin()
+                + 2370.0 * (2.0 * d).sin()
+                - 668.0 * lp.sin()
+                - 412.0 * (2.0 * f).sin()
+                - 212.0 * (2.0 * l - 2.0 * d).sin()
+                - 206.0 * (l + lp - 2.0 * d).sin()
+                + 192.0 * (l + 2.0 * d).sin()
+                - 165.0 * (lp - 2.0 * d).sin()
+                + 148.0 * (l - lp).sin()
+                - 125.0 * d.sin()
+                - 110.0 * (l + lp).sin()
+                - 55.0 * (2.0 * f - 2.0 * d).sin(),
+        );
+    let latitude = arcsec(
+        18520.0 * (f + longitude - mean_longitude + arcsec(412.0 * (2.0 * f).sin() + 541.0 * lp.sin())).sin()
+            - 526.0 * (f - 2.0 * d).sin()
+            + 44.0 * (l + f - 2.0 * d).sin()
+            - 31.0 * (-l + f - 2.0 * d).sin()
+            - 25.0 * (-2.0 * l + f).sin()
+            - 23.0 * (lp + f - 2.0 * d).sin()
+            + 21.0 * (-l + f).sin()
+            + 11.0 * (-lp + f - 2.0 * d).sin(),
+    );
+    let distance = 385000.0 - 20905.0 * l.cos() - 3699.0 * (2.0 * d - l).cos() - 2956.0 * (2.0 * d).cos()
+        - 570.0 * (2.0 * l).cos()
+        + 246.0 * (2.0 * l - 2.0 * d).cos()
+        - 205.0 * (lp - 2.0 * d).cos()
+        - 171.0 * (l + 2.0 * d).cos()
+        - 152.0 * (l + lp - 2.0 * d).cos();
+
+    ecliptic_to_gcrf(longitude, latitude, distance)
+}
+
+/// Lunisolar point-mass perturbations, with the bodies held at fixed positions
+///
+/// The Sun and Moon move slowly compared with a frame, so the physics stage evaluates their
+/// positions once at the frame midpoint.
+#[derive(Clone, Copy, Debug)]
+pub struct ThirdBodyGravity {
+    /// Geocentric Sun position in km
+    pub sun: DVec3,
+    /// Geocentric Moon position in km
+    pub moon: DVec3,
+    /// Sun's gravitational parameter
+    pub sun_mu: Km3PerSec2,
+    /// Moon's gravitational parameter
+    pub moon_mu: Km3PerSec2,
+}
+
+impl ThirdBodyGravity {
+    /// Sun and Moon at the given epoch
+    pub fn at(constants: &Constants, epoch: &Epoch) -> Self {
+        Self {
+            sun: sun_position(epoch),
+            moon: moon_position(epoch),
+            sun_mu: constants.sun_gravitational_parameter,
+            moon_mu: constants.moon_gravitational_parameter,
+        }
+    }
+
+    /// Perturbing acceleration in km/s² on an object at `position` (km)
+    pub fn acceleration_f64(&self, position: DVec3) -> DVec3 {
+        third_body_acceleration(position, self.sun, self.sun_mu)
+            + third_body_acceleration(position, self.moon, self.moon_mu)
+    }
+}
+
+/// Difference between a body's pull on the object and on the Earth, in km/s²
+pub fn third_body_acceleration(position: DVec3, body: DVec3, mu: Km3PerSec2) -> DVec3 {
+    let relative = body - position;
+    mu.0 * (relative / relative.length().powi(3) - body / body.length().powi(3))
+}
@@ -0,0 +1,87 @@
+use bevy::math::DVec3;
+use crate::resources::Constants;
+use crate::utils::atmosphere::*;
+use crate::utils::ephemeris::ThirdBodyGravity;
+use crate::utils::gravity::*;
+use crate::utils::radiation_pressure::SolarRadiationPressure;
+use crate::utils::time::Epoch;
+
+/// Per-object properties the non-gravitational forces scale with
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ForceCoefficients {
+    /// Ballistic coefficient Cd·A/m in m²/kg for drag
+    pub ballistic_coefficient: f64,
+    /// Area-to-mass ratio A/m in m²/kg for solar radiation pressure
+    pub area_to_mass: f64,
+}
+
+impl ForceCoefficients {
+    /// Coefficients for an object that feels drag but no radiation pressure
+    pub fn drag_only(ballistic_coefficient: f64) -> Self {
+        Self { ballistic_coefficient, area_to_mass: 0.0 }
+    }
+}
+
+/// Combined force model evaluated by every integrator: geopotential plus optional drag,
+/// lunisolar gravity and solar radiation pressure
+#[derive(Clone, Copy, Debug)]
+pub struct ForceModel {
+    pub gravity: Geopotential,
+    /// Atmospheric drag, `None` for a vacuum
+    pub drag: Option<AtmosphericDrag>,
+    /// Sun and Moon point-mass perturbations, `None` to leave them out
+    pub third_body: Option<ThirdBodyGravity>,
+    /// Solar radiation pressure, `None` to leave it out
+    pub radiation_pressure: Option<SolarRadiationPressure>,
+}
+
+impl ForceModel {
+    /// Build the force model from the simulation constants
+    pub fn new(constants: &Constants, gravity_model: GravityModel, atmosphere: Option<AtmosphereModel>) -> Self {
+        Self {
+            gravity: Geopotential::new(constants, gravity_model),
+            drag: atmosphere.map(|model| AtmosphericDrag {
+                model,
+                earth_radius: constants.earth_radius,
+                earth_rotation_rate: constants.earth_rotation_rate,
+            }),
+            third_body: None,
+            radiation_pressure: None,
+        }
+    }
+
+    /// Gravity without any non-conservative forces
+    pub fn gravity_only(gravity: Geopotential) -> Self {
+        Self { gravity, drag: None, third_body: None, radiation_pressure: None }
+    }
+
+    /// Add Sun and Moon gravity with the bodies placed at `epoch`
+    pub fn with_third_body(mut self, constants: &Constants, epoch: &Epoch) -> Self {
+        self.third_body = Some(ThirdBodyGravity::at(constants, epoch));
+        self
+    }
+
+    /// Add solar radiation pressure with the Sun placed at `epoch`
+    pub fn with_radiation_pressure(mut self, constants: &Constants, epoch: &Epoch, reflectivity: f64) -> Self {
+        self.radiation_pressure = Some(SolarRadiationPressure::at(constants, epoch, reflectivity));
+        self
+    }
+
+    /// Total acceleration in km/s² for a position (km) and velocity (km/s)
+    ///
+    /// `coefficients` carries the object's drag and radiation pressure properties
+    /// (see [`crate::components::PhysicsObject::force_coefficients`])
+    pub fn acceleration_f64(&self, position: DVec3, velocity: DVec3, coefficients: ForceCoefficients) -> DVec3 {
+        let mut acc = self.gravity.acceleration_f64(position);
+        if let Some(drag) = &self.drag {
+            acc += drag.acceleration_f64(position, velocity, coefficients.ballistic_coefficient);
+        }
+        if let Some(third_body) = &self.third_body {
+            acc += third_body.acceleration_f64(position);
+        }
+        if let Some(radiation_pressure) = &self.radiation_pressure {
+            acc += radiation_pressure.acceleration_f64(position, coefficients.area_to_mass);
+        }
+        acc
+    }
+}
@@ -0,0 +1,257 @@
+// Reference frame conversions between the simulation's inertial frame and the rotating Earth
+// The inertial frame is GCRF (J2000 axes); SGP4 states arrive in TEME and are rotated into it.
+// Earth orientation follows IAU-76 precession and a truncated IAU-80 nutation, with UT1 ≈ UTC
+// and no polar motion, which keeps ground points within ~20 m at LEO altitudes.
+// Geodetic coordinates are on the WGS-84 ellipsoid
+
+use bevy::math::{DMat3, DVec3};
+use std::f64::consts::TAU;
+use crate::utils::time::{Epoch, SECONDS_PER_DAY};
+
+/// WGS-84 semi-major axis in km
+pub const WGS84_EQUATORIAL_RADIUS: f64 = 6378.137;
+/// WGS-84 flattening
+pub const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+
+/// Earth's sidereal rotation rate in rad/s
+pub const EARTH_ROTATION_RATE: f64 = 7.292115146706979e-5;
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+const DAYS_PER_JULIAN_CENTURY: f64 = 36525.0;
+
+/// Geodetic coordinates on the WGS-84 ellipsoid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Geodetic {
+    /// Geodetic latitude in degrees, positive north
+    pub latitude: f64,
+    /// Longitude in degrees, in (-180, 180], positive east
+    pub longitude: f64,
+    /// Height above the ellipsoid in km
+    pub altitude: f64,
+}
+
+/// Rotate an inertial position (km) into the Earth-fixed frame
+/// `rotation_angle` is the angle in radians between the inertial X axis and the Greenwich meridian
+pub fn inertial_to_earth_fixed(position: DVec3, rotation_angle: f64) -> DVec3 {
+    let (sin, cos) = rotation_angle.sin_cos();
+    DVec3::new(
+        cos * position.x + sin * position.y,
+        -sin * position.x + cos * position.y,
+        position.z,
+    )
+}
+
+/// Radial, transverse (along-track) and orbit-normal unit vectors of an inertial state
+pub fn rtn_basis(position: DVec3, velocity: DVec3) -> (DVec3, DVec3, DVec3) {
+    let radial = position.normalize();
+    let normal = position.cross(velocity).normalize();
+    (radial, normal.cross(radial), normal)
+}
+
+/// Rotate a vector given in RTN components into the inertial frame
+pub fn rtn_to_inertial(position: DVec3, velocity: DVec3, rtn: DVec3) -> DVec3 {
+    let (radial, transverse, normal) = rtn_basis(position, velocity);
+    rtn.x * radial + rtn.y * transverse + rtn.z * normal
+}
+
+/// Rotate an inertial vector into RTN components
+pub fn inertial_to_rtn(position: DVec3, velocity: DVec3, vector: DVec3) -> DVec3 {
+    let (radial, transverse, normal) = rtn_basis(position, velocity);
+    DVec3::new(vector.dot(radial), vector.dot(transverse), vector.dot(normal))
+}
+
+/// Convert an Earth-fixed position (km) to WGS-84 geodetic coordinates
+pub fn earth_fixed_to_geodetic(position: DVec3) -> Geodetic {
+    let a = WGS84_EQUATORIAL_RADIUS;
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+
+    let p = (position.x * position.x + position.y * position.y).sqrt();
+    let longitude = position.y.atan2(position.x);
+
+    // Fixed-point iteration on latitude; converges to sub-millimetre in a handful of passes
+    let mut latitude = position.z.atan2(p * (1.0 - e2));
+    let mut altitude = 0.0;
+    for _ in 0..10 {
+        let sin_lat = latitude.sin();
+        let n = a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        altitude = if latitude.cos().abs() > 1e-10 {
+            p / latitude.cos() - n
+        } else {
+            position.z.abs() - n * (1.0 - e2)
+        };
+        let next = position.z.atan2(p * (1.0 - e2 * n / (n + altitude)));
+        if (next - latitude).abs() < 1e-14 {
+            latitude = next;
+            break;
+        }
+        latitude = next;
+    }
+
+    Geodetic {
+        latitude: latitude.to_degrees(),
+        longitude: longitude.to_degrees(),
+        altitude,
+    }
+}
+
+/// WGS-84 geodetic coordinates to an Earth-fixed position (km)
+pub fn geodetic_to_earth_fixed(geodetic: &Geodetic) -> DVec3 {
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let (sin_lat, cos_lat) = geodetic.latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = geodetic.longitude.to_radians().sin_cos();
+    let n = WGS84_EQUATORIAL_RADIUS / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    DVec3::new(
+        (n + geodetic.altitude) * cos_lat * cos_lon,
+        (n + geodetic.altitude) * cos_lat * sin_lon,
+        (n * (1.0 - e2) + geodetic.altitude) * sin_lat,
+    )
+}
+
+/// UT1 days since J2000.0, taking UT1 = UTC
+fn ut1_days_since_j2000(epoch: &Epoch) -> f64 {
+    epoch.utc_julian_day() - crate::utils::sgp4_wrapper::J2000_JULIAN_DAY
+}
+
+/// Greenwich mean sidereal time (IAU-82) in radians, [0, 2π)
+/// This is the angle that takes TEME to the Earth-fixed frame.
+pub fn gmst(epoch: &Epoch) -> f64 {
+    let days = ut1_days_since_j2000(epoch);
+    let t = days / DAYS_PER_JULIAN_CENTURY;
+    // Split the 24h-per-day term off so the large part stays exact in f64
+    let seconds = 67310.54841 + (8640184.812866 + (0.093104 - 6.2e-6 * t) * t) * t;
+    (days.fract() * TAU + seconds / SECONDS_PER_DAY * TAU).rem_euclid(TAU)
+}
+
+/// Earth rotation angle (IAU-2000) in radians, [0, 2π)
+/// The CIO-based counterpart of GMST; the two differ by the accumulated precession in RA.
+pub fn earth_rotation_angle(epoch: &Epoch) -> f64 {
+    let days = ut1_days_since_j2000(epoch);
+    (TAU * (0.7790572732640 + 0.00273781191135448 * days + days.fract())).rem_euclid(TAU)
+}
+
+/// Nutation in longitude and obliquity plus the true obliquity, all in radians
+/// Only the four largest IAU-80 terms are kept, good to about 0.5″.
+fn nutation(t: f64) -> (f64, f64, f64) {
+    let node = (125.04452 - 1934.136261 * t).to_radians();
+    let sun = (280.4665 + 36000.7698 * t).to_radians();
+    let moon = (218.3165 + 481267.8813 * t).to_radians();
+
+    let delta_psi = -17.20 * node.sin() - 1.32 * (2.0 * sun).sin() - 0.23 * (2.0 * moon).sin()
+        + 0.21 * (2.0 * node).sin();
+    let delta_epsilon = 9.20 * node.cos() + 0.57 * (2.0 * sun).cos() + 0.10 * (2.0 * moon).cos()
+        - 0.09 * (2.0 * node).cos();
+    let mean_obliquity = 84381.448 - (46.8150 + (0.00059 - 0.001813 * t) * t) * t;
+
+    (
+        delta_psi * ARCSEC_TO_RAD,
+        delta_epsilon * ARCSEC_TO_RAD,
+        mean_obliquity * ARCSEC_TO_RAD,
+    )
+}
+
+/// Rotation taking TEME vectors to GCRF at the given epoch
+pub fn teme_to_gcrf_matrix(epoch: &Epoch) -> DMat3 {
+    let t = epoch.julian_centuries_tt();
+    let (delta_psi, delta_epsilon, mean_obliquity) = nutation(t);
+    let equation_of_equinoxes = delta_psi * mean_obliquity.cos();
+
+    // IAU-76 precession angles
+    let zeta = (2306.2181 + (0.30188 + 0.017998 * t) * t) * t * ARCSEC_TO_RAD;
+    let theta = (2004.3109 - (0.42665 + 0.041833 * t) * t) * t * ARCSEC_TO_RAD;
+    let z = (2306.2181 + (1.09468 + 0.018203 * t) * t) * t * ARCSEC_TO_RAD;
+
+    let teme_to_true_of_date = DMat3::from_rotation_z(equation_of_equinoxes);
+    let true_to_mean_of_date = DMat3::from_rotation_x(mean_obliquity)
+        * DMat3::from_rotation_z(-delta_psi)
+        * DMat3::from_rotation_x(-(mean_obliquity + delta_epsilon));
+    let mean_of_date_to_gcrf =
+        DMat3::from_rotation_z(-zeta) * DMat3::from_rotation_y(theta) * DMat3::from_rotation_z(-z);
+
+    mean_of_date_to_gcrf * true_to_mean_of_date * teme_to_true_of_date
+}
+
+/// Rotate a TEME position and velocity (SGP4 output) into GCRF
+/// TEME's own rotation is negligible, so the velocity is rotated like the position.
+pub fn teme_to_gcrf(position: DVec3, velocity: DVec3, epoch: &Epoch) -> (DVec3, DVec3) {
+    let rotation = teme_to_gcrf_matrix(epoch);
+    (rotation * position, rotation * velocity)
+}
+
+/// Rotate a GCRF position and velocity into TEME
+pub fn gcrf_to_teme(position: DVec3, velocity: DVec3, epoch: &Epoch) -> (DVec3, DVec3) {
+    let rotation = teme_to_gcrf_matrix(epoch).transpose();
+    (rotation * position, rotation * velocity)
+}
+
+/// Rotation taking GCRF vectors to the Earth-fixed frame at the given epoch
+pub fn gcrf_to_earth_fixed_matrix(epoch: &Epoch) -> DMat3 {
+    DMat3::from_rotation_z(-gmst(epoch)) * teme_to_gcrf_matrix(epoch).transpose()
+}
+
+/// Earth-fixed position (km) of a GCRF position at the given epoch
+pub fn eci_to_ecef(position: DVec3, epoch: &Epoch) -> DVec3 {
+    gcrf_to_earth_fixed_matrix(epoch) * position
+}
+
+/// GCRF position (km) of an Earth-fixed position at the given epoch
+pub fn ecef_to_eci(position: DVec3, epoch: &Epoch) -> DVec3 {
+    gcrf_to_earth_fixed_matrix(epoch).transpose() * position
+}
+
+/// Earth-fixed position (km) and velocity relative to the ground (km/s) of a GCRF state
+pub fn eci_to_ecef_state(position: DVec3, velocity: DVec3, epoch: &Epoch) -> (DVec3, DVec3) {
+    let rotation = gcrf_to_earth_fixed_matrix(epoch);
+    let earth_fixed = rotation * position;
+    let spin = DVec3::new(0.0, 0.0, EARTH_ROTATION_RATE);
+    (earth_fixed, rotation * velocity - spin.cross(earth_fixed))
+}
+
+/// GCRF position (km) and velocity (km/s) of an Earth-fixed state
+pub fn ecef_to_eci_state(position: DVec3, velocity: DVec3, epoch: &Epoch) -> (DVec3, DVec3) {
+    let rotation = gcrf_to_earth_fixed_matrix(epoch).transpose();
+    let spin = DVec3::new(0.0, 0.0, EARTH_ROTATION_RATE);
+    (rotation * position, rotation * (velocity + spin.cross(position)))
+}
+
+/// Sub-satellite point of a GCRF position, the basis of ground tracks
+pub fn ground_point(position: DVec3, epoch: &Epoch) -> Geodetic {
+    earth_fixed_to_geodetic(eci_to_ecef(position, epoch))
+}
+
+/// Direction and distance from a ground site to a target
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LookAngles {
+    /// Azimuth in degrees clockwise from north, [0, 360)
+    pub azimuth: f64,
+    /// Elevation above the local horizon in degrees
+    pub elevation: f64,
+    /// Slant range in km
+    pub range: f64,
+}
+
+impl LookAngles {
+    /// Whether the target is above the site's elevation mask (degrees)
+    pub fn is_visible(&self, min_elevation: f64) -> bool {
+        self.elevation >= min_elevation
+    }
+}
+
+/// Look angles from a ground site to an Earth-fixed target position (km)
+pub fn look_angles(site: &Geodetic, target: DVec3) -> LookAngles {
+    let (sin_lat, cos_lat) = site.latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = site.longitude.to_radians().sin_cos();
+    let offset = target - geodetic_to_earth_fixed(site);
+
+    // Local east-north-up axes of the ellipsoid normal at the site
+    let east = DVec3::new(-sin_lon, cos_lon, 0.0);
+    let north = DVec3::new(-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat);
+    let up = DVec3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat);
+
+    let range = offset.length();
+    let (e, n, u) = (offset.dot(east), offset.dot(north), offset.dot(up));
+    LookAngles {
+        azimuth: e.atan2(n).to_degrees().rem_euclid(360.0),
+        elevation: (u / range).clamp(-1.0, 1.0).asin().to_degrees(),
+        range,
+    }
+}
@@ -0,0 +1,134 @@
+use bevy::math::DVec3;
+use crate::resources::Constants;
+use crate::utils::units::Km3PerSec2;
+
+/// Which terms of the Earth's geopotential to include
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GravityModel {
+    /// Central (point-mass) gravity only
+    PointMass,
+    /// Central gravity plus J2 oblateness
+    J2,
+    /// Central gravity plus J2 and J3
+    J3,
+    /// Central gravity plus J2, J3 and J4
+    J4,
+}
+
+/// Earth gravity field evaluated with zonal harmonics up to the selected degree
+#[derive(Clone, Copy, Debug)]
+pub struct Geopotential {
+    /// Gravitational parameter in km³/s²
+    pub mu: f64,
+    /// Reference (equatorial) radius of the harmonic expansion in km
+    pub reference_radius: f64,
+    pub j2: f64,
+    pub j3: f64,
+    pub j4: f64,
+    pub model: GravityModel,
+}
+
+impl Geopotential {
+    /// Build the geopotential from the simulation constants
+    pub fn new(constants: &Constants, model: GravityModel) -> Self {
+        Self {
+            mu: constants.gravitational_parameter.0,
+            reference_radius: constants.earth_equatorial_radius.0,
+            j2: constants.j2,
+            j3: constants.j3,
+            j4: constants.j4,
+            model,
+        }
+    }
+
+    /// Point-mass gravity of a body with gravitational parameter `mu`
+    pub fn point_mass(mu: Km3PerSec2) -> Self {
+        Self {
+            mu: mu.0,
+            reference_radius: 0.0,
+            j2: 0.0,
+            j3: 0.0,
+            j4: 0.0,
+            model: GravityModel::PointMass,
+        }
+    }
+
+    /// Gravitational acceleration in km/s² at a position in km (inertial frame, Z along the spin axis)
+    pub fn acceleration_f64(&self, position: DVec3) -> DVec3 {
+        let r2 = position.length_squared();
+        if r2 <= 0.0 {
+            return DVec3::ZERO;
+        }
+        let r = r2.sqrt();
+        let (x, y, z) = (position.x, position.y, position.z);
+
+        let mut acc = -self.mu / (r2 * r) * position;
+
+        if self.model == GravityModel::PointMass {
+            return acc;
+        }
+
+        let re = self.reference_radius;
+        let z2_r2 = z * z / r2;
+        let r7 = r2 * r2 * r2 * r;
+
+        // J2: -(3/2) J2 mu Re² / r⁵ * [x(1 - 5z²/r²), y(1 - 5z²/r²), z(3 - 5z²/r²)]
+        let k2 = -1.5 * self.j2 * self.mu * re * re / (r2 * r2 * r);
+        acc += DVec3::new(
+            k2 * x * (1.0 - 5.0 * z2_r2),
+            k2 * y * (1.0 - 5.0 * z2_r2),
+            k2 * z * (3.0 - 5.0 * z2_r2),
+        );
+
+        if self.model == GravityModel::J2 {
+            return acc;
+        }
+
+        // J3: -(5/2) J3 mu Re³ / r⁷ * [x(3z - 7z³/r²), y(3z - 7z³/r²), 6z² - 7z⁴/r² - (3/5)r²]
+        let k3 = -2.5 * self.j3 * self.mu * re * re * re / r7;
+        let xy3 = 3.0 * z - 7.0 * z * z2_r2;
+        acc += DVec3::new(
+            k3 * x * xy3,
+            k3 * y * xy3,
+            k3 * (6.0 * z * z - 7.0 * z * z * z2_r2 - 0.6 * r2),
+        );
+
+        if self.model == GravityModel::J3 {
+            return acc;
+        }
+
+        // J4: (15/8) J4 mu Re⁴ / r⁷ * [x(1 - 14z²/r² + 21z⁴/r⁴), y(...), z(5 - 70z²/(3r²) + 21z⁴/r⁴)]
+        let k4 = 1.875 * self.j4 * self.mu * re * re * re * re / r7;
+        let xy4 = 1.0 - 14.0 * z2_r2 + 21.0 * z2_r2 * z2_r2;
+        acc += DVec3::new(
+            k4 * x * xy4,
+            k4 * y * xy4,
+            k4 * z * (5.0 - 70.0 / 3.0 * z2_r2 + 21.0 * z2_r2 * z2_r2),
+        );
+
+        acc
+    }
+
+    /// Gravitational potential in km²/s² (acceleration is its gradient)
+    pub fn potential(&self, position: DVec3) -> f64 {
+        let r = position.length();
+        if r <= 0.0 {
+            return 0.0;
+        }
+        let s = position.z / r; // sine of geocentric latitude
+        let ratio = self.reference_radius / r;
+
+        let mut sum = 0.0;
+        if self.model != GravityModel::PointMass {
+            sum += self.j2 * ratio.powi(2) * 0.5 * (3.0 * s * s - 1.0);
+        }
+        if matches!(self.model, GravityModel::J3 | GravityModel::J4) {
+            sum += self.j3 * ratio.powi(3) * 0.5 * (5.0 * s.powi(3) - 3.0 * s);
+        }
+        if self.model == GravityModel::J4 {
+            sum += self.j4 * ratio.powi(4) * 0.125 * (35.0 * s.powi(4) - 30.0 * s * s + 3.0);
+        }
+
+        self.mu / r * (1.0 - sum)
+    }
+}
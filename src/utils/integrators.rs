@@ -1,50 +1,66 @@
+use bevy::math::DVec3;
 use bevy::prelude::*;
+use crate::resources::Constants;
+use crate::utils::analytical::AnalyticalPropagator;
+use crate::utils::atmosphere::*;
+use crate::utils::forces::*;
+use crate::utils::gravity::*;
+use crate::utils::time::Epoch;
+
+/// A fixed-step scheme that advances one object's position and velocity under a force model
+pub trait Integrator: Send + Sync {
+    /// Short name used in logs and the UI
+    fn name(&self) -> &'static str;
+
+    /// Advance position (km) and velocity (km/s) by `dt` seconds
+    /// Returns new position and velocity
+    fn integrate(
+        &self,
+        position: DVec3,
+        velocity: DVec3,
+        dt: f64,
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+    ) -> (DVec3, DVec3);
+}
 
 /// Runge-Kutta 4th order integrator for more accurate physics
 pub struct RK4Integrator;
 
-impl RK4Integrator {
-    /// Integrate orbital state using RK4 method
-    /// Returns new position and velocity
-    pub fn integrate(
-        position: Vec3,
-        velocity: Vec3,
+impl Integrator for RK4Integrator {
+    fn name(&self) -> &'static str {
+        "RK4"
+    }
+
+    fn integrate(
+        &self,
+        position: DVec3,
+        velocity: DVec3,
         dt: f64,
-        gm: f64,
-    ) -> (Vec3, Vec3) {
-        // Helper function to compute acceleration
-        let accel = |pos: Vec3| -> Vec3 {
-            let r_mag_km = pos.length() as f64;
-            if r_mag_km <= 0.0 {
-                return Vec3::ZERO;
-            }
-            
-            let r_mag_m = r_mag_km * 1000.0;
-            let acc_magnitude = -gm / (r_mag_m * r_mag_m);
-            let r_unit = pos / r_mag_km as f32;
-            let acc_km_s2 = (acc_magnitude / 1000.0) as f32;
-            r_unit * acc_km_s2
-        };
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+    ) -> (DVec3, DVec3) {
+        let accel = |pos: DVec3, vel: DVec3| -> DVec3 { forces.acceleration_f64(pos, vel, coefficients) };
 
         // k1: derivative at current state
-        let k1_v = accel(position);
+        let k1_v = accel(position, velocity);
         let k1_p = velocity;
 
         // k2: derivative at midpoint using k1
-        let k2_v = accel(position + k1_p * (dt as f32 * 0.5));
-        let k2_p = velocity + k1_v * (dt as f32 * 0.5);
+        let k2_p = velocity + k1_v * (dt * 0.5);
+        let k2_v = accel(position + k1_p * (dt * 0.5), k2_p);
 
         // k3: derivative at midpoint using k2
-        let k3_v = accel(position + k2_p * (dt as f32 * 0.5));
-        let k3_p = velocity + k2_v * (dt as f32 * 0.5);
+        let k3_p = velocity + k2_v * (dt * 0.5);
+        let k3_v = accel(position + k2_p * (dt * 0.5), k3_p);
 
         // k4: derivative at endpoint using k3
-        let k4_v = accel(position + k3_p * dt as f32);
-        let k4_p = velocity + k3_v * dt as f32;
+        let k4_p = velocity + k3_v * dt;
+        let k4_v = accel(position + k3_p * dt, k4_p);
 
         // Combine weighted average
-        let new_velocity = velocity + (k1_v + 2.0 * k2_v + 2.0 * k3_v + k4_v) * (dt as f32 / 6.0);
-        let new_position = position + (k1_p + 2.0 * k2_p + 2.0 * k3_p + k4_p) * (dt as f32 / 6.0);
+        let new_velocity = velocity + (k1_v + 2.0 * k2_v + 2.0 * k3_v + k4_v) * (dt / 6.0);
+        let new_position = position + (k1_p + 2.0 * k2_p + 2.0 * k3_p + k4_p) * (dt / 6.0);
 
         (new_position, new_velocity)
     }
@@ -53,48 +69,482 @@ impl RK4Integrator {
 /// Euler integrator (original, less accurate but faster)
 pub struct EulerIntegrator;
 
-impl EulerIntegrator {
-    pub fn integrate(
-        position: Vec3,
-        velocity: Vec3,
+impl Integrator for EulerIntegrator {
+    fn name(&self) -> &'static str {
+        "Euler"
+    }
+
+    fn integrate(
+        &self,
+        position: DVec3,
+        velocity: DVec3,
         dt: f64,
-        gm: f64,
-    ) -> (Vec3, Vec3) {
-        let r_mag_km = position.length() as f64;
-        if r_mag_km <= 0.0 {
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+    ) -> (DVec3, DVec3) {
+        if position.length() <= 0.0 {
             return (position, velocity);
         }
 
-        let r_mag_m = r_mag_km * 1000.0;
-        let acc_magnitude = -gm / (r_mag_m * r_mag_m);
-        let r_unit = position / r_mag_km as f32;
-        let acc_km_s2 = (acc_magnitude / 1000.0) as f32;
-        let acc = r_unit * acc_km_s2;
+        let acc = forces.acceleration_f64(position, velocity, coefficients);
 
-        let new_velocity = velocity + acc * dt as f32;
-        let new_position = position + new_velocity * dt as f32;
+        let new_velocity = velocity + acc * dt;
+        let new_position = position + new_velocity * dt;
 
         (new_position, new_velocity)
     }
 }
 
+/// Velocity Verlet (kick-drift-kick leapfrog), second order and symplectic
+///
+/// Energy error stays bounded instead of drifting, so orbits neither spiral in nor out over
+/// multi-year runs. Drag is evaluated with the half-kicked velocity.
+pub struct VelocityVerletIntegrator;
+
+impl Integrator for VelocityVerletIntegrator {
+    fn name(&self) -> &'static str {
+        "Velocity Verlet"
+    }
+
+    fn integrate(
+        &self,
+        position: DVec3,
+        velocity: DVec3,
+        dt: f64,
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+    ) -> (DVec3, DVec3) {
+        if position.length() <= 0.0 {
+            return (position, velocity);
+        }
+
+        let half_velocity = velocity + forces.acceleration_f64(position, velocity, coefficients) * (dt * 0.5);
+        let new_position = position + half_velocity * dt;
+        let new_velocity =
+            half_velocity + forces.acceleration_f64(new_position, half_velocity, coefficients) * (dt * 0.5);
+
+        (new_position, new_velocity)
+    }
+}
+
+/// Yoshida's 4th order symplectic integrator: three leapfrog stages with tuned weights
+pub struct Yoshida4Integrator;
+
+impl Yoshida4Integrator {
+    /// Drift coefficients c1..c4
+    const DRIFT: [f64; 4] = [
+        0.675_603_595_979_828_8,
+        -0.175_603_595_979_828_8,
+        -0.175_603_595_979_828_8,
+        0.675_603_595_979_828_8,
+    ];
+    /// Kick coefficients d1..d3
+    const KICK: [f64; 3] = [1.351_207_191_959_657_6, -1.702_414_383_919_315_3, 1.351_207_191_959_657_6];
+}
+
+impl Integrator for Yoshida4Integrator {
+    fn name(&self) -> &'static str {
+        "Yoshida 4"
+    }
+
+    fn integrate(
+        &self,
+        position: DVec3,
+        velocity: DVec3,
+        dt: f64,
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+    ) -> (DVec3, DVec3) {
+        if position.length() <= 0.0 {
+            return (position, velocity);
+        }
+
+        let mut position = position;
+        let mut velocity = velocity;
+        for (drift, kick) in Self::DRIFT.iter().zip(Self::KICK.iter()) {
+            position += velocity * (drift * dt);
+            velocity += forces.acceleration_f64(position, velocity, coefficients) * (kick * dt);
+        }
+        position += velocity * (Self::DRIFT[3] * dt);
+
+        (position, velocity)
+    }
+}
+
+/// Built-in fixed-step integrators selectable through [`IntegratorConfig`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum IntegratorKind {
+    #[default]
+    Euler,
+    RK4,
+    VelocityVerlet,
+    Yoshida4,
+}
+
+impl IntegratorKind {
+    /// Every registered integrator, in order of increasing cost
+    pub const ALL: [IntegratorKind; 4] = [
+        IntegratorKind::Euler,
+        IntegratorKind::VelocityVerlet,
+        IntegratorKind::RK4,
+        IntegratorKind::Yoshida4,
+    ];
+
+    /// The integrator implementation for this kind
+    pub fn integrator(self) -> &'static dyn Integrator {
+        match self {
+            IntegratorKind::Euler => &EulerIntegrator,
+            IntegratorKind::RK4 => &RK4Integrator,
+            IntegratorKind::VelocityVerlet => &VelocityVerletIntegrator,
+            IntegratorKind::Yoshida4 => &Yoshida4Integrator,
+        }
+    }
+
+    /// Whether the scheme is symplectic and keeps orbital energy bounded
+    pub fn is_symplectic(self) -> bool {
+        matches!(self, IntegratorKind::VelocityVerlet | IntegratorKind::Yoshida4)
+    }
+}
+
+/// Step-size bounds and error tolerance for adaptive integration
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepControl {
+    /// Smallest step in seconds; steps at this size are accepted even above tolerance
+    pub min_step: f64,
+    /// Largest step in seconds
+    pub max_step: f64,
+    /// Local error tolerance per step, relative to the position and velocity magnitudes
+    pub tolerance: f64,
+}
+
+impl StepControl {
+    /// Clamp a proposed step size to the configured bounds
+    pub fn clamp(&self, step: f64) -> f64 {
+        step.clamp(self.min_step, self.max_step.max(self.min_step))
+    }
+}
+
+/// Result of integrating one object across an interval with adaptive steps
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveResult {
+    pub position: DVec3,
+    pub velocity: DVec3,
+    /// Step size proposed for the object's next interval in seconds
+    pub next_step: f64,
+    /// Number of accepted steps
+    pub steps: u32,
+    /// Number of steps rejected by the error test
+    pub rejected: u32,
+}
+
+/// Dormand-Prince 5(4) embedded Runge-Kutta integrator with local error control
+///
+/// Advances with the fifth-order solution and uses the embedded fourth-order one to estimate
+/// the error, so the step shrinks near perigee of eccentric or low orbits and grows at GEO.
+pub struct DormandPrinceIntegrator;
+
+impl DormandPrinceIntegrator {
+    const SAFETY: f64 = 0.9;
+    const MIN_SCALE: f64 = 0.2;
+    const MAX_SCALE: f64 = 5.0;
+
+    /// Take a single step of `dt` seconds
+    /// Returns the fifth-order position and velocity and the error norm relative to `tolerance`,
+    /// which is at most 1.0 when the step meets the tolerance
+    pub fn step(
+        position: DVec3,
+        velocity: DVec3,
+        dt: f64,
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+        tolerance: f64,
+    ) -> (DVec3, DVec3, f64) {
+        let accel = |pos: DVec3, vel: DVec3| -> DVec3 { forces.acceleration_f64(pos, vel, coefficients) };
+
+        // Stage derivatives: position changes with velocity, velocity with acceleration
+        let k1_p = velocity;
+        let k1_v = accel(position, velocity);
+
+        let k2_p = velocity + dt * (k1_v / 5.0);
+        let k2_v = accel(position + dt * (k1_p / 5.0), k2_p);
+
+        let k3_p = velocity + dt * (3.0 / 40.0 * k1_v + 9.0 / 40.0 * k2_v);
+        let k3_v = accel(position + dt * (3.0 / 40.0 * k1_p + 9.0 / 40.0 * k2_p), k3_p);
+
+        let k4_p = velocity + dt * (44.0 / 45.0 * k1_v - 56.0 / 15.0 * k2_v + 32.0 / 9.0 * k3_v);
+        let k4_v = accel(
+            position + dt * (44.0 / 45.0 * k1_p - 56.0 / 15.0 * k2_p + 32.0 / 9.0 * k3_p),
+            k4_p,
+        );
+
+        let k5_p = velocity
+            + dt * (19372.0 / 6561.0 * k1_v - 25360.0 / 2187.0 * k2_v + 64448.0 / 6561.0 * k3_v
+                - 212.0 / 729.0 * k4_v);
+        let k5_v = accel(
+            position
+                + dt * (19372.0 / 6561.0 * k1_p - 25360.0 / 2187.0 * k2_p + 64448.0 / 6561.0 * k3_p
+                    - 212.0 / 729.0 * k4_p),
+            k5_p,
+        );
+
+        let k6_p = velocity
+            + dt * (9017.0 / 3168.0 * k1_v - 355.0 / 33.0 * k2_v + 46732.0 / 5247.0 * k3_v
+                + 49.0 / 176.0 * k4_v
+                - 5103.0 / 18656.0 * k5_v);
+        let k6_v = accel(
+            position
+                + dt * (9017.0 / 3168.0 * k1_p - 355.0 / 33.0 * k2_p + 46732.0 / 5247.0 * k3_p
+                    + 49.0 / 176.0 * k4_p
+                    - 5103.0 / 18656.0 * k5_p),
+            k6_p,
+        );
+
+        // Fifth-order solution
+        let new_position = position
+            + dt * (35.0 / 384.0 * k1_p + 500.0 / 1113.0 * k3_p + 125.0 / 192.0 * k4_p
+                - 2187.0 / 6784.0 * k5_p
+                + 11.0 / 84.0 * k6_p);
+        let new_velocity = velocity
+            + dt * (35.0 / 384.0 * k1_v + 500.0 / 1113.0 * k3_v + 125.0 / 192.0 * k4_v
+                - 2187.0 / 6784.0 * k5_v
+                + 11.0 / 84.0 * k6_v);
+
+        // First-same-as-last stage, needed only by the embedded fourth-order solution
+        let k7_p = new_velocity;
+        let k7_v = accel(new_position, new_velocity);
+
+        // Difference between the fifth- and fourth-order solutions
+        let error_p = dt
+            * (71.0 / 57600.0 * k1_p - 71.0 / 16695.0 * k3_p + 71.0 / 1920.0 * k4_p
+                - 17253.0 / 339200.0 * k5_p
+                + 22.0 / 525.0 * k6_p
+                - k7_p / 40.0);
+        let error_v = dt
+            * (71.0 / 57600.0 * k1_v - 71.0 / 16695.0 * k3_v + 71.0 / 1920.0 * k4_v
+                - 17253.0 / 339200.0 * k5_v
+                + 22.0 / 525.0 * k6_v
+                - k7_v / 40.0);
+
+        let position_scale = tolerance * position.length().max(new_position.length()).max(1.0);
+        let velocity_scale = tolerance * velocity.length().max(new_velocity.length()).max(1.0e-3);
+        let error = (error_p.length() / position_scale).max(error_v.length() / velocity_scale);
+
+        (new_position, new_velocity, error)
+    }
+
+    /// Integrate across `dt` seconds with error-controlled steps, starting from `initial_step`
+    /// Steps stay within the bounds of `control`, the last one shortened to land exactly on `dt`
+    pub fn integrate(
+        position: DVec3,
+        velocity: DVec3,
+        dt: f64,
+        forces: &ForceModel,
+        coefficients: ForceCoefficients,
+        control: &StepControl,
+        initial_step: f64,
+    ) -> AdaptiveResult {
+        let mut result = AdaptiveResult {
+            position,
+            velocity,
+            next_step: control.clamp(initial_step),
+            steps: 0,
+            rejected: 0,
+        };
+        if position.length() <= 0.0 {
+            return result;
+        }
+
+        let mut elapsed = 0.0;
+        while elapsed < dt {
+            let remaining = dt - elapsed;
+            let step = result.next_step.min(remaining);
+
+            let (new_position, new_velocity, error) = Self::step(
+                result.position,
+                result.velocity,
+                step,
+                forces,
+                coefficients,
+                control.tolerance,
+            );
+
+            let scale = if error.is_nan() {
+                Self::MIN_SCALE
+            } else if error > 0.0 {
+                (Self::SAFETY * error.powf(-0.2)).clamp(Self::MIN_SCALE, Self::MAX_SCALE)
+            } else {
+                Self::MAX_SCALE
+            };
+
+            if error <= 1.0 || step <= control.min_step {
+                result.position = new_position;
+                result.velocity = new_velocity;
+                result.steps += 1;
+                elapsed += step;
+                // A step shortened to hit the interval end says nothing about the next one
+                if step < result.next_step && error <= 1.0 {
+                    continue;
+                }
+            } else {
+                result.rejected += 1;
+            }
+            result.next_step = control.clamp(step * scale);
+        }
+
+        result
+    }
+}
+
+/// Where the physics stage integrates objects
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PhysicsBackend {
+    /// Integrate each entity's components in place
+    Ecs,
+    /// Pack state into contiguous arrays and integrate them in parallel with rayon
+    #[default]
+    Packed,
+    /// Jump each object's mean elements analytically with Kepler's equation, secular J2 and
+    /// drag decay; cost does not depend on the step size, so the sub-step budget is lifted.
+    /// Lunisolar gravity and radiation pressure are not modelled here.
+    Analytical,
+}
+
+/// Everything needed to advance one object through a frame's sub-steps
+///
+/// Both physics backends integrate through this, so they produce identical results.
+#[derive(Clone, Copy)]
+pub struct FrameIntegration {
+    pub integrator: &'static dyn Integrator,
+    pub forces: ForceModel,
+    /// Adaptive step control, `None` for fixed steps
+    pub step_control: Option<StepControl>,
+    /// Fixed sub-step in seconds
+    pub dt: f64,
+    /// Number of fixed sub-steps this frame
+    pub steps: u32,
+}
+
+impl FrameIntegration {
+    /// The same settings over a different number of sub-steps
+    pub fn with_steps(&self, steps: u32) -> Self {
+        Self { steps, ..*self }
+    }
+
+    /// Advance position and velocity through the frame
+    /// `adaptive_step` is the object's previous step proposal; under adaptive stepping the
+    /// new proposal is returned alongside the state
+    pub fn integrate(
+        &self,
+        position: DVec3,
+        velocity: DVec3,
+        coefficients: ForceCoefficients,
+        adaptive_step: Option<f64>,
+    ) -> (DVec3, DVec3, Option<f64>) {
+        match &self.step_control {
+            Some(control) => {
+                // The adaptive integrator picks its own steps across the whole frame
+                let result = DormandPrinceIntegrator::integrate(
+                    position,
+                    velocity,
+                    self.dt * self.steps as f64,
+                    &self.forces,
+                    coefficients,
+                    control,
+                    adaptive_step.unwrap_or(control.max_step),
+                );
+                (result.position, result.velocity, Some(result.next_step))
+            }
+            None => {
+                let mut state = (position, velocity);
+                for _ in 0..self.steps {
+                    state = self.integrator.integrate(state.0, state.1, self.dt, &self.forces, coefficients);
+                }
+                (state.0, state.1, None)
+            }
+        }
+    }
+}
+
 /// Resource to configure which integrator to use
 #[derive(Resource)]
 pub struct IntegratorConfig {
-    pub use_rk4: bool,
+    /// Fixed-step scheme used when `adaptive_timestep` is off
+    pub integrator: IntegratorKind,
+    /// Geopotential terms applied by every physics path
+    pub gravity_model: GravityModel,
+    /// Density model for atmospheric drag, `None` disables drag
+    pub atmosphere_model: Option<AtmosphereModel>,
+    /// Include Sun and Moon gravity
+    pub third_body_gravity: bool,
+    /// Reflectivity coefficient Cr for solar radiation pressure, `None` disables it
+    pub solar_radiation_pressure: Option<f64>,
+    /// Use the Dormand-Prince integrator with per-object step control
     pub adaptive_timestep: bool,
+    /// Smallest adaptive step in seconds
     pub min_timestep: f64,
+    /// Largest adaptive step in seconds
     pub max_timestep: f64,
+    /// Relative local error tolerance for adaptive steps
+    pub tolerance: f64,
+    /// Where the physics stage runs
+    pub backend: PhysicsBackend,
 }
 
 impl Default for IntegratorConfig {
     fn default() -> Self {
         Self {
-            use_rk4: false, // Default to Euler for compatibility
+            integrator: IntegratorKind::Euler, // Default to Euler for compatibility
+            gravity_model: GravityModel::J2,
+            atmosphere_model: Some(AtmosphereModel::Exponential),
+            third_body_gravity: false,
+            solar_radiation_pressure: None,
             adaptive_timestep: false,
             min_timestep: 0.01,
             max_timestep: 1.0,
+            tolerance: 1.0e-10,
+            backend: PhysicsBackend::Packed,
         }
     }
 }
 
+impl IntegratorConfig {
+    /// Force model applied by every physics path under this configuration
+    /// The Sun and Moon, if modelled, are placed at `epoch`.
+    pub fn force_model(&self, constants: &Constants, epoch: &Epoch) -> ForceModel {
+        let mut forces = ForceModel::new(constants, self.gravity_model, self.atmosphere_model);
+        if self.third_body_gravity {
+            forces = forces.with_third_body(constants, epoch);
+        }
+        if let Some(reflectivity) = self.solar_radiation_pressure {
+            forces = forces.with_radiation_pressure(constants, epoch, reflectivity);
+        }
+        forces
+    }
+
+    /// Closed-form propagator for the analytical backend, using the same gravity and drag settings
+    pub fn analytical(&self, constants: &Constants) -> AnalyticalPropagator {
+        AnalyticalPropagator::new(constants, self.gravity_model, self.atmosphere_model)
+    }
+
+    /// Step bounds and tolerance for the adaptive integrator
+    pub fn step_control(&self) -> StepControl {
+        StepControl {
+            min_step: self.min_timestep,
+            max_step: self.max_timestep,
+            tolerance: self.tolerance,
+        }
+    }
+
+    /// Integration settings for a frame of `steps` sub-steps of `dt` seconds starting at `start`
+    /// The Sun and Moon are held at their frame-midpoint positions.
+    pub fn frame(&self, constants: &Constants, start: &Epoch, dt: f64, steps: u32) -> FrameIntegration {
+        let midpoint = start.plus_seconds(0.5 * dt * steps as f64);
+        FrameIntegration {
+            integrator: self.integrator.integrator(),
+            forces: self.force_model(constants, &midpoint),
+            step_control: self.adaptive_timestep.then(|| self.step_control()),
+            dt,
+            steps,
+        }
+    }
+}
@@ -1,7 +1,31 @@
 pub mod tle_parser;
 pub mod sgp4_wrapper;
 pub mod integrators;
+pub mod gravity;
+pub mod atmosphere;
+pub mod forces;
+pub mod ephemeris;
+pub mod radiation_pressure;
+pub mod frames;
+pub mod elements;
+pub mod analytical;
+pub mod conjunction;
+pub mod time;
+pub mod breakup;
+pub mod units;
 
 pub use tle_parser::*;
 pub use sgp4_wrapper::*;
-pub use integrators::*;
\ No newline at end of file
+pub use integrators::*;
+pub use gravity::*;
+pub use atmosphere::*;
+pub use forces::*;
+pub use ephemeris::*;
+pub use radiation_pressure::*;
+pub use frames::*;
+pub use elements::*;
+pub use analytical::*;
+pub use conjunction::*;
+pub use time::*;
+pub use breakup::*;
+pub use units::*;
@@ -0,0 +1,58 @@
+// Cannonball solar radiation pressure with a cylindrical Earth shadow
+// Significant for high area-to-mass debris and dominant over drag above roughly 800 km
+
+use bevy::math::DVec3;
+use crate::resources::Constants;
+use crate::utils::ephemeris::sun_position;
+use crate::utils::time::Epoch;
+use crate::utils::units::*;
+
+/// Whether `position` (km) lies in the Earth's shadow, modelled as a cylinder of radius
+/// `earth_radius` extending away from the Sun
+pub fn in_earth_shadow(position: DVec3, sun: DVec3, earth_radius: Km) -> bool {
+    let sun_direction = sun.normalize();
+    let along = position.dot(sun_direction);
+    along < 0.0 && Km::length(position - along * sun_direction) < earth_radius
+}
+
+/// Solar radiation pressure on a sphere, with the Sun held at a fixed position
+#[derive(Clone, Copy, Debug)]
+pub struct SolarRadiationPressure {
+    /// Geocentric Sun position in km
+    pub sun: DVec3,
+    /// Radiation pressure at 1 AU in N/m²
+    pub pressure: f64,
+    pub astronomical_unit: Km,
+    /// Reflectivity coefficient Cr: 1 for a perfect absorber, 2 for a perfect mirror
+    pub reflectivity: f64,
+    /// Radius of the shadow cylinder
+    pub shadow_radius: Km,
+}
+
+impl SolarRadiationPressure {
+    /// Radiation pressure from the Sun at the given epoch
+    pub fn at(constants: &Constants, epoch: &Epoch, reflectivity: f64) -> Self {
+        Self {
+            sun: sun_position(epoch),
+            pressure: constants.solar_radiation_pressure,
+            astronomical_unit: constants.astronomical_unit,
+            reflectivity,
+            shadow_radius: constants.earth_equatorial_radius,
+        }
+    }
+
+    /// Acceleration in km/s² pushing an object at `position` (km) away from the Sun
+    ///
+    /// `area_to_mass` is the object's cross section over its mass in m²/kg; zero disables SRP.
+    pub fn acceleration_f64(&self, position: DVec3, area_to_mass: f64) -> DVec3 {
+        if area_to_mass <= 0.0 || in_earth_shadow(position, self.sun, self.shadow_radius) {
+            return DVec3::ZERO;
+        }
+
+        let from_sun = position - self.sun;
+        let distance = from_sun.length();
+        let scale = (self.astronomical_unit / Km(distance)).powi(2);
+        // P [N/m²] · Cr · A/m [m²/kg] gives m/s²
+        self.pressure * self.reflectivity * area_to_mass * scale / METERS_PER_KM * (from_sun / distance)
+    }
+}
@@ -1,109 +1,143 @@
-// SGP4 wrapper - Simplified implementation for TLE to state vector conversion
-// For Phase 2, we'll implement a working version using basic orbital mechanics
-// This can be enhanced with full SGP4 later
+// SGP4 wrapper - TLE to state vector conversion backed by the `sgp4` crate
+// Uses the full SGP4/SDP4 model (drag, secular/periodic perturbations, deep-space terms)
+// with the WGS72 geopotential and AFSPC conventions that TLEs are generated with
 
+use crate::utils::time::Epoch;
 use crate::utils::TleRecord;
-use bevy::prelude::Vec3;
+use bevy::math::DVec3;
 use std::f64::consts::PI;
 
-/// Convert TLE data to initial position/velocity state vectors
-/// Uses simplified orbital mechanics (can be upgraded to full SGP4 later)
-pub fn tle_to_state_vectors(tle: &TleRecord) -> Result<(Vec3, Vec3), String> {
-    // Convert orbital elements to Cartesian coordinates
-    // This is a simplified implementation - full SGP4 would be more accurate
-
-    // Convert angles from degrees to radians
-    let inclination = tle.inclination.to_radians();
-    let raan = tle.right_ascension.to_radians(); // Right Ascension of Ascending Node
-    let arg_perigee = tle.argument_of_perigee.to_radians();
-    let mean_anomaly = tle.mean_anomaly.to_radians();
-    let eccentricity = tle.eccentricity;
-
-    // Earth's gravitational parameter (km³/s²)
-    let mu = 398600.4418;
-
-    // Calculate semi-major axis from mean motion
-    // n = sqrt(mu/a³) => a = (mu/n²)^(1/3)
-    let mean_motion_rad_per_sec = tle.mean_motion * 2.0 * PI / 86400.0; // Convert rev/day to rad/s
-    let semi_major_axis =
-        (mu / (mean_motion_rad_per_sec * mean_motion_rad_per_sec)).powf(1.0 / 3.0);
-
-    // Solve Kepler's equation: E - e*sin(E) = M
-    // Using Newton's method for eccentric anomaly
-    let eccentric_anomaly = solve_keplers_equation(mean_anomaly, eccentricity)?;
-
-    // Calculate true anomaly
-    let true_anomaly = 2.0
-        * ((1.0 + eccentricity) / (1.0 - eccentricity)).sqrt().atan()
-        * (eccentric_anomaly / 2.0).tan();
-
-    // Calculate distance from Earth center
-    let radius = semi_major_axis * (1.0 - eccentricity * eccentric_anomaly.cos());
-
-    // Position in orbital plane
-    let cos_true_anom = true_anomaly.cos();
-    let sin_true_anom = true_anomaly.sin();
-
-    let pos_orbital = Vec3::new(
-        (radius * cos_true_anom) as f32,
-        (radius * sin_true_anom) as f32,
-        0.0,
-    );
-
-    // Velocity in orbital plane (km/s)
-    let h = (mu * semi_major_axis * (1.0 - eccentricity * eccentricity)).sqrt(); // Angular momentum
-    let vel_orbital = Vec3::new(
-        (-mu / h * sin_true_anom) as f32,
-        (mu / h * (eccentricity + cos_true_anom)) as f32,
-        0.0,
-    );
-
-    // Rotation matrices to convert from orbital plane to ECI coordinates
-    let cos_raan = raan.cos();
-    let sin_raan = raan.sin();
-    let cos_inc = inclination.cos();
-    let sin_inc = inclination.sin();
-    let cos_arg = arg_perigee.cos();
-    let sin_arg = arg_perigee.sin();
-
-    // Combined rotation matrix elements
-    let p11 = cos_raan * cos_arg - sin_raan * sin_arg * cos_inc;
-    let p12 = -cos_raan * sin_arg - sin_raan * cos_arg * cos_inc;
-    let p13 = sin_raan * sin_inc;
-
-    let p21 = sin_raan * cos_arg + cos_raan * sin_arg * cos_inc;
-    let p22 = -sin_raan * sin_arg + cos_raan * cos_arg * cos_inc;
-    let p23 = -cos_raan * sin_inc;
-
-    let p31 = sin_arg * sin_inc;
-    let p32 = cos_arg * sin_inc;
-    let p33 = cos_inc;
-
-    // Transform position to ECI coordinates
-    let position = Vec3::new(
-        (p11 * pos_orbital.x as f64 + p12 * pos_orbital.y as f64 + p13 * pos_orbital.z as f64)
-            as f32,
-        (p21 * pos_orbital.x as f64 + p22 * pos_orbital.y as f64 + p23 * pos_orbital.z as f64)
-            as f32,
-        (p31 * pos_orbital.x as f64 + p32 * pos_orbital.y as f64 + p33 * pos_orbital.z as f64)
-            as f32,
-    );
-
-    // Transform velocity to ECI coordinates
-    let velocity = Vec3::new(
-        (p11 * vel_orbital.x as f64 + p12 * vel_orbital.y as f64 + p13 * vel_orbital.z as f64)
-            as f32,
-        (p21 * vel_orbital.x as f64 + p22 * vel_orbital.y as f64 + p23 * vel_orbital.z as f64)
-            as f32,
-        (p31 * vel_orbital.x as f64 + p32 * vel_orbital.y as f64 + p33 * vel_orbital.z as f64)
-            as f32,
-    );
-
-    Ok((position, velocity))
+/// Julian date of the J2000 epoch (2000-01-01 12:00 UTC)
+pub const J2000_JULIAN_DAY: f64 = 2451545.0;
+
+/// Reference atmospheric density ρ₀ of the TLE BSTAR drag term in kg/m²/ER
+pub const BSTAR_REFERENCE_DENSITY: f64 = 0.15696615;
+
+/// Errors produced while initializing or propagating SGP4/SDP4
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sgp4Error {
+    /// The TLE elements could not be turned into SGP4 epoch constants
+    InvalidElements(sgp4::ElementsError),
+    /// SGP4 propagation diverged at the requested time
+    Propagation(sgp4::Error),
+    /// The propagated position lies inside the Earth
+    Decayed { minutes_since_epoch: f64 },
+    /// Kepler's equation solver failed to converge
+    KeplerNonConvergence,
+}
+
+impl std::fmt::Display for Sgp4Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Sgp4Error::InvalidElements(e) => write!(f, "Invalid TLE elements: {}", e),
+            Sgp4Error::Propagation(e) => write!(f, "SGP4 propagation failed: {}", e),
+            Sgp4Error::Decayed { minutes_since_epoch } => write!(
+                f,
+                "Object has decayed {:.1} minutes after TLE epoch",
+                minutes_since_epoch
+            ),
+            Sgp4Error::KeplerNonConvergence => {
+                write!(f, "Failed to converge in Kepler's equation solution")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Sgp4Error {}
+
+impl From<sgp4::ElementsError> for Sgp4Error {
+    fn from(error: sgp4::ElementsError) -> Self {
+        Sgp4Error::InvalidElements(error)
+    }
+}
+
+impl From<sgp4::Error> for Sgp4Error {
+    fn from(error: sgp4::Error) -> Self {
+        Sgp4Error::Propagation(error)
+    }
+}
+
+/// SGP4/SDP4 propagator initialized from a single TLE
+///
+/// Output states are in the TEME (True Equator, Mean Equinox) frame of the TLE epoch,
+/// positions in km and velocities in km/s.
+pub struct Sgp4Propagator {
+    constants: sgp4::Constants,
+    /// Julian date (UTC) of the TLE epoch
+    pub epoch_julian_day: f64,
+}
+
+impl Sgp4Propagator {
+    /// Initialize the SGP4 epoch constants from a parsed TLE record
+    pub fn from_tle(tle: &TleRecord) -> Result<Self, Sgp4Error> {
+        let epoch_julian_day = tle_epoch_julian_day(tle);
+
+        // Kozai mean motion in rad/min (TLE mean motion is in rev/day)
+        let kozai_mean_motion = tle.mean_motion * (PI / 720.0);
+
+        let orbit = sgp4::Orbit::from_kozai_elements(
+            &sgp4::WGS72,
+            tle.inclination.to_radians(),
+            tle.right_ascension.to_radians(),
+            tle.eccentricity,
+            tle.argument_of_perigee.to_radians(),
+            tle.mean_anomaly.to_radians(),
+            kozai_mean_motion,
+        )
+        .map_err(sgp4::ElementsError::from)?;
+
+        let constants = sgp4::Constants::new(
+            sgp4::WGS72,
+            sgp4::afspc_epoch_to_sidereal_time,
+            (epoch_julian_day - J2000_JULIAN_DAY) / 365.25,
+            tle.bstar,
+            orbit,
+        )
+        .map_err(sgp4::ElementsError::from)?;
+
+        Ok(Self {
+            constants,
+            epoch_julian_day,
+        })
+    }
+
+    /// Propagate to a time offset from the TLE epoch
+    /// Returns TEME position (km) and velocity (km/s)
+    pub fn propagate_minutes(&self, minutes_since_epoch: f64) -> Result<(DVec3, DVec3), Sgp4Error> {
+        let prediction = self
+            .constants
+            .propagate_afspc_compatibility_mode(sgp4::MinutesSinceEpoch(minutes_since_epoch))?;
+
+        let position = DVec3::from_array(prediction.position);
+        let velocity = DVec3::from_array(prediction.velocity);
+
+        if position.length() < sgp4::WGS72.ae {
+            return Err(Sgp4Error::Decayed { minutes_since_epoch });
+        }
+
+        Ok((position, velocity))
+    }
+
+    /// Propagate to an absolute epoch; the offset from the TLE epoch counts elapsed SI seconds
+    pub fn propagate_to_epoch(&self, epoch: &Epoch) -> Result<(DVec3, DVec3), Sgp4Error> {
+        let tle_epoch = Epoch::from_utc_julian_day(self.epoch_julian_day);
+        self.propagate_minutes(epoch.seconds_since(&tle_epoch) / 60.0)
+    }
+
+    /// Propagate to an absolute Julian date (UTC)
+    pub fn propagate_to_julian_day(&self, julian_day: f64) -> Result<(DVec3, DVec3), Sgp4Error> {
+        self.propagate_minutes((julian_day - self.epoch_julian_day) * 1440.0)
+    }
+}
+
+/// Convert TLE data to position/velocity state vectors at the TLE epoch
+/// Uses full SGP4/SDP4, output is in the TEME frame (km, km/s)
+pub fn tle_to_state_vectors(tle: &TleRecord) -> Result<(DVec3, DVec3), Sgp4Error> {
+    Sgp4Propagator::from_tle(tle)?.propagate_minutes(0.0)
 }
 
 /// Solve Kepler's equation using Newton's method
-fn solve_keplers_equation(mean_anomaly: f64, eccentricity: f64) -> Result<f64, String> {
+pub fn solve_keplers_equation(mean_anomaly: f64, eccentricity: f64) -> Result<f64, Sgp4Error> {
     let mut eccentric_anomaly = mean_anomaly; // Initial guess
     const MAX_ITERATIONS: u32 = 50;
     const TOLERANCE: f64 = 1e-12;
@@ -113,7 +147,7 @@ fn solve_keplers_equation(mean_anomaly: f64, eccentricity: f64) -> Result<f64, S
         let f_prime = 1.0 - eccentricity * eccentric_anomaly.cos();
 
         if f_prime.abs() < TOLERANCE {
-            return Err("Division by zero in Kepler's equation solution".to_string());
+            return Err(Sgp4Error::KeplerNonConvergence);
         }
 
         let delta = f / f_prime;
@@ -124,7 +158,7 @@ fn solve_keplers_equation(mean_anomaly: f64, eccentricity: f64) -> Result<f64, S
         }
     }
 
-    Err("Failed to converge in Kepler's equation solution".to_string())
+    Err(Sgp4Error::KeplerNonConvergence)
 }
 
 /// Get current Julian day number for epoch calculations
@@ -140,54 +174,42 @@ pub fn current_julian_day() -> f64 {
     2440587.5 + unix_time / 86400.0
 }
 
-/// Calculate time difference between TLE epoch and current time in days
-pub fn days_since_epoch(tle: &TleRecord) -> f64 {
-    let current_jd = current_julian_day();
-
-    // Convert TLE epoch to Julian Day
+/// Julian date (UTC) of the TLE epoch
+pub fn tle_epoch_julian_day(tle: &TleRecord) -> f64 {
+    // Two-digit years: 57-99 are 1957-1999, 00-56 are 2000-2056
     let year = if tle.epoch_year < 57 {
         tle.epoch_year + 2000
     } else {
         tle.epoch_year + 1900
-    } as f64;
+    } as i64;
 
-    // Calculate Julian Day for TLE epoch
-    // Approximate calculation - good enough for this phase
-    let tle_jd = 365.25 * (year - 1.0) + tle.epoch_day + 1721425.5;
-
-    current_jd - tle_jd
+    // Julian date of January 0.0 of the epoch year (valid 1901-2099)
+    let jan0 = (367 * year - (7 * year) / 4 + 30 + 1721013) as f64 + 0.5;
+    jan0 + tle.epoch_day
 }
 
-/// Propagate satellite using simplified orbital mechanics
-/// This advances the satellite from its TLE epoch to the current time
-pub fn propagate_to_current_time(tle: &TleRecord) -> Result<(Vec3, Vec3), String> {
-    // Get position/velocity at TLE epoch
-    let (_pos, _vel) = tle_to_state_vectors(tle)?;
-
-    // Calculate time elapsed since TLE epoch
-    let days_elapsed = days_since_epoch(tle);
-    let seconds_elapsed = days_elapsed * 86400.0;
-
-    // For now, use simple Keplerian propagation
-    // This could be enhanced with perturbation models later
-
-    // Calculate mean motion in rad/s
-    let mean_motion_rad_per_sec = tle.mean_motion * 2.0 * PI / 86400.0;
+/// Calculate time difference between TLE epoch and current time in days
+pub fn days_since_epoch(tle: &TleRecord) -> f64 {
+    current_julian_day() - tle_epoch_julian_day(tle)
+}
 
-    // Advance mean anomaly
-    let delta_mean_anomaly = mean_motion_rad_per_sec * seconds_elapsed;
+/// Propagate satellite with SGP4/SDP4 from its TLE epoch to the given epoch
+pub fn propagate_to_epoch(tle: &TleRecord, epoch: &Epoch) -> Result<(DVec3, DVec3), Sgp4Error> {
+    Sgp4Propagator::from_tle(tle)?.propagate_to_epoch(epoch)
+}
 
-    // Create updated TLE with advanced mean anomaly
-    let mut updated_tle = tle.clone();
-    updated_tle.mean_anomaly = (tle.mean_anomaly + delta_mean_anomaly.to_degrees()) % 360.0;
+/// Epoch of a TLE
+pub fn tle_epoch(tle: &TleRecord) -> Epoch {
+    Epoch::from_utc_julian_day(tle_epoch_julian_day(tle))
+}
 
-    // Recalculate position and velocity
-    tle_to_state_vectors(&updated_tle)
+/// Newest epoch in a catalogue, the default simulation start
+pub fn newest_tle_epoch(records: &[TleRecord]) -> Option<Epoch> {
+    records.iter().map(tle_epoch).max_by(|a, b| a.tai_seconds.total_cmp(&b.tai_seconds))
 }
 
-/// Convert TLE data to simplified orbital state (for immediate use)
-/// This provides basic orbital mechanics without full SGP4 complexity
-pub fn tle_to_simple_orbit(tle: &TleRecord) -> Result<(Vec3, Vec3, f64), String> {
+/// Convert TLE data to its epoch state plus orbital period (for immediate use)
+pub fn tle_to_simple_orbit(tle: &TleRecord) -> Result<(DVec3, DVec3, f64), Sgp4Error> {
     let (pos, vel) = tle_to_state_vectors(tle)?;
 
     // Calculate orbital period in seconds
@@ -0,0 +1,249 @@
+// Time scales: UTC calendar dates, Julian dates and a leap-second aware TAI instant
+// Simulation time counts SI seconds, so instants are stored in TAI and converted to UTC for display
+
+use crate::utils::sgp4_wrapper::{current_julian_day, J2000_JULIAN_DAY};
+
+pub const SECONDS_PER_DAY: f64 = 86400.0;
+/// Julian date of the Modified Julian Date origin (1858-11-17 00:00)
+pub const MJD_OFFSET: f64 = 2400000.5;
+/// TT−TAI in seconds
+pub const TT_MINUS_TAI: f64 = 32.184;
+
+/// UTC dates from which TAI−UTC took each value, as (year, month, TAI−UTC seconds)
+/// Leap seconds are inserted at the end of the day before each date.
+const LEAP_SECONDS: [(i64, i64, f64); 28] = [
+    (1972, 1, 10.0),
+    (1972, 7, 11.0),
+    (1973, 1, 12.0),
+    (1974, 1, 13.0),
+    (1975, 1, 14.0),
+    (1976, 1, 15.0),
+    (1977, 1, 16.0),
+    (1978, 1, 17.0),
+    (1979, 1, 18.0),
+    (1980, 1, 19.0),
+    (1981, 7, 20.0),
+    (1982, 7, 21.0),
+    (1983, 7, 22.0),
+    (1985, 7, 23.0),
+    (1988, 1, 24.0),
+    (1990, 1, 25.0),
+    (1991, 1, 26.0),
+    (1992, 7, 27.0),
+    (1993, 7, 28.0),
+    (1994, 7, 29.0),
+    (1996, 1, 30.0),
+    (1997, 7, 31.0),
+    (1999, 1, 32.0),
+    (2006, 1, 33.0),
+    (2009, 1, 34.0),
+    (2012, 7, 35.0),
+    (2015, 7, 36.0),
+    (2017, 1, 37.0),
+];
+
+/// Error returned when a timestamp cannot be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeParseError(pub String);
+
+impl std::fmt::Display for TimeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid ISO-8601 timestamp: {}", self.0)
+    }
+}
+
+impl std::error::Error for TimeParseError {}
+
+/// Modified Julian Day number of a proleptic Gregorian calendar date
+pub fn calendar_to_mjd(year: i64, month: i64, day: i64) -> i64 {
+    // Shift the year to start in March so the leap day falls at its end
+    let (y, m) = if month <= 2 { (year - 1, month + 9) } else { (year, month - 3) };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 678881
+}
+
+/// Proleptic Gregorian (year, month, day) of a Modified Julian Day number
+pub fn mjd_to_calendar(mjd: i64) -> (i64, i64, i64) {
+    let days = mjd + 678881;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let m = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * m + 2) / 5 + 1;
+    let month = if m < 10 { m + 3 } else { m - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// TAI−UTC in seconds on the UTC day with the given MJD
+/// Dates before 1972 use the initial 10 s offset.
+pub fn tai_minus_utc(mjd: i64) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|&&(year, month, _)| mjd >= calendar_to_mjd(year, month, 1))
+        .map_or(LEAP_SECONDS[0].2, |&(_, _, offset)| offset)
+}
+
+/// An instant in time, stored as TAI seconds since JD 2451545.0 TAI
+///
+/// TAI has no leap seconds, so differences between epochs are true elapsed SI seconds
+/// even across a UTC leap second.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Epoch {
+    pub tai_seconds: f64,
+}
+
+impl Epoch {
+    /// J2000.0 reference epoch, 2000-01-01T12:00:00 UTC
+    pub fn j2000() -> Self {
+        Self::from_utc_julian_day(J2000_JULIAN_DAY)
+    }
+
+    /// The wall-clock time now
+    pub fn now() -> Self {
+        Self::from_utc_julian_day(current_julian_day())
+    }
+
+    /// From a UTC Julian date
+    pub fn from_utc_julian_day(julian_day: f64) -> Self {
+        let mjd = julian_day - MJD_OFFSET;
+        let day = mjd.floor();
+        Self::from_utc_day(day as i64, (mjd - day) * SECONDS_PER_DAY)
+    }
+
+    /// From a UTC calendar date and time; `second` may reach 60 during a leap second
+    pub fn from_utc(year: i64, month: i64, day: i64, hour: u32, minute: u32, second: f64) -> Self {
+        let seconds_of_day = hour as f64 * 3600.0 + minute as f64 * 60.0 + second;
+        Self::from_utc_day(calendar_to_mjd(year, month, day), seconds_of_day)
+    }
+
+    fn from_utc_day(mjd: i64, seconds_of_day: f64) -> Self {
+        // The day's own offset applies through its last second, including an inserted leap second
+        let utc_seconds = (mjd as f64 + MJD_OFFSET - J2000_JULIAN_DAY) * SECONDS_PER_DAY + seconds_of_day;
+        Self { tai_seconds: utc_seconds + tai_minus_utc(mjd) }
+    }
+
+    /// UTC day (MJD) and seconds into it, which reach past 86400 inside a leap second
+    pub fn utc_day(&self) -> (i64, f64) {
+        // TAI instant of the midnight starting UTC day `mjd`; day `mjd` runs until the next one
+        let midnight =
+            |mjd: i64| (mjd as f64 + MJD_OFFSET - J2000_JULIAN_DAY) * SECONDS_PER_DAY + tai_minus_utc(mjd);
+
+        let mut mjd = (self.tai_seconds / SECONDS_PER_DAY + J2000_JULIAN_DAY - MJD_OFFSET).floor() as i64;
+        while self.tai_seconds < midnight(mjd) {
+            mjd -= 1;
+        }
+        while self.tai_seconds >= midnight(mjd + 1) {
+            mjd += 1;
+        }
+        (mjd, self.tai_seconds - midnight(mjd))
+    }
+
+    /// UTC Julian date; instants inside a leap second map to the following midnight
+    pub fn utc_julian_day(&self) -> f64 {
+        let (mjd, seconds_of_day) = self.utc_day();
+        mjd as f64 + MJD_OFFSET + seconds_of_day.min(SECONDS_PER_DAY) / SECONDS_PER_DAY
+    }
+
+    /// UTC Modified Julian Date
+    pub fn mjd(&self) -> f64 {
+        self.utc_julian_day() - MJD_OFFSET
+    }
+
+    /// TAI−UTC in effect at this instant
+    pub fn tai_minus_utc(&self) -> f64 {
+        tai_minus_utc(self.utc_day().0)
+    }
+
+    /// Julian centuries of TT since J2000.0, the time argument of precession, nutation and the ephemerides
+    pub fn julian_centuries_tt(&self) -> f64 {
+        (self.tai_seconds + TT_MINUS_TAI) / (SECONDS_PER_DAY * 36525.0)
+    }
+
+    /// The epoch `seconds` SI seconds later
+    pub fn plus_seconds(&self, seconds: f64) -> Self {
+        Self { tai_seconds: self.tai_seconds + seconds }
+    }
+
+    /// Elapsed SI seconds from `earlier` to this epoch
+    pub fn seconds_since(&self, earlier: &Epoch) -> f64 {
+        self.tai_seconds - earlier.tai_seconds
+    }
+
+    /// UTC timestamp like `2024-03-01T12:30:05.250Z`, with `:60` during a leap second
+    pub fn to_iso8601(&self) -> String {
+        let (mut mjd, seconds_of_day) = self.utc_day();
+        let mut millis = (seconds_of_day * 1000.0).round() as i64;
+        let day_length = 86_400_000 + 1000 * (tai_minus_utc(mjd + 1) - tai_minus_utc(mjd)) as i64;
+        if millis >= day_length {
+            mjd += 1;
+            millis -= day_length;
+        }
+
+        let (hour, minute, millis) = if millis >= 86_400_000 {
+            (23, 59, millis - 86_340_000)
+        } else {
+            (millis / 3_600_000, millis / 60_000 % 60, millis % 60_000)
+        };
+        let (year, month, day) = mjd_to_calendar(mjd);
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            millis / 1000,
+            millis % 1000
+        )
+    }
+
+    /// Parse a UTC timestamp: `YYYY-MM-DD`, or with `THH:MM[:SS[.fff]]` and an optional `Z`
+    pub fn parse_iso8601(text: &str) -> Result<Self, TimeParseError> {
+        let error = || TimeParseError(text.to_string());
+        let text = text.trim();
+        let text = text.strip_suffix('Z').unwrap_or(text);
+        let (date, time) = match text.split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+
+        let date: Vec<i64> = date.split('-').map(|p| p.parse().map_err(|_| error())).collect::<Result<_, _>>()?;
+        let [year, month, day] = date[..] else {
+            return Err(error());
+        };
+        // Days past the end of the month roll into the next, so they fail the round trip
+        if !(1..=12).contains(&month) || mjd_to_calendar(calendar_to_mjd(year, month, day)) != (year, month, day) {
+            return Err(error());
+        }
+
+        let (hour, minute, second) = match time {
+            None => (0, 0, 0.0),
+            Some(time) => {
+                let mut parts = time.split(':');
+                let hour: u32 = parts.next().and_then(|p| p.parse().ok()).ok_or_else(error)?;
+                let minute: u32 = parts.next().and_then(|p| p.parse().ok()).ok_or_else(error)?;
+                let second: f64 = match parts.next() {
+                    Some(p) => p.parse().map_err(|_| error())?,
+                    None => 0.0,
+                };
+                if parts.next().is_some() || hour > 23 || minute > 59 || !(0.0..61.0).contains(&second) {
+                    return Err(error());
+                }
+                // :60 only exists in the last minute of a day that ends with an inserted leap second
+                let mjd = calendar_to_mjd(year, month, day);
+                if second >= 60.0 && (hour != 23 || minute != 59 || tai_minus_utc(mjd + 1) <= tai_minus_utc(mjd)) {
+                    return Err(error());
+                }
+                (hour, minute, second)
+            }
+        };
+
+        Ok(Self::from_utc(year, month, day, hour, minute, second))
+    }
+}
@@ -138,7 +138,9 @@ fn parse_exponential(s: &str) -> Result<f64, TleParseError> {
             let exponent: i32 = exponent_str.parse()
                 .map_err(|_| TleParseError::InvalidField("Exponential exponent".to_string()))?;
             
-            return Ok(mantissa * 10f64.powi(exponent - mantissa_str.len() as i32 + 1));
+            // Mantissa has an assumed leading decimal point: "12345-3" is 0.12345e-3
+            let digits = mantissa_str.trim_start_matches(['+', '-']).len() as i32;
+            return Ok(mantissa * 10f64.powi(exponent - digits));
         }
     }
     
@@ -0,0 +1,190 @@
+// Unit-safe scalar quantities
+// Positions are kept in km and velocities in km/s throughout the simulation, while object sizes
+// come from catalogs in metres; wrapping the scalars makes mixing the two a compile error
+
+use bevy::math::DVec3;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Metres per kilometre
+pub const METERS_PER_KM: f64 = 1000.0;
+
+/// Define a scalar quantity newtype with same-unit arithmetic and scaling by plain numbers
+macro_rules! quantity {
+    ($(#[$doc:meta])* $name:ident, $symbol:literal) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f64);
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                $name(self * rhs.0)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        /// The ratio of two like quantities is a plain number
+        impl Div for $name {
+            type Output = f64;
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)?;
+                write!(f, " {}", $symbol)
+            }
+        }
+    };
+}
+
+quantity!(
+    /// Length in kilometres, the unit of simulation positions
+    Km,
+    "km"
+);
+quantity!(
+    /// Length in metres, the unit of object sizes
+    Meters,
+    "m"
+);
+quantity!(
+    /// Speed in kilometres per second, the unit of simulation velocities
+    KmPerSec,
+    "km/s"
+);
+quantity!(
+    /// Speed in metres per second
+    MetersPerSec,
+    "m/s"
+);
+quantity!(
+    /// Acceleration in metres per second squared
+    MetersPerSec2,
+    "m/s²"
+);
+quantity!(
+    /// Mass in kilograms
+    Kg,
+    "kg"
+);
+quantity!(
+    /// Gravitational parameter GM in cubic kilometres per second squared, matching simulation
+    /// positions in km
+    Km3PerSec2,
+    "km³/s²"
+);
+
+impl Km {
+    /// Distance between two simulation positions
+    pub fn between(a: DVec3, b: DVec3) -> Self {
+        Self(a.distance(b))
+    }
+
+    /// Length of a simulation position or displacement vector
+    pub fn length(v: DVec3) -> Self {
+        Self(v.length())
+    }
+}
+
+impl KmPerSec {
+    /// Magnitude of a simulation velocity vector
+    pub fn length(v: DVec3) -> Self {
+        Self(v.length())
+    }
+}
+
+impl Km3PerSec2 {
+    /// Speed of a circular orbit of radius `r` about the body
+    pub fn circular_speed(self, r: Km) -> KmPerSec {
+        KmPerSec((self.0 / r.0).sqrt())
+    }
+
+    /// Speed needed to escape the body from radius `r`
+    pub fn escape_speed(self, r: Km) -> KmPerSec {
+        KmPerSec((2.0 * self.0 / r.0).sqrt())
+    }
+
+    /// Gravitational acceleration at distance `r` from the body
+    pub fn acceleration(self, r: Km) -> MetersPerSec2 {
+        MetersPerSec2(self.0 / (r.0 * r.0) * METERS_PER_KM)
+    }
+
+    /// Gravitational potential energy in Joules of `mass` at distance `r` from the body
+    pub fn potential_energy(self, mass: Kg, r: Km) -> f64 {
+        -self.0 * METERS_PER_KM.powi(2) * mass.0 / r.0
+    }
+}
+
+impl From<Meters> for Km {
+    fn from(m: Meters) -> Self {
+        Km(m.0 / METERS_PER_KM)
+    }
+}
+
+impl From<Km> for Meters {
+    fn from(km: Km) -> Self {
+        Meters(km.0 * METERS_PER_KM)
+    }
+}
+
+impl From<MetersPerSec> for KmPerSec {
+    fn from(v: MetersPerSec) -> Self {
+        KmPerSec(v.0 / METERS_PER_KM)
+    }
+}
+
+impl From<KmPerSec> for MetersPerSec {
+    fn from(v: KmPerSec) -> Self {
+        MetersPerSec(v.0 * METERS_PER_KM)
+    }
+}
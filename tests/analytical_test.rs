@@ -0,0 +1,235 @@
+// Unit tests for the analytical propagation backend
+// Tests two-body agreement with numerical integration, secular J2 rates, drag decay and century-long jumps
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+use std::f64::consts::TAU;
+
+fn numerical(elements: &KeplerianElements, forces: &ForceModel, duration: f64, dt: f64) -> (DVec3, DVec3) {
+    let mu = Constants::default().gravitational_parameter.0;
+    let (mut position, mut velocity) = elements.to_state(mu);
+    for _ in 0..(duration / dt) as usize {
+        (position, velocity) = RK4Integrator.integrate(position, velocity, dt, forces, ForceCoefficients::default());
+    }
+    (position, velocity)
+}
+
+#[test]
+fn test_true_anomaly_from_mean_inverts_mean_anomaly() {
+    for e in [0.0, 0.1, 0.6, 0.95] {
+        let elements = KeplerianElements { eccentricity: e, true_anomaly: 2.5, ..eccentric_elements(9000.0, 0.0, 30.0) };
+        let true_anomaly = true_anomaly_from_mean(elements.mean_anomaly(), e).unwrap();
+        assert_relative_eq!(true_anomaly, 2.5, epsilon = 1e-10);
+    }
+}
+
+#[test]
+fn test_two_body_matches_numerical_integration() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let elements = eccentric_elements(8000.0, 0.15, 40.0);
+    let forces = ForceModel::gravity_only(Geopotential::new(&constants, GravityModel::PointMass));
+
+    let analytical = propagator(GravityModel::PointMass, None).propagate(&elements, 5000.0, 0.0).unwrap();
+    let (position, velocity) = analytical.to_state(mu);
+    let (expected_position, expected_velocity) = numerical(&elements, &forces, 5000.0, 5.0);
+
+    assert!((position - expected_position).length() < 1e-3, "Off by {:.3e} km", (position - expected_position).length());
+    assert!((velocity - expected_velocity).length() < 1e-6);
+}
+
+#[test]
+fn test_sun_synchronous_raan_drift() {
+    // ~700 km sun-synchronous orbit precesses with the mean Sun, ~0.9856°/day
+    let elements = eccentric_elements(7078.0, 0.001, 98.19);
+    let (raan_rate, _, _) = propagator(GravityModel::J2, None).secular_rates(&elements);
+    assert_relative_eq!(raan_rate.to_degrees() * SECONDS_PER_DAY, 0.9856, max_relative = 0.01);
+
+    // Critical inclination freezes the argument of perigee
+    let critical = eccentric_elements(26600.0, 0.7, 63.435);
+    let (_, arg_perigee_rate, _) = propagator(GravityModel::J2, None).secular_rates(&critical);
+    assert!(arg_perigee_rate.abs() < 1e-12);
+}
+
+#[test]
+fn test_secular_j2_follows_numerical_raan_drift() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let elements = eccentric_elements(7000.0, 0.01, 51.6);
+    let forces = ForceModel::gravity_only(Geopotential::new(&constants, GravityModel::J2));
+
+    let analytical = propagator(GravityModel::J2, None).propagate(&elements, SECONDS_PER_DAY, 0.0).unwrap();
+    let (position, velocity) = numerical(&elements, &forces, SECONDS_PER_DAY, 10.0);
+    let osculating = KeplerianElements::from_state(position, velocity, mu);
+
+    // A day of drift is ~4.5°; the analytical node tracks it to within the short-period wobble
+    let drift = (analytical.raan - elements.raan).to_degrees();
+    assert!(drift < -4.0 && drift > -5.0, "RAAN drift {:.3}°", drift);
+    assert!((analytical.raan - osculating.raan).to_degrees().abs() < 0.05);
+}
+
+#[test]
+fn test_mean_elements_invert_short_period_terms() {
+    let constants = Constants::default();
+    let j2 = propagator(GravityModel::J2, None);
+    for elements in [eccentric_elements(7000.0, 0.001, 53.0), eccentric_elements(8000.0, 0.15, 98.0)] {
+        let (position, velocity) = elements.to_state(constants.gravitational_parameter.0);
+        let mean = j2.mean_elements(position, velocity);
+        let (osculating_position, osculating_velocity) = j2.osculating_state(&mean);
+        assert!((osculating_position - position).length() < 1e-6);
+        assert!((osculating_velocity - velocity).length() < 1e-9);
+        // The terms move a LEO orbit by kilometres
+        assert!((mean.semi_major_axis - elements.semi_major_axis).abs() > 1.0);
+    }
+
+    // Without J2 mean and osculating elements coincide
+    let (position, velocity) = eccentric_elements(7000.0, 0.001, 53.0).to_state(constants.gravitational_parameter.0);
+    let point_mass = propagator(GravityModel::PointMass, None);
+    let mean = point_mass.mean_elements(position, velocity);
+    assert_eq!(mean, KeplerianElements::from_state(position, velocity, point_mass.mu));
+    assert_eq!(point_mass.osculating_state(&mean), mean.to_state(point_mass.mu));
+}
+
+#[test]
+fn test_drag_decays_low_orbits() {
+    let drag = propagator(GravityModel::J2, Some(AtmosphereModel::Exponential));
+    let elements = eccentric_elements(Constants::default().earth_radius.0 + 300.0, 0.0005, 51.6);
+
+    let week = drag.propagate(&elements, 7.0 * SECONDS_PER_DAY, 0.01).unwrap();
+    let month = drag.propagate(&elements, 30.0 * SECONDS_PER_DAY, 0.01).unwrap();
+    assert!(week.semi_major_axis < elements.semi_major_axis);
+    assert!(month.semi_major_axis < week.semi_major_axis);
+
+    // A dense object barely notices the atmosphere and the same orbit without drag does not decay
+    let dense = drag.propagate(&elements, 30.0 * SECONDS_PER_DAY, 0.0001).unwrap();
+    assert!(dense.semi_major_axis > month.semi_major_axis);
+    let vacuum = propagator(GravityModel::J2, None).propagate(&elements, 30.0 * SECONDS_PER_DAY, 0.01).unwrap();
+    assert_eq!(vacuum.semi_major_axis, elements.semi_major_axis);
+}
+
+#[test]
+fn test_century_jump_keeps_geo_orbit() {
+    let constants = Constants::default();
+    let elements = eccentric_elements(42164.0, 0.0002, 0.05);
+    let century = 100.0 * 365.25 * SECONDS_PER_DAY;
+
+    let propagated = propagator(GravityModel::J2, Some(AtmosphereModel::Exponential))
+        .propagate(&elements, century, 0.01)
+        .unwrap();
+    assert_eq!(propagated.semi_major_axis, elements.semi_major_axis);
+    assert!(propagated.raan >= 0.0 && propagated.raan < TAU);
+
+    let (position, _) = propagated.to_state(constants.gravitational_parameter.0);
+    assert_relative_eq!(position.length(), 42164.0, max_relative = 1e-3);
+
+    let escape = KeplerianElements { eccentricity: 1.3, semi_major_axis: -9000.0, ..elements };
+    assert!(propagator(GravityModel::J2, None).propagate(&escape, 60.0, 0.0).is_none());
+}
+
+fn analytical_world(timestep: f64) -> World {
+    physics_test_world(
+        IntegratorConfig {
+            backend: PhysicsBackend::Analytical,
+            gravity_model: GravityModel::PointMass,
+            atmosphere_model: None,
+            ..Default::default()
+        },
+        timestep,
+    )
+}
+
+#[test]
+fn test_analytical_backend_runs_a_century_in_one_frame() {
+    let mut world = analytical_world(3600.0);
+    let geo = world.spawn(create_test_orbital_state(35786.0)).id();
+    let century = 100.0 * 365.25 * SECONDS_PER_DAY;
+
+    // Far more sub-steps than the budget allows, all taken because the cost does not grow
+    world.resource_mut::<SimulationTime>().accumulator = century;
+    let system = world.register_system(physics_system);
+    world.run_system(system).unwrap();
+
+    let sim_time = world.resource::<SimulationTime>();
+    assert_relative_eq!(sim_time.current, century, max_relative = 1e-12);
+    assert!(!sim_time.behind);
+
+    let state = world.get::<OrbitalState>(geo).unwrap();
+    assert_relative_eq!(state.position.length(), Constants::default().earth_radius.0 + 35786.0, max_relative = 1e-9);
+    let mean = world.get::<MeanElements>(geo).expect("Analytical objects keep their mean elements");
+    assert_eq!(mean.epoch, century);
+}
+
+#[test]
+fn test_analytical_backend_follows_numerical_j2_orbit() {
+    // 700 km at 53°, where osculating seeding drifted along-track by ~200 km in six hours
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let elements = eccentric_elements(constants.earth_radius.0 + 700.0, 0.0, 53.0);
+    let (position, velocity) = elements.to_state(mu);
+    let mut world = analytical_world(60.0);
+    world.resource_mut::<IntegratorConfig>().gravity_model = GravityModel::J2;
+    let entity = world.spawn(OrbitalState::new(position, velocity, Kg(1000.0))).id();
+
+    let system = world.register_system(physics_system);
+    for _ in 0..6 {
+        world.resource_mut::<SimulationTime>().accumulator = 3600.0;
+        world.run_system(system).unwrap();
+    }
+
+    let forces = ForceModel::gravity_only(Geopotential::new(&constants, GravityModel::J2));
+    let (expected, _) = numerical(&elements, &forces, 6.0 * 3600.0, 10.0);
+    let error = (world.get::<OrbitalState>(entity).unwrap().position - expected).length();
+    assert!(error < 1.0, "Off by {:.2} km after six hours", error);
+}
+
+#[test]
+fn test_analytical_backend_reanchors_after_external_changes() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let mut world = analytical_world(10.0);
+    let entity = world.spawn(create_test_orbital_state(400.0)).id();
+    let system = world.register_system(physics_system);
+
+    world.resource_mut::<SimulationTime>().accumulator = 600.0;
+    world.run_system(system).unwrap();
+
+    // Another system raises the orbit with an impulsive burn
+    let burned = {
+        let mut state = world.get_mut::<OrbitalState>(entity).unwrap();
+        state.velocity *= 1.01;
+        (state.position, state.velocity)
+    };
+    world.resource_mut::<SimulationTime>().accumulator = 600.0;
+    world.run_system(system).unwrap();
+
+    let expected = KeplerianElements::from_state(burned.0, burned.1, mu);
+    let mean = world.get::<MeanElements>(entity).unwrap();
+    assert_relative_eq!(mean.elements.semi_major_axis, expected.semi_major_axis, max_relative = 1e-9);
+    assert_eq!(mean.epoch, 1200.0);
+}
+
+#[test]
+fn test_analytical_backend_integrates_escape_orbits_numerically() {
+    let mut world = analytical_world(10.0);
+    let mut state = create_test_orbital_state(400.0);
+    state.velocity *= 1.5;
+    let (position, velocity) = (state.position, state.velocity);
+    let entity = world.spawn(state).id();
+
+    world.resource_mut::<SimulationTime>().accumulator = 10.0;
+    let system = world.register_system(physics_system);
+    world.run_system(system).unwrap();
+
+    let forces = ForceModel::gravity_only(Geopotential::new(&Constants::default(), GravityModel::PointMass));
+    let expected = EulerIntegrator.integrate(position, velocity, 10.0, &forces, ForceCoefficients::default());
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    assert_eq!((state.position, state.velocity), expected);
+    assert!(world.get::<MeanElements>(entity).is_none());
+}
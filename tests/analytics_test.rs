@@ -3,8 +3,10 @@
 
 use kessler_simulator::components::*;
 use kessler_simulator::resources::*;
+use kessler_simulator::utils::Kg;
 mod common;
 use approx::assert_relative_eq;
+use bevy::math::DVec3;
 use bevy::prelude::*;
 use common::*;
 
@@ -74,11 +76,11 @@ fn test_add_energy_measurement() {
     let constants = Constants::default();
 
     // Create orbital state at 400km altitude
-    let position = Vec3::new(6771.0, 0.0, 0.0); // 400km altitude
-    let velocity = Vec3::new(0.0, 7.66, 0.0);
-    let orbital_state = OrbitalState::new(position, velocity, 1000.0);
+    let position = DVec3::new(6771.0, 0.0, 0.0); // 400km altitude
+    let velocity = DVec3::new(0.0, 7.66, 0.0);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(1000.0));
 
-    let altitude = orbital_state.altitude() - constants.earth_radius; // 400km
+    let altitude = (orbital_state.radius() - constants.earth_radius).0; // 400km
     let energy = orbital_state.total_energy(constants.gravitational_parameter);
 
     analytics.add_energy_measurement(altitude, energy);
@@ -151,9 +153,9 @@ fn test_multiple_altitude_bins() {
     // Add measurements at different altitudes
     let altitudes = vec![300.0, 400.0, 500.0, 600.0];
     for altitude in altitudes {
-        let position = Vec3::new((6371.0 + altitude) as f32, 0.0, 0.0);
-        let velocity = Vec3::new(0.0, 7.66, 0.0);
-        let orbital_state = OrbitalState::new(position, velocity, 1000.0);
+        let position = DVec3::new(6371.0 + altitude, 0.0, 0.0);
+        let velocity = DVec3::new(0.0, 7.66, 0.0);
+        let orbital_state = OrbitalState::new(position, velocity, Kg(1000.0));
         let energy = orbital_state.total_energy(constants.gravitational_parameter);
         analytics.add_energy_measurement(altitude, energy);
     }
@@ -214,9 +216,9 @@ fn test_statistics_calculation() {
     let mut total_energy_sum = 0.0;
     for i in 0..10 {
         let altitude = 300.0 + (i as f64 * 50.0);
-        let position = Vec3::new((6371.0 + altitude) as f32, 0.0, 0.0);
-        let velocity = Vec3::new(0.0, 7.66, 0.0);
-        let orbital_state = OrbitalState::new(position, velocity, 1000.0);
+        let position = DVec3::new(6371.0 + altitude, 0.0, 0.0);
+        let velocity = DVec3::new(0.0, 7.66, 0.0);
+        let orbital_state = OrbitalState::new(position, velocity, Kg(1000.0));
         let energy = orbital_state.total_energy(constants.gravitational_parameter);
         analytics.add_energy_measurement(altitude, energy);
         total_energy_sum += energy;
@@ -0,0 +1,200 @@
+// Unit tests for the NASA Standard Breakup Model
+// Tests the catastrophic threshold, size/A-M/delta-V distributions and collision fragmentation
+
+use kessler_simulator::components::*;
+use kessler_simulator::utils::*;
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn parent(mass: f64, velocity: DVec3, characteristic_length: f64) -> BreakupParent {
+    BreakupParent {
+        mass,
+        velocity,
+        characteristic_length,
+        class: ObjectClass::Spacecraft,
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[test]
+fn test_catastrophic_threshold() {
+    // 1 kg at 10 km/s into 1000 kg is 50 J/g: catastrophic
+    assert_relative_eq!(specific_impact_energy(1000.0, 1.0, 10.0), 50_000.0);
+    assert!(specific_impact_energy(1000.0, 1.0, 10.0) >= CATASTROPHIC_ENERGY_THRESHOLD);
+
+    // 0.5 kg at 10 km/s is 25 J/g: cratering only
+    assert!(specific_impact_energy(1000.0, 0.5, 10.0) < CATASTROPHIC_ENERGY_THRESHOLD);
+}
+
+#[test]
+fn test_collision_fragment_count_power_law() {
+    // N(Lc) = 0.1 M^0.75 Lc^-1.71
+    assert_relative_eq!(
+        collision_fragment_count(1000.0, 0.1),
+        0.1 * 1000.0_f64.powf(0.75) * 0.1_f64.powf(-1.71),
+        max_relative = 1e-12
+    );
+    assert_relative_eq!(collision_fragment_count(1000.0, 1.0), 0.1 * 1000.0_f64.powf(0.75), max_relative = 1e-12);
+}
+
+#[test]
+fn test_characteristic_length_distribution() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let (min, max) = (0.1, 5.0);
+    let samples: Vec<f64> = (0..20000)
+        .map(|_| sample_characteristic_length(min, max, 1.71, &mut rng))
+        .collect();
+
+    assert!(samples.iter().all(|&lc| (min..=max).contains(&lc)));
+
+    // Fraction larger than 2·Lmin follows the truncated power law
+    let truncation = (max / min).powf(-1.71);
+    let expected = (2.0_f64.powf(-1.71) - truncation) / (1.0 - truncation);
+    let observed = samples.iter().filter(|&&lc| lc > 2.0 * min).count() as f64 / samples.len() as f64;
+    assert_relative_eq!(observed, expected, max_relative = 0.05);
+}
+
+#[test]
+fn test_small_fragment_area_to_mass_distribution() {
+    // At Lc = 1 cm (log10 = -2) the small-object distribution has mean -0.3 and sigma 0.4
+    let mut rng = StdRng::seed_from_u64(11);
+    let chis: Vec<f64> = (0..20000)
+        .map(|_| sample_area_to_mass(0.01, ObjectClass::Spacecraft, &mut rng).log10())
+        .collect();
+
+    let m = mean(&chis);
+    let sigma = (chis.iter().map(|c| (c - m).powi(2)).sum::<f64>() / chis.len() as f64).sqrt();
+    assert_relative_eq!(m, -0.3, epsilon = 0.02);
+    assert_relative_eq!(sigma, 0.4, epsilon = 0.02);
+}
+
+#[test]
+fn test_large_fragment_area_to_mass_by_class() {
+    // At Lc = 1 m rocket-body fragments have a higher mean A/M than spacecraft fragments
+    let mut rng = StdRng::seed_from_u64(13);
+    let spacecraft: Vec<f64> = (0..20000)
+        .map(|_| sample_area_to_mass(1.0, ObjectClass::Spacecraft, &mut rng).log10())
+        .collect();
+    let rocket_body: Vec<f64> = (0..20000)
+        .map(|_| sample_area_to_mass(1.0, ObjectClass::RocketBody, &mut rng).log10())
+        .collect();
+
+    // Spacecraft: alpha = 0.78, means -0.95 and -2.0
+    let alpha_sc = 0.3 + 0.4 * (0.0 + 1.2);
+    assert_relative_eq!(mean(&spacecraft), alpha_sc * -0.95 + (1.0 - alpha_sc) * -2.0, epsilon = 0.02);
+    // Rocket body: alpha = 0.5, both means -0.9
+    assert_relative_eq!(mean(&rocket_body), -0.9, epsilon = 0.02);
+}
+
+#[test]
+fn test_delta_v_distribution() {
+    let mut rng = StdRng::seed_from_u64(17);
+
+    // log10(dV) has mean 0.9·chi + 2.9 for collisions, 0.2·chi + 1.85 for explosions
+    let collision: Vec<f64> = (0..20000)
+        .map(|_| sample_delta_v(0.1, BreakupKind::Collision, &mut rng).0.log10())
+        .collect();
+    let explosion: Vec<f64> = (0..20000)
+        .map(|_| sample_delta_v(0.1, BreakupKind::Explosion, &mut rng).0.log10())
+        .collect();
+
+    assert_relative_eq!(mean(&collision), 2.0, epsilon = 0.02);
+    assert_relative_eq!(mean(&explosion), 1.65, epsilon = 0.02);
+}
+
+#[test]
+fn test_fragment_area_is_continuous() {
+    let below = fragment_area(0.00167 - 1e-9);
+    let above = fragment_area(0.00167);
+    assert_relative_eq!(below, above, max_relative = 0.01);
+    assert_relative_eq!(fragment_area(1.0), 0.556945, max_relative = 1e-12);
+}
+
+#[test]
+fn test_catastrophic_collision_breakup() {
+    let mut rng = StdRng::seed_from_u64(23);
+    let config = BreakupConfig::default();
+    let target = parent(1000.0, DVec3::new(0.0, 7.5, 0.0), 2.0);
+    let projectile = parent(10.0, DVec3::new(0.0, -2.5, 0.0), 0.3);
+
+    let result = collision_breakup(&config, &projectile, &target, &mut rng);
+
+    assert!(result.catastrophic);
+    assert_eq!(result.remnant_mass, 0.0);
+
+    let expected = collision_fragment_count(1010.0, config.min_characteristic_length)
+        - collision_fragment_count(1010.0, 2.0);
+    assert!(result.fragments.len() as f64 > 0.5 * expected);
+    assert!(result.fragments.len() as f64 <= expected + 1.0);
+
+    let fragment_mass: f64 = result.fragments.iter().map(|f| f.mass).sum();
+    assert!(fragment_mass <= 1010.0);
+
+    // Most fragments leave at tens to hundreds of m/s
+    let mut speeds: Vec<f64> = result
+        .fragments
+        .iter()
+        .map(|f| (f.velocity - target.velocity).length().min((f.velocity - projectile.velocity).length()))
+        .collect();
+    speeds.sort_by(f64::total_cmp);
+    let median = speeds[speeds.len() / 2];
+    assert!(median > 0.01 && median < 1.0, "Median ejection speed {:.3} km/s", median);
+
+    for fragment in &result.fragments {
+        assert!(fragment.characteristic_length >= config.min_characteristic_length);
+        assert!(fragment.characteristic_length <= 2.0);
+        assert_relative_eq!(fragment.mass * fragment.area_to_mass, fragment.area, max_relative = 1e-9);
+        // Ejection speed never exceeds the 10 km/s impact speed
+        let from_target = (fragment.velocity - target.velocity).length();
+        let from_projectile = (fragment.velocity - projectile.velocity).length();
+        assert!(from_target.min(from_projectile) <= 10.0 + 1e-4);
+    }
+}
+
+#[test]
+fn test_non_catastrophic_collision_leaves_remnant() {
+    let mut rng = StdRng::seed_from_u64(29);
+    let config = BreakupConfig::default();
+    let target = parent(1000.0, DVec3::new(0.0, 7.5, 0.0), 2.0);
+    let projectile = parent(0.05, DVec3::new(0.0, 7.5, 1.0), 0.05);
+
+    let result = collision_breakup(&config, &target, &projectile, &mut rng);
+
+    assert!(!result.catastrophic);
+    // Ejecta mass is m_p·v² = 0.05 kg
+    assert_relative_eq!(result.remnant_mass, 1000.0, epsilon = 1e-9);
+    let fragment_mass: f64 = result.fragments.iter().map(|f| f.mass).sum();
+    assert!(fragment_mass <= 0.05 + 1e-12);
+}
+
+#[test]
+fn test_fragment_cap_keeps_largest() {
+    let mut rng = StdRng::seed_from_u64(31);
+    let config = BreakupConfig { min_characteristic_length: 0.05, max_fragments: 50 };
+    let target = parent(2000.0, DVec3::new(7.5, 0.0, 0.0), 3.0);
+    let projectile = parent(500.0, DVec3::new(-7.5, 0.0, 0.0), 1.5);
+
+    let result = collision_breakup(&config, &target, &projectile, &mut rng);
+    assert_eq!(result.fragments.len(), 50);
+
+    let smallest_kept = result
+        .fragments
+        .iter()
+        .map(|f| f.characteristic_length)
+        .fold(f64::MAX, f64::min);
+    assert!(smallest_kept > 0.1, "Cap should drop the smallest fragments first");
+}
+
+#[test]
+fn test_fragment_physics_object_uses_area() {
+    let physics_object = PhysicsObject::fragment(0.02, 0.2);
+    assert_eq!(physics_object.cross_section, 0.02);
+    assert_eq!(physics_object.collision_radius, Meters(0.1));
+    assert_relative_eq!(physics_object.ballistic_coefficient(Kg(0.5)), 2.2 * 0.02 / 0.5);
+}
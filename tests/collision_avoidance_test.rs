@@ -0,0 +1,295 @@
+// Unit tests for conjunction screening and collision avoidance
+// Tests closest approach search, collision probability, burn sizing and the avoidance system's bookkeeping
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+const RADIUS: f64 = 6371.0 + 550.0;
+const ENCOUNTER: f64 = 2800.0;
+
+/// States of two objects whose orbits cross at their common node, `offset` km apart along the
+/// second when the first passes through it `ENCOUNTER` seconds from now; they pass again at the
+/// opposite node every half orbit after that
+///
+/// The encounter is flown back to now by numerical integration with J2, so it is the truth the
+/// screening has to find.
+fn crossing_states(offset: f64) -> ((DVec3, DVec3), (DVec3, DVec3)) {
+    let constants = Constants::default();
+    let forces = ForceModel::gravity_only(Geopotential::new(&constants, GravityModel::J2));
+    let back = |elements: KeplerianElements| {
+        let (mut position, mut velocity) = elements.to_state(constants.gravitational_parameter.0);
+        for _ in 0..(ENCOUNTER / 10.0) as usize {
+            (position, velocity) =
+                RK4Integrator.integrate(position, velocity, -10.0, &forces, ForceCoefficients::default());
+        }
+        (position, velocity)
+    };
+    (back(circular_elements(RADIUS, 0.2, 0.0)), back(circular_elements(RADIUS, 1.5, offset / RADIUS)))
+}
+
+/// Mean elements of the crossing pair, as the screening sees them
+fn crossing_pair(offset: f64) -> (KeplerianElements, KeplerianElements) {
+    let propagator = propagator(GravityModel::J2, None);
+    let ((position_a, velocity_a), (position_b, velocity_b)) = crossing_states(offset);
+    (propagator.mean_elements(position_a, velocity_a), propagator.mean_elements(position_b, velocity_b))
+}
+
+fn orbital_state(state: (DVec3, DVec3), mass: f64) -> OrbitalState {
+    OrbitalState::new(state.0, state.1, Kg(mass))
+}
+
+fn avoidance_world(offset: f64, active: bool) -> (World, Entity, Entity) {
+    let (satellite, debris) = crossing_states(offset);
+    let mut world = simulation_test_world(0.0);
+    world.insert_resource(AvoidanceConfig::default());
+    world.init_resource::<AvoidanceLog>();
+    let satellite = world
+        .spawn((
+            Satellite::new("SAT".to_string(), 12345, active),
+            orbital_state(satellite, 500.0),
+            PhysicsObject::satellite(Kg(500.0)),
+        ))
+        .id();
+    let debris = world
+        .spawn((orbital_state(debris, 10.0), PhysicsObject::debris(Kg(10.0)), Debris::new(None, 1, 0.0)))
+        .id();
+    (world, satellite, debris)
+}
+
+#[test]
+fn test_closest_approach_finds_crossing() {
+    let propagator = propagator(GravityModel::J2, None);
+    let (a, b) = crossing_pair(0.05);
+    let conjunction = closest_approach(&propagator, &a, &b, 6.0 * 3600.0, 60.0).unwrap();
+    assert_relative_eq!(conjunction.time_to_closest_approach, ENCOUNTER, epsilon = 1.0);
+    assert!(conjunction.miss_distance < 0.5, "miss {}", conjunction.miss_distance);
+    // Near-polar against low-inclination crossing: a large fraction of orbital speed
+    assert!(conjunction.relative_speed > 7.0);
+
+    // A window that ends before the crossing sees the objects far apart
+    let early = closest_approach(&propagator, &a, &b, 1800.0, 60.0).unwrap();
+    assert!(early.miss_distance > 100.0);
+
+    // Osculating elements flown at the mean rates drift away from the true encounter
+    let mu = Constants::default().gravitational_parameter.0;
+    let ((position_a, velocity_a), (position_b, velocity_b)) = crossing_states(0.05);
+    let osculating_a = KeplerianElements::from_state(position_a, velocity_a, mu);
+    let osculating_b = KeplerianElements::from_state(position_b, velocity_b, mu);
+    let drifted = closest_approach(&propagator, &osculating_a, &osculating_b, 6.0 * 3600.0, 60.0).unwrap();
+    assert!(drifted.miss_distance > 5.0, "miss {}", drifted.miss_distance);
+}
+
+#[test]
+fn test_radial_ranges_overlap() {
+    let low = circular_elements(RADIUS, 0.5, 0.0);
+    let high = KeplerianElements { semi_major_axis: RADIUS + 300.0, ..low };
+    assert!(radial_ranges_overlap(&low, &low, 0.0));
+    assert!(!radial_ranges_overlap(&low, &high, 10.0));
+    assert!(radial_ranges_overlap(&low, &high, 400.0));
+
+    // An eccentric orbit reaching down through the shell overlaps it
+    let eccentric = KeplerianElements { semi_major_axis: RADIUS + 1000.0, eccentricity: 0.15, ..low };
+    assert!(radial_ranges_overlap(&low, &eccentric, 0.0));
+}
+
+#[test]
+fn test_collision_probability() {
+    // Head-on: the Gaussian mass inside radius R is 1 - exp(-R²/2σ²)
+    let exact = 1.0 - (-0.01_f64 * 0.01 / (2.0 * 0.2 * 0.2)).exp();
+    assert_relative_eq!(collision_probability(0.0, 0.01, 0.2), exact, max_relative = 1e-3);
+
+    // Falls off with miss distance and vanishes far outside the uncertainty
+    assert!(collision_probability(0.2, 0.01, 0.2) < collision_probability(0.0, 0.01, 0.2));
+    assert!(collision_probability(5.0, 0.01, 0.2) < 1e-20);
+
+    // A perfectly known conjunction either hits or misses
+    assert_eq!(collision_probability(0.005, 0.01, 0.0), 1.0);
+    assert_eq!(collision_probability(0.02, 0.01, 0.0), 0.0);
+}
+
+#[test]
+fn test_along_track_response() {
+    let n = (Constants::default().gravitational_parameter.0 / RADIUS.powi(3)).sqrt();
+    let half_orbit = std::f64::consts::PI / n;
+
+    // Half an orbit after a prograde burn: 4/n higher and 3π/n behind per unit delta-V
+    let response = along_track_response(n, half_orbit);
+    assert_relative_eq!(response.x, 4.0 / n, max_relative = 1e-12);
+    assert_relative_eq!(response.y, -3.0 * std::f64::consts::PI / n, max_relative = 1e-12);
+    assert_eq!(response.z, 0.0);
+    // Straight after the burn the object simply moves ahead at the delta-V
+    assert_relative_eq!(along_track_response(n, 1.0).y, 1.0, max_relative = 1e-3);
+}
+
+#[test]
+fn test_active_satellite_plans_avoidance_burn() {
+    let (mut world, satellite, debris) = avoidance_world(0.05, true);
+    world.run_system_once(collision_avoidance_system).unwrap();
+
+    let config = AvoidanceConfig::default();
+    let log = world.resource::<AvoidanceLog>();
+    assert_eq!(log.total(), 1);
+    let maneuver = &log.maneuvers[0];
+    assert_eq!(maneuver.satellite, satellite);
+    assert_eq!(maneuver.threat, debris);
+    assert!(maneuver.probability > 1e-4);
+    assert_relative_eq!(maneuver.closest_approach, ENCOUNTER, epsilon = 1.0);
+    assert_relative_eq!(maneuver.burn_time, ENCOUNTER - config.lead_time, epsilon = 1.0);
+    assert!(maneuver.delta_v > 0.0 && maneuver.delta_v <= config.max_delta_v);
+    assert_eq!(log.per_satellite()[&12345], 1);
+
+    // The satellite was given a plan holding the prograde burn
+    let plan = world.get::<ManeuverPlan>(satellite).unwrap();
+    let burn = plan.pending().next().unwrap();
+    assert_eq!(burn.frame, BurnFrame::Rtn);
+    assert_relative_eq!(burn.delta_v.y, maneuver.delta_v);
+    assert_eq!(burn.time, maneuver.burn_time);
+
+    // Later screenings before the closest approach leave the handled conjunction alone
+    world.resource_mut::<AvoidanceConfig>().screening_interval = 0.0;
+    world.resource_mut::<SimulationTime>().current = 1000.0;
+    world.run_system_once(collision_avoidance_system).unwrap();
+    assert_eq!(world.resource::<AvoidanceLog>().total(), 1);
+    assert_eq!(world.get::<ManeuverPlan>(satellite).unwrap().pending().count(), 1);
+}
+
+#[test]
+fn test_avoidance_burn_opens_target_separation() {
+    let mu = Constants::default().gravitational_parameter.0;
+    let config = AvoidanceConfig::default();
+    let (satellite, debris) = crossing_pair(0.05);
+    let propagator = propagator(GravityModel::J2, None);
+
+    let before = closest_approach(&propagator, &satellite, &debris, 6.0 * 3600.0, 60.0).unwrap();
+    let burn = config.plan_burn(0.0, &before, satellite.mean_motion(mu).unwrap());
+    assert_relative_eq!(burn.time, ENCOUNTER - config.lead_time, epsilon = 1.0);
+    let (position, velocity) = propagator.propagate(&satellite, burn.time, 0.0).unwrap().to_state(mu);
+    let moved = KeplerianElements::from_state(position, velocity + rtn_to_inertial(position, velocity, burn.delta_v), mu);
+    let debris = propagator.propagate(&debris, burn.time, 0.0).unwrap();
+
+    let conjunction = closest_approach(&propagator, &moved, &debris, 2.0 * (ENCOUNTER - burn.time), 60.0).unwrap();
+    assert!(
+        conjunction.miss_distance > 0.9 * config.target_separation,
+        "miss after the burn {}",
+        conjunction.miss_distance
+    );
+    assert!(!config.requires_maneuver(&conjunction, config.hard_body_radius));
+}
+
+#[test]
+fn test_threshold_and_inactive_satellites() {
+    // A distant pass stays under the probability threshold
+    let (mut world, _, _) = avoidance_world(3.0, true);
+    world.run_system_once(collision_avoidance_system).unwrap();
+    assert_eq!(world.resource::<AvoidanceLog>().total(), 0);
+
+    // ... but not a miss distance threshold set wider than it
+    let (mut world, _, _) = avoidance_world(3.0, true);
+    world.insert_resource(AvoidanceConfig {
+        threshold: AvoidanceThreshold::MissDistance(5.0),
+        ..Default::default()
+    });
+    world.run_system_once(collision_avoidance_system).unwrap();
+    assert_eq!(world.resource::<AvoidanceLog>().total(), 1);
+
+    // Dead satellites cannot maneuver
+    let (mut world, satellite, _) = avoidance_world(0.05, false);
+    world.run_system_once(collision_avoidance_system).unwrap();
+    assert_eq!(world.resource::<AvoidanceLog>().total(), 0);
+    assert!(world.get::<ManeuverPlan>(satellite).is_none());
+}
+
+#[test]
+fn test_coarse_screen_keeps_close_passes() {
+    let propagator = propagator(GravityModel::J2, None);
+    let (satellite, near) = crossing_pair(0.05);
+    // Crosses the same node 50 km higher
+    let far = KeplerianElements { semi_major_axis: RADIUS + 50.0, ..near };
+    let objects = [satellite, near, far];
+
+    let pairs = coarse_screen(&propagator, &objects, &[0], 6.0 * 3600.0, 60.0, 10.0);
+    assert_eq!(pairs, vec![(0, 1)]);
+    // Every object screens against every other when all are primaries
+    let pairs = coarse_screen(&propagator, &objects[..2], &[0, 1], 6.0 * 3600.0, 60.0, 10.0);
+    assert_eq!(pairs, vec![(0, 1), (1, 0)]);
+}
+
+#[test]
+fn test_constellation_screening_is_bounded() {
+    let mut world = simulation_test_world(0.0);
+    world.insert_resource(AvoidanceConfig::default());
+    world.init_resource::<AvoidanceLog>();
+    world.init_resource::<CatalogNumbers>();
+    world
+        .run_system_once(|mut commands: Commands, mut catalog: ResMut<CatalogNumbers>, constants: Res<Constants>| {
+            let definition = ConstellationDefinition::starlink_like();
+            spawn_constellation(&mut commands, &definition, &mut catalog, 0.0, &constants);
+        })
+        .unwrap();
+    let satellites = ConstellationDefinition::starlink_like().total();
+
+    let config = AvoidanceConfig::default();
+    world.run_system_once(collision_avoidance_system).unwrap();
+    let log = world.resource::<AvoidanceLog>();
+    let candidates = log.candidate_pairs;
+    assert!(log.screening_in_progress());
+    assert_eq!(log.frame_searches, config.max_pairs_per_frame);
+    // The coarse filter leaves a small fraction of the satellites' pairs to search
+    assert!(
+        candidates > config.max_pairs_per_frame && candidates < satellites * (satellites - 1) / 100,
+        "{} candidate pairs",
+        candidates
+    );
+
+    // The rest of the screening is spread over the following frames
+    let frames = candidates.div_ceil(config.max_pairs_per_frame);
+    for _ in 1..frames {
+        world.run_system_once(collision_avoidance_system).unwrap();
+        assert!(world.resource::<AvoidanceLog>().frame_searches <= config.max_pairs_per_frame);
+    }
+    let log = world.resource::<AvoidanceLog>();
+    assert!(!log.screening_in_progress());
+    assert_eq!(log.candidate_pairs, candidates);
+
+    // No new screening starts before the interval has passed
+    world.run_system_once(collision_avoidance_system).unwrap();
+    assert_eq!(world.resource::<AvoidanceLog>().frame_searches, 0);
+}
+
+#[test]
+fn test_avoidance_log_rates() {
+    let mut log = AvoidanceLog::default();
+    let maneuver = |norad_id: u32, time: f64| AvoidanceManeuver {
+        satellite: Entity::from_raw(norad_id),
+        norad_id,
+        threat: Entity::from_raw(999),
+        time,
+        burn_time: time,
+        closest_approach: time + 3600.0,
+        miss_distance: 0.1,
+        probability: 1e-3,
+        delta_v: 0.0005,
+    };
+    log.record(maneuver(1, 100.0));
+    log.record(maneuver(1, SECONDS_PER_YEAR + 100.0));
+    log.record(maneuver(2, SECONDS_PER_YEAR + 200.0));
+
+    assert_eq!(log.yearly_summary(), vec![1, 2]);
+    assert_eq!(log.per_satellite()[&1], 2);
+    assert_relative_eq!(log.total_delta_v(), 0.0015);
+    // Three maneuvers across two satellites over two years
+    assert_relative_eq!(log.rate_per_satellite_year(2, 2.0 * SECONDS_PER_YEAR), 0.75);
+
+    // Handled conjunctions are forgotten once their closest approach passes
+    assert!(log.is_handled(Entity::from_raw(1), Entity::from_raw(999)));
+    log.expire(2.0 * SECONDS_PER_YEAR);
+    assert!(!log.is_handled(Entity::from_raw(1), Entity::from_raw(999)));
+}
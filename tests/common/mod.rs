@@ -1,6 +1,7 @@
 // Shared test utilities and fixtures for Kessler Syndrome Simulator tests
 
-use bevy::prelude::*;
+use bevy::math::DVec3;
+use bevy::prelude::{Time, World};
 use kessler_simulator::components::*;
 use kessler_simulator::resources::*;
 use kessler_simulator::systems::collision::{OctreeNode, SpatialOctree};
@@ -10,17 +11,75 @@ use kessler_simulator::utils::*;
 /// Returns a circular orbit with proper velocity for that altitude
 pub fn create_test_orbital_state(altitude_km: f64) -> OrbitalState {
     let constants = Constants::default();
-    let radius_km = constants.earth_radius + altitude_km;
-    let orbital_velocity = constants.circular_velocity(altitude_km);
+    let radius_km = (constants.earth_radius + Km(altitude_km)).0;
+    let orbital_velocity = constants.circular_velocity(Km(altitude_km)).0;
 
     // Position at positive x-axis, velocity in positive y direction (circular orbit)
     OrbitalState::new(
-        Vec3::new(radius_km as f32, 0.0, 0.0),
-        Vec3::new(0.0, orbital_velocity as f32, 0.0),
-        1000.0, // Default mass of 1000 kg
+        DVec3::new(radius_km, 0.0, 0.0),
+        DVec3::new(0.0, orbital_velocity, 0.0),
+        Kg(1000.0), // Default mass of 1000 kg
     )
 }
 
+/// Create a circular orbit at a given altitude (km) inclined by `inclination_deg`,
+/// starting at its ascending node on the positive x-axis
+pub fn inclined_orbit(altitude_km: f64, inclination_deg: f64) -> OrbitalState {
+    let mut state = create_test_orbital_state(altitude_km);
+    let (sin_i, cos_i) = inclination_deg.to_radians().sin_cos();
+    state.velocity = DVec3::new(0.0, cos_i, sin_i) * state.velocity.length();
+    state
+}
+
+/// Elements of a circular orbit of radius `semi_major_axis` km in the plane through the x-axis,
+/// with inclination and position along it in radians
+pub fn circular_elements(semi_major_axis: f64, inclination: f64, true_anomaly: f64) -> KeplerianElements {
+    KeplerianElements {
+        semi_major_axis,
+        eccentricity: 0.0,
+        inclination,
+        raan: 0.0,
+        arg_perigee: 0.0,
+        true_anomaly,
+    }
+}
+
+/// Elements of a general orbit of semi-major axis `semi_major_axis` km, with node, perigee
+/// and position away from the reference axes
+pub fn eccentric_elements(semi_major_axis: f64, eccentricity: f64, inclination_deg: f64) -> KeplerianElements {
+    KeplerianElements {
+        semi_major_axis,
+        eccentricity,
+        inclination: inclination_deg.to_radians(),
+        raan: 0.4,
+        arg_perigee: 1.1,
+        true_anomaly: 2.0,
+    }
+}
+
+/// Create an analytical propagator over the default constants
+pub fn propagator(gravity_model: GravityModel, atmosphere: Option<AtmosphereModel>) -> AnalyticalPropagator {
+    AnalyticalPropagator::new(&Constants::default(), gravity_model, atmosphere)
+}
+
+/// Create a world holding the resources the physics stage reads
+/// Tests extend it with the resources and entities of the system under test
+pub fn physics_test_world(config: IntegratorConfig, timestep: f64) -> World {
+    let mut world = World::new();
+    world.insert_resource(Time::<()>::default());
+    world.insert_resource(Constants::default());
+    world.insert_resource(SimulationTime { timestep, ..Default::default() });
+    world.insert_resource(config);
+    world
+}
+
+/// Create a world for systems that only read the clock and constants, at simulation time `now`
+pub fn simulation_test_world(now: f64) -> World {
+    let mut world = physics_test_world(IntegratorConfig::default(), SimulationTime::default().timestep);
+    world.resource_mut::<SimulationTime>().current = now;
+    world
+}
+
 /// Create a test TLE record with specified parameters
 pub fn create_test_tle(name: &str, norad_id: u32) -> TleRecord {
     TleRecord {
@@ -73,9 +132,9 @@ pub fn assert_energy_conserved(before: f64, after: f64, tolerance: f64) {
 pub fn assert_orbital_valid(state: &OrbitalState, constants: &Constants) {
     // Position should be above Earth surface
     assert!(
-        state.altitude() > constants.earth_radius,
+        state.radius() > constants.earth_radius,
         "Orbital state position is below Earth surface: altitude={:.2} km",
-        state.altitude() - constants.earth_radius
+        (state.radius() - constants.earth_radius).0
     );
 
     // Velocity should be positive
@@ -97,24 +156,20 @@ pub fn assert_orbital_valid(state: &OrbitalState, constants: &Constants) {
 /// Run physics calculations manually for testing
 /// This simulates a single physics step without Bevy ECS
 pub fn run_physics_step(state: &mut OrbitalState, constants: &Constants, dt: f64) {
-    use bevy::prelude::*;
-
-    // Work with f64 precision for physics calculations
-    let pos_x = state.position.x as f64;
-    let pos_y = state.position.y as f64;
-    let pos_z = state.position.z as f64;
+    let pos_x = state.position.x;
+    let pos_y = state.position.y;
+    let pos_z = state.position.z;
 
-    let vel_x = state.velocity.x as f64;
-    let vel_y = state.velocity.y as f64;
-    let vel_z = state.velocity.z as f64;
+    let vel_x = state.velocity.x;
+    let vel_y = state.velocity.y;
+    let vel_z = state.velocity.z;
 
     // Calculate gravitational acceleration: a = -GM * r / |r|³
     let r_magnitude_km = (pos_x * pos_x + pos_y * pos_y + pos_z * pos_z).sqrt();
     let r_magnitude_m = r_magnitude_km * 1000.0; // Convert km to m
 
     if r_magnitude_m > 0.0 {
-        let gm = constants.gravitational_parameter;
-        let acc_magnitude = -gm / (r_magnitude_m * r_magnitude_m);
+        let acc_magnitude = -constants.gravity_acceleration(Km(r_magnitude_km)).0; // m/s²
 
         // Unit vector components
         let r_unit_x = pos_x / r_magnitude_km;
@@ -137,8 +192,8 @@ pub fn run_physics_step(state: &mut OrbitalState, constants: &Constants, dt: f64
         let new_pos_z = pos_z + new_vel_z * dt;
 
         // Update orbital state
-        state.velocity = Vec3::new(new_vel_x as f32, new_vel_y as f32, new_vel_z as f32);
-        state.position = Vec3::new(new_pos_x as f32, new_pos_y as f32, new_pos_z as f32);
+        state.velocity = DVec3::new(new_vel_x, new_vel_y, new_vel_z);
+        state.position = DVec3::new(new_pos_x, new_pos_y, new_pos_z);
     }
 }
 
@@ -193,7 +248,7 @@ pub fn create_hubble_tle() -> TleRecord {
 /// Calculate expected orbital period from semi-major axis
 pub fn calculate_orbital_period(semi_major_axis_km: f64) -> f64 {
     let constants = Constants::default();
-    let mu = constants.gravitational_parameter / 1e9; // Convert to km³/s²
+    let mu = constants.gravitational_parameter.0;
     let a = semi_major_axis_km;
     2.0 * std::f64::consts::PI * (a * a * a / mu).sqrt()
 }
@@ -201,7 +256,7 @@ pub fn calculate_orbital_period(semi_major_axis_km: f64) -> f64 {
 /// Calculate semi-major axis from orbital period
 pub fn calculate_semi_major_axis(period_seconds: f64) -> f64 {
     let constants = Constants::default();
-    let mu = constants.gravitational_parameter / 1e9; // Convert to km³/s²
+    let mu = constants.gravitational_parameter.0;
     (mu * period_seconds * period_seconds / (4.0 * std::f64::consts::PI * std::f64::consts::PI))
         .powf(1.0 / 3.0)
 }
@@ -0,0 +1,215 @@
+// Unit tests for the Walker constellation generator
+// Tests delta and star geometry, shell validation, multi-shell definitions, slots and missions of spawned satellites and spawning from the stress test
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use std::f64::consts::TAU;
+
+/// Smallest angle between two angles in radians
+fn angle_difference(a: f64, b: f64) -> f64 {
+    let difference = (b - a).rem_euclid(TAU);
+    difference.min(TAU - difference)
+}
+
+#[test]
+fn test_walker_delta_geometry() {
+    // Galileo: 56°: 24/3/1 at 23222 km
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let shell = WalkerConstellation::delta("Galileo", 24, 3, 1, 23222.0, 56.0);
+    let elements = shell.elements(&constants);
+    assert_eq!(elements.len(), 24);
+    assert_eq!(shell.satellites_per_plane(), 8);
+
+    for (index, element) in elements.iter().enumerate() {
+        let (plane, slot) = (index / 8, index % 8);
+        assert_relative_eq!(element.inclination, 56.0_f64.to_radians());
+        assert_relative_eq!(element.raan, plane as f64 * TAU / 3.0, epsilon = 1e-12);
+
+        // Round trip through the state: circular at the shell altitude
+        let (position, velocity) = element.to_state(mu);
+        assert_relative_eq!(position.length() - constants.earth_radius.0, 23222.0, epsilon = 1e-6);
+        let recovered = KeplerianElements::from_state(position, velocity, mu);
+        assert!(recovered.eccentricity < 1e-9);
+
+        // Evenly spaced in plane, each plane a phasing step of 15° ahead of the last
+        let expected = slot as f64 * TAU / 8.0 + plane as f64 * TAU / 24.0;
+        assert!(angle_difference(expected, element.argument_of_latitude()) < 1e-9);
+    }
+}
+
+#[test]
+fn test_walker_star_spreads_half_circle() {
+    let constants = Constants::default();
+    let shell = WalkerConstellation::star("Iridium", 66, 6, 2, 780.0, 86.4);
+    let elements = shell.elements(&constants);
+
+    // Six planes 30° apart, the first and last counter-rotating across the seam
+    let mut raans: Vec<f64> = elements.iter().map(|e| e.raan).collect();
+    raans.dedup();
+    assert_eq!(raans.len(), 6);
+    for pair in raans.windows(2) {
+        assert_relative_eq!(pair[1] - pair[0], 30.0_f64.to_radians(), epsilon = 1e-12);
+    }
+    assert!(raans.iter().all(|&raan| raan < std::f64::consts::PI));
+
+    let offset = WalkerConstellation { raan_offset: 45.0, ..shell.clone() };
+    assert_relative_eq!(offset.elements(&constants)[0].raan, 45.0_f64.to_radians(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_shell_validation_and_parsing() {
+    assert!(WalkerConstellation::delta("Uneven", 25, 3, 1, 550.0, 53.0).validate().is_err());
+    assert!(WalkerConstellation::delta("Phasing", 24, 3, 3, 550.0, 53.0).validate().is_err());
+    assert!(WalkerConstellation::delta("Empty", 0, 3, 0, 550.0, 53.0).validate().is_err());
+    assert!(WalkerConstellation::delta("Underground", 24, 3, 1, -10.0, 53.0).validate().is_err());
+
+    // Presets resemble the filings and are valid Walker patterns
+    let starlink = ConstellationDefinition::starlink_like();
+    assert!(starlink.validate().is_ok());
+    assert_eq!(starlink.total(), 4408);
+    let oneweb = ConstellationDefinition::oneweb_like();
+    assert!(oneweb.validate().is_ok());
+    assert_eq!(oneweb.total(), 648);
+    assert_eq!(oneweb.shells[0].pattern, WalkerPattern::Star);
+
+    // Unspecified fields take the shell defaults
+    let json = r#"{"name": "Test", "shells": [{"name": "A", "total": 40, "planes": 5, "pattern": "Star"}]}"#;
+    let definition = parse_constellation(json).unwrap();
+    assert_eq!(definition.shells[0].pattern, WalkerPattern::Star);
+    assert_eq!(definition.shells[0].altitude, WalkerConstellation::default().altitude);
+    assert!(parse_constellation(r#"{"name": "Bad", "shells": [{"total": 7, "planes": 2}]}"#).is_err());
+    assert!(load_constellation(std::path::Path::new("missing_constellation.json")).is_err());
+
+    let example = load_constellation(std::path::Path::new("assets/constellations/example_constellation.json")).unwrap();
+    assert_eq!(example.shells.len(), 3);
+    assert_eq!(example.total(), 1584 + 648 + 24);
+}
+
+#[test]
+fn test_spawn_constellation() {
+    let definition = ConstellationDefinition {
+        name: "Test".to_string(),
+        shells: vec![
+            WalkerConstellation::delta("Low", 40, 5, 2, 550.0, 53.0),
+            WalkerConstellation::star("Polar", 12, 3, 1, 1200.0, 87.9).with_mass(150.0),
+        ],
+    };
+    let mut world = World::new();
+    world.insert_resource(Constants::default());
+    world.init_resource::<CatalogNumbers>();
+    let spawned = world
+        .run_system_once(
+            move |mut commands: Commands, mut catalog: ResMut<CatalogNumbers>, constants: Res<Constants>| {
+                spawn_constellation(&mut commands, &definition, &mut catalog, 100.0, &constants)
+            },
+        )
+        .unwrap();
+    assert_eq!(spawned.len(), 52);
+
+    let constants = Constants::default();
+    let mut satellites = world.query::<(&Satellite, &OrbitalState, &PhysicsObject, &ObjectClass)>();
+    let mut catalog_numbers = Vec::new();
+    for (satellite, state, _, class) in satellites.iter(&world) {
+        assert!(satellite.active);
+        assert_eq!(*class, ObjectClass::Spacecraft);
+        let altitude = (state.radius() - constants.earth_radius).0;
+        if satellite.name.starts_with("Polar") {
+            assert_relative_eq!(altitude, 1200.0, epsilon = 1e-6);
+            assert_eq!(state.mass, Kg(150.0));
+        } else {
+            assert_relative_eq!(altitude, 550.0, epsilon = 1e-6);
+        }
+        catalog_numbers.push(satellite.norad_id);
+    }
+    catalog_numbers.sort();
+    let expected: Vec<u32> = (GENERATED_CATALOG_START..GENERATED_CATALOG_START + 52).collect();
+    assert_eq!(catalog_numbers, expected);
+    assert_eq!(world.resource::<CatalogNumbers>().next(), GENERATED_CATALOG_START + 52);
+    assert_eq!(world.get::<Satellite>(spawned[9]).unwrap().name, "Low 2-2");
+
+    // Each satellite starts its mission on deployment and holds the slot it was spawned in
+    let mut keepers = world.query::<(&OrbitalState, &Mission, &StationKeeping, &ManeuverPlan)>();
+    let propagator = AnalyticalPropagator::new(&constants, GravityModel::J2, None);
+    for (state, mission, station_keeping, plan) in keepers.iter(&world) {
+        assert!(mission.is_operational());
+        assert_relative_eq!(plan.propellant, PROPELLANT_FRACTION * state.mass.0);
+        assert_eq!(mission.start, 100.0);
+        assert_eq!(mission.end_of_life(), 100.0 + 5.0 * SECONDS_PER_YEAR);
+        let StationKeepingBox::Constellation { semi_major_axis, latitude_argument, epoch, .. } = station_keeping.station
+        else {
+            panic!("expected a constellation slot, got {:?}", station_keeping.station);
+        };
+        assert_eq!(epoch, 100.0);
+        assert_relative_eq!(semi_major_axis, propagator.mean_semi_major_axis(state.position, state.velocity));
+        let elements = KeplerianElements::from_state(state.position, state.velocity, propagator.mu);
+        assert!(angle_difference(latitude_argument, elements.argument_of_latitude()) < 1e-9);
+    }
+    assert_eq!(keepers.iter(&world).count(), 52);
+
+    // No two satellites share a position
+    let positions: Vec<_> = satellites.iter(&world).map(|(_, state, _, _)| state.position).collect();
+    for (i, a) in positions.iter().enumerate() {
+        for b in &positions[i + 1..] {
+            assert!(a.distance(*b) > 100.0);
+        }
+    }
+}
+
+#[test]
+fn test_stress_test_spawns_constellation() {
+    let mut world = World::new();
+    world.insert_resource(Constants::default());
+    world.insert_resource(SimulationTime::default());
+    world.init_resource::<CatalogNumbers>();
+    world.insert_resource(ButtonInput::<KeyCode>::default());
+    world.insert_resource(StressTestConfig {
+        constellation: Some(ConstellationDefinition::oneweb_like()),
+        ..Default::default()
+    });
+    let spawn = world.register_system(stress_test_spawn_system);
+    world.run_system(spawn).unwrap();
+
+    // The constellation replaces random placement, so later frames add nothing
+    let config = world.resource::<StressTestConfig>();
+    assert!(config.constellation.is_none());
+    assert_eq!(config.target_leo, 648);
+    assert_eq!(config.target_objects, 648);
+    world.run_system(spawn).unwrap();
+    assert_eq!(world.query::<&StressTestObject>().iter(&world).count(), 648);
+    assert_eq!(world.query::<&Satellite>().iter(&world).count(), 648);
+}
+
+#[test]
+fn test_stress_test_counts_constellation_by_orbit() {
+    let mut world = World::new();
+    world.insert_resource(Constants::default());
+    world.insert_resource(SimulationTime::default());
+    world.init_resource::<CatalogNumbers>();
+    world.insert_resource(ButtonInput::<KeyCode>::default());
+    let definition = ConstellationDefinition {
+        name: "Mixed".to_string(),
+        shells: vec![
+            WalkerConstellation::delta("Low", 40, 5, 2, 550.0, 53.0),
+            WalkerConstellation::delta("Galileo", 24, 3, 1, 23222.0, 56.0),
+        ],
+    };
+    world.insert_resource(StressTestConfig { constellation: Some(definition), ..Default::default() });
+    world.run_system_once(stress_test_spawn_system).unwrap();
+
+    let config = world.resource::<StressTestConfig>();
+    assert_eq!((config.target_leo, config.target_meo, config.target_geo), (40, 24, 0));
+    assert_eq!(config.target_objects, 64);
+    let mut objects = world.query::<(&StressTestObject, &Satellite)>();
+    let meo = objects
+        .iter(&world)
+        .filter(|(object, _)| matches!(object.orbit_type, OrbitType::MEO))
+        .inspect(|(_, satellite)| assert!(satellite.name.starts_with("Galileo")))
+        .count();
+    assert_eq!(meo, 24);
+}
@@ -0,0 +1,182 @@
+// Unit tests for atmospheric density models and drag
+// Tests the exponential table, F10.7/Ap response, drag direction and drag-driven orbital decay
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+use common::*;
+
+fn solar_minimum() -> SpaceWeather {
+    SpaceWeather { f107: 70.0, f107_average: 70.0, ap: 4.0 }
+}
+
+fn solar_maximum() -> SpaceWeather {
+    SpaceWeather { f107: 250.0, f107_average: 250.0, ap: 50.0 }
+}
+
+/// Semi-major axis in km from vis-viva
+fn semi_major_axis(state: &OrbitalState, mu: f64) -> f64 {
+    let r = state.position.length();
+    let v2 = state.velocity.length_squared();
+    1.0 / (2.0 / r - v2 / mu)
+}
+
+#[test]
+fn test_exponential_density_matches_table() {
+    let model = AtmosphereModel::Exponential;
+
+    assert_relative_eq!(model.density(0.0), 1.225, max_relative = 1e-12);
+    assert_relative_eq!(model.density(400.0), 3.725e-12, max_relative = 1e-12);
+
+    // Within a band density falls off with the band's scale height
+    assert_relative_eq!(
+        model.density(420.0),
+        3.725e-12 * (-20.0_f64 / 58.515).exp(),
+        max_relative = 1e-12
+    );
+
+    // Monotonically decreasing with altitude
+    let mut previous = f64::MAX;
+    for altitude in (0..1500).step_by(10) {
+        let density = model.density(altitude as f64);
+        assert!(density < previous, "Density not decreasing at {} km", altitude);
+        previous = density;
+    }
+}
+
+#[test]
+fn test_jacchia_matches_table_at_lower_boundary() {
+    // Continuous with the exponential table where the thermosphere model takes over
+    let model = AtmosphereModel::Jacchia(SpaceWeather::default());
+    assert_relative_eq!(model.density(120.0), 2.438e-8, max_relative = 0.01);
+    assert_eq!(model.density(100.0), AtmosphereModel::Exponential.density(100.0));
+
+    // Moderate activity stays close to the CIRA-72 based table
+    for altitude in [200.0, 300.0, 400.0, 500.0, 800.0] {
+        let ratio = model.density(altitude) / AtmosphereModel::Exponential.density(altitude);
+        assert!(ratio > 0.5 && ratio < 2.0, "Ratio {:.2} at {} km", ratio, altitude);
+    }
+}
+
+#[test]
+fn test_jacchia_responds_to_solar_activity() {
+    let minimum = AtmosphereModel::Jacchia(solar_minimum());
+    let maximum = AtmosphereModel::Jacchia(solar_maximum());
+
+    assert!(solar_maximum().exospheric_temperature() > solar_minimum().exospheric_temperature());
+
+    // Solar maximum heats and expands the thermosphere, raising density by an order of magnitude at 400 km
+    let ratio = maximum.density(400.0) / minimum.density(400.0);
+    assert!(ratio > 5.0 && ratio < 30.0, "Solar max/min density ratio {:.1}", ratio);
+
+    // The effect grows with altitude
+    let high_ratio = maximum.density(800.0) / minimum.density(800.0);
+    assert!(high_ratio > ratio);
+}
+
+#[test]
+fn test_drag_opposes_relative_velocity() {
+    let constants = Constants::default();
+    let forces = ForceModel::new(&constants, GravityModel::PointMass, Some(AtmosphereModel::Exponential));
+    let gravity_only = ForceModel::new(&constants, GravityModel::PointMass, None);
+
+    let position = DVec3::new(constants.earth_radius.0 + 300.0, 0.0, 0.0);
+    let velocity = DVec3::new(0.0, 7.7, 0.0);
+
+    let drag = forces.acceleration_f64(position, velocity, ForceCoefficients::drag_only(0.02))
+        - gravity_only.acceleration_f64(position, velocity, ForceCoefficients::drag_only(0.02));
+
+    // Relative to the co-rotating atmosphere the object moves slightly slower than inertially
+    let relative_speed = 7.7 - constants.earth_rotation_rate * position.x;
+    let expected = 0.5 * AtmosphereModel::Exponential.density(300.0) * 0.02 * relative_speed * relative_speed * 1e3;
+
+    assert!(drag.y < 0.0);
+    assert_relative_eq!(drag.y, -expected, max_relative = 1e-9);
+    assert_relative_eq!(drag.x, 0.0);
+
+    // Zero ballistic coefficient switches drag off
+    assert_eq!(
+        forces.acceleration_f64(position, velocity, ForceCoefficients::default()),
+        gravity_only.acceleration_f64(position, velocity, ForceCoefficients::default())
+    );
+}
+
+#[test]
+fn test_ballistic_coefficient_from_bstar() {
+    // ISS-like BSTAR of 3.5e-4 / earth radii
+    let physics_object = PhysicsObject::from_bstar(3.5e-4, Kg(420000.0));
+    assert_relative_eq!(
+        physics_object.ballistic_coefficient(Kg(420000.0)),
+        2.0 * 3.5e-4 / BSTAR_REFERENCE_DENSITY,
+        max_relative = 1e-12
+    );
+
+    // Non-positive BSTAR keeps the mass-based estimate
+    let fallback = PhysicsObject::from_bstar(-1e-5, Kg(1000.0));
+    let estimate = PhysicsObject::satellite(Kg(1000.0));
+    assert_eq!(fallback.cross_section, estimate.cross_section);
+}
+
+#[test]
+fn test_drag_decays_circular_orbit_at_expected_rate() {
+    // Circular orbit decay per revolution: Δa ≈ -2π ρ (Cd·A/m) a²
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let forces = ForceModel::new(&constants, GravityModel::PointMass, Some(AtmosphereModel::Exponential));
+    let ballistic_coefficient = 0.022; // 1 m², Cd 2.2, 100 kg
+
+    let mut state = create_test_orbital_state(300.0);
+    let initial_a = semi_major_axis(&state, mu);
+
+    let dt = 10.0;
+    let steps = 8640; // One day
+    for _ in 0..steps {
+        let (p, v) = RK4Integrator.integrate(state.position, state.velocity, dt, &forces, ForceCoefficients::drag_only(ballistic_coefficient));
+        state.position = p;
+        state.velocity = v;
+    }
+
+    let decay_km = initial_a - semi_major_axis(&state, mu);
+
+    let density = AtmosphereModel::Exponential.density(300.0);
+    let a_m = initial_a * 1000.0;
+    let period = 2.0 * std::f64::consts::PI * (initial_a.powi(3) / mu).sqrt();
+    let revolutions = dt * steps as f64 / period;
+    let expected_km = 2.0 * std::f64::consts::PI * density * ballistic_coefficient * a_m * a_m * revolutions / 1000.0;
+
+    assert!(expected_km > 1.0, "Expected decay too small to test: {:.3} km", expected_km);
+    assert_relative_eq!(decay_km, expected_km, max_relative = 0.15);
+}
+
+#[test]
+fn test_drag_applied_by_euler_integrator() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let vacuum = ForceModel::new(&constants, GravityModel::PointMass, None);
+    let atmosphere = ForceModel::new(&constants, GravityModel::PointMass, Some(AtmosphereModel::Exponential));
+
+    let mut free = create_test_orbital_state(250.0);
+    let mut dragged = create_test_orbital_state(250.0);
+
+    for _ in 0..5400 {
+        let (p, v) = EulerIntegrator.integrate(free.position, free.velocity, 1.0, &vacuum, ForceCoefficients::drag_only(0.022));
+        free.position = p;
+        free.velocity = v;
+
+        let (p, v) = EulerIntegrator.integrate(dragged.position, dragged.velocity, 1.0, &atmosphere, ForceCoefficients::drag_only(0.022));
+        dragged.position = p;
+        dragged.velocity = v;
+    }
+
+    assert!(semi_major_axis(&dragged, mu) < semi_major_axis(&free, mu) - 0.1);
+}
+
+#[test]
+fn test_integrator_config_enables_drag_by_default() {
+    let config = IntegratorConfig::default();
+    assert_eq!(config.atmosphere_model, Some(AtmosphereModel::Exponential));
+    assert!(config.force_model(&Constants::default(), &Epoch::j2000()).drag.is_some());
+}
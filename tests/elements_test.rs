@@ -0,0 +1,188 @@
+// Unit tests for orbital elements
+// Tests Cartesian round trips, circular/equatorial conventions, equinoctial elements and the derived component
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+fn mu() -> f64 {
+    Constants::default().gravitational_parameter.0
+}
+
+fn assert_states_close(a: (DVec3, DVec3), b: (DVec3, DVec3)) {
+    assert!((a.0 - b.0).length() < 1e-6, "Positions {:?} vs {:?}", a.0, b.0);
+    assert!((a.1 - b.1).length() < 1e-9, "Velocities {:?} vs {:?}", a.1, b.1);
+}
+
+fn sample_elements() -> Vec<KeplerianElements> {
+    let mut samples = Vec::new();
+    for &(a, e) in &[(6778.0, 0.0005), (26560.0, 0.01), (24400.0, 0.73), (-20000.0, 1.4)] {
+        for &i in &[0.3, 0.9, FRAC_PI_2, 2.5] {
+            for &(raan, w, nu) in &[(0.1, 0.2, 0.3_f64), (4.0, 5.5, 1.0), (2.0, 0.7, 6.0)] {
+                // Hyperbolic true anomaly must stay inside the asymptotes
+                let nu = if e > 1.0 { nu.min(1.5) } else { nu };
+                samples.push(KeplerianElements {
+                    semi_major_axis: a,
+                    eccentricity: e,
+                    inclination: i,
+                    raan,
+                    arg_perigee: w,
+                    true_anomaly: nu,
+                });
+            }
+        }
+    }
+    samples
+}
+
+#[test]
+fn test_keplerian_round_trip() {
+    for elements in sample_elements() {
+        let state = elements.to_state(mu());
+        let recovered = KeplerianElements::from_state(state.0, state.1, mu());
+
+        assert_relative_eq!(recovered.semi_major_axis, elements.semi_major_axis, max_relative = 1e-10);
+        assert_relative_eq!(recovered.eccentricity, elements.eccentricity, epsilon = 1e-10);
+        assert_relative_eq!(recovered.inclination, elements.inclination, epsilon = 1e-10);
+        assert_relative_eq!(recovered.raan, elements.raan, epsilon = 1e-8);
+        assert_relative_eq!(recovered.arg_perigee, elements.arg_perigee, epsilon = 1e-6);
+        assert_relative_eq!(recovered.true_anomaly, elements.true_anomaly, epsilon = 1e-6);
+        assert_states_close(recovered.to_state(mu()), state);
+    }
+}
+
+#[test]
+fn test_circular_equatorial_orbit() {
+    let state = create_test_orbital_state(400.0);
+    let elements = KeplerianElements::from_state(state.position, state.velocity, mu());
+
+    assert_relative_eq!(elements.semi_major_axis, Constants::default().earth_radius.0 + 400.0, max_relative = 1e-12);
+    assert!(elements.eccentricity < 1e-12);
+    assert_eq!(elements.inclination, 0.0);
+    assert_eq!(elements.raan, 0.0);
+    assert_eq!(elements.arg_perigee, 0.0);
+    // True longitude of a point on the X axis
+    assert_relative_eq!(elements.true_anomaly.sin(), 0.0, epsilon = 1e-12);
+    assert_states_close(elements.to_state(mu()), (state.position, state.velocity));
+}
+
+#[test]
+fn test_circular_inclined_uses_argument_of_latitude() {
+    let elements = KeplerianElements {
+        semi_major_axis: 7000.0,
+        eccentricity: 0.0,
+        inclination: 0.9,
+        raan: 1.2,
+        arg_perigee: 0.0,
+        true_anomaly: 2.0,
+    };
+    let (position, velocity) = elements.to_state(mu());
+    let recovered = KeplerianElements::from_state(position, velocity, mu());
+
+    assert_eq!(recovered.arg_perigee, 0.0);
+    assert_relative_eq!(recovered.raan, 1.2, epsilon = 1e-12);
+    assert_relative_eq!(recovered.true_anomaly, 2.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_equatorial_uses_longitude_of_perigee() {
+    for inclination in [0.0, PI] {
+        let elements = KeplerianElements {
+            semi_major_axis: 12000.0,
+            eccentricity: 0.3,
+            inclination,
+            raan: 0.0,
+            arg_perigee: 1.0,
+            true_anomaly: 0.5,
+        };
+        let (position, velocity) = elements.to_state(mu());
+        let recovered = KeplerianElements::from_state(position, velocity, mu());
+
+        assert_eq!(recovered.raan, 0.0);
+        assert_relative_eq!(recovered.arg_perigee, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(recovered.true_anomaly, 0.5, epsilon = 1e-12);
+        assert_states_close(recovered.to_state(mu()), (position, velocity));
+    }
+}
+
+#[test]
+fn test_derived_quantities() {
+    // GPS-like orbit: 12 sidereal hours
+    let elements = KeplerianElements {
+        semi_major_axis: 26561.75,
+        eccentricity: 0.01,
+        inclination: 55.0_f64.to_radians(),
+        raan: 0.0,
+        arg_perigee: 0.0,
+        true_anomaly: PI,
+    };
+    assert_relative_eq!(elements.period(mu()).unwrap(), 43082.0, max_relative = 1e-3);
+    assert_relative_eq!(elements.perigee_radius(), 26561.75 * 0.99, max_relative = 1e-12);
+    assert_relative_eq!(elements.apogee_radius().unwrap(), 26561.75 * 1.01, max_relative = 1e-12);
+    assert_relative_eq!(elements.mean_anomaly(), PI, epsilon = 1e-12);
+
+    let escape = KeplerianElements { semi_major_axis: -20000.0, eccentricity: 1.4, ..elements };
+    assert!(escape.period(mu()).is_none());
+    assert!(escape.apogee_radius().is_none());
+}
+
+#[test]
+fn test_equinoctial_round_trips() {
+    for elements in sample_elements().into_iter().filter(KeplerianElements::is_bound) {
+        let state = elements.to_state(mu());
+        let equinoctial = EquinoctialElements::from_state(state.0, state.1, mu());
+
+        assert_states_close(equinoctial.to_state(mu()), state);
+        assert_states_close(equinoctial.to_keplerian().to_state(mu()), state);
+
+        let converted = elements.to_equinoctial();
+        assert_relative_eq!(converted.semi_latus_rectum, equinoctial.semi_latus_rectum, max_relative = 1e-10);
+        assert_relative_eq!(converted.f, equinoctial.f, epsilon = 1e-10);
+        assert_relative_eq!(converted.g, equinoctial.g, epsilon = 1e-10);
+        assert_relative_eq!(converted.h, equinoctial.h, epsilon = 1e-10);
+        assert_relative_eq!(converted.k, equinoctial.k, epsilon = 1e-10);
+    }
+}
+
+#[test]
+fn test_equinoctial_is_smooth_through_circular_equatorial() {
+    // Classical angles are undefined here, but the equinoctial elements stay well behaved
+    let state = create_test_orbital_state(800.0);
+    let equinoctial = EquinoctialElements::from_state(state.position, state.velocity, mu());
+
+    assert!(equinoctial.f.abs() < 1e-12 && equinoctial.g.abs() < 1e-12);
+    assert_eq!((equinoctial.h, equinoctial.k), (0.0, 0.0));
+    assert_states_close(equinoctial.to_state(mu()), (state.position, state.velocity));
+}
+
+#[test]
+fn test_orbital_elements_system_tracks_state() {
+    let mut world = World::new();
+    world.insert_resource(Constants::default());
+
+    let state = create_test_orbital_state(400.0);
+    let stale = OrbitalElements::from_state(&create_test_orbital_state(1000.0), mu());
+    let tracked = world.spawn((state, stale)).id();
+    let untracked = world.spawn(create_test_orbital_state(400.0)).id();
+
+    world.run_system_once(orbital_elements_system).unwrap();
+
+    let OrbitalElements(elements) = *world.get::<OrbitalElements>(tracked).unwrap();
+    assert_relative_eq!(elements.semi_major_axis, Constants::default().earth_radius.0 + 400.0, max_relative = 1e-12);
+    assert!(world.get::<OrbitalElements>(untracked).is_none());
+
+    // Raising the velocity is reflected on the next run
+    world.get_mut::<OrbitalState>(tracked).unwrap().velocity *= 1.01;
+    world.run_system_once(orbital_elements_system).unwrap();
+    let OrbitalElements(raised) = *world.get::<OrbitalElements>(tracked).unwrap();
+    assert!(raised.semi_major_axis > elements.semi_major_axis + 100.0);
+    assert!(raised.eccentricity > 0.01);
+}
@@ -0,0 +1,174 @@
+// Unit tests for explosions and spontaneous fragmentation
+// Tests the explosion branch of the breakup model, per-class rates, passivation and the explosion system
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn rocket_body() -> BreakupParent {
+    BreakupParent {
+        mass: 1400.0,
+        velocity: DVec3::new(0.0, 7.6, 0.0),
+        characteristic_length: 4.0,
+        class: ObjectClass::RocketBody,
+    }
+}
+
+#[test]
+fn test_explosion_fragment_count_power_law() {
+    // N(Lc) = 6 S Lc^-1.6
+    assert_relative_eq!(explosion_fragment_count(1.0, 1.0), 6.0);
+    assert_relative_eq!(explosion_fragment_count(0.5, 0.1), 3.0 * 0.1_f64.powf(-1.6), max_relative = 1e-12);
+}
+
+#[test]
+fn test_explosion_breakup_fragments_whole_object() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let config = BreakupConfig::default();
+    let parent = rocket_body();
+
+    let result = explosion_breakup(&config, &parent, 1.0, &mut rng);
+
+    assert!(result.catastrophic);
+    assert_eq!(result.remnant_mass, 0.0);
+    assert_relative_eq!(result.total_mass(), parent.mass, max_relative = 1e-9);
+
+    let expected = explosion_fragment_count(1.0, 0.1) - explosion_fragment_count(1.0, 4.0);
+    assert!(result.fragments.len() as f64 > 0.5 * expected);
+    assert!(result.fragments.len() as f64 <= expected + 1.0);
+
+    // Fragments spread around the parent velocity at explosion speeds, far below collision ejecta
+    let momentum = parent_momentum(&[&parent]);
+    assert!((result.total_momentum() - momentum).length() < 1e-4 * momentum.length());
+    let mean_speed = result
+        .fragments
+        .iter()
+        .map(|f| (f.velocity - parent.velocity).length())
+        .sum::<f64>()
+        / result.fragments.len() as f64;
+    assert!(mean_speed < 0.5, "Mean ejection speed {:.3} km/s", mean_speed);
+}
+
+#[test]
+fn test_explosion_probability_by_class() {
+    let config = ExplosionConfig::default();
+
+    // Over one year the probability is 1 - exp(-rate)
+    assert_relative_eq!(
+        config.explosion_probability(ObjectClass::RocketBody, SECONDS_PER_YEAR),
+        1.0 - (-config.rocket_body_rate).exp(),
+        max_relative = 1e-12
+    );
+    assert!(
+        config.explosion_probability(ObjectClass::RocketBody, 3600.0)
+            > config.explosion_probability(ObjectClass::Spacecraft, 3600.0)
+    );
+    assert_eq!(config.explosion_probability(ObjectClass::Debris, 3600.0), 0.0);
+    assert_eq!(config.explosion_probability(ObjectClass::RocketBody, 0.0), 0.0);
+}
+
+#[test]
+fn test_object_class_from_catalog_name() {
+    assert_eq!(ObjectClass::from_catalog_name("SL-16 R/B"), ObjectClass::RocketBody);
+    assert_eq!(ObjectClass::from_catalog_name("COSMOS 2251 DEB"), ObjectClass::Debris);
+    assert_eq!(ObjectClass::from_catalog_name("ISS (ZARYA)"), ObjectClass::Spacecraft);
+}
+
+fn explosion_world() -> World {
+    let mut world = simulation_test_world(1000.0);
+    world.insert_resource(ExplosionConfig {
+        rocket_body_rate: 0.0,
+        spacecraft_rate: 0.0,
+        ..Default::default()
+    });
+    world.init_resource::<BreakupConfig>();
+    world.init_resource::<BreakupStatistics>();
+    world.init_resource::<Events<ExplosionEvent>>();
+    world
+}
+
+#[test]
+fn test_scheduled_explosion_fragments_object() {
+    let mut world = explosion_world();
+
+    let stage = world
+        .spawn((
+            create_test_orbital_state(800.0),
+            PhysicsObject::new(10.0, 2.2, Meters(2.0)),
+            ObjectClass::RocketBody,
+            ScheduledExplosion { time: 500.0 },
+        ))
+        .id();
+    let later = world
+        .spawn((
+            create_test_orbital_state(800.0),
+            PhysicsObject::new(10.0, 2.2, Meters(2.0)),
+            ObjectClass::RocketBody,
+            ScheduledExplosion { time: 5000.0 },
+        ))
+        .id();
+
+    world.run_system_once(explosion_system).unwrap();
+
+    assert!(world.get_entity(stage).is_err());
+    assert!(world.get_entity(later).is_ok());
+
+    let events = world.resource::<Events<ExplosionEvent>>();
+    let event = events.iter_current_update_events().next().unwrap().clone();
+    assert_eq!(event.entity, stage);
+    assert_eq!(event.trigger, ExplosionTrigger::Scheduled);
+    assert_eq!(event.class, ObjectClass::RocketBody);
+
+    let debris = world.query::<&Debris>().iter(&world).count();
+    assert_eq!(debris, event.fragments);
+    assert!(debris > 0);
+    assert_eq!(world.resource::<BreakupStatistics>().breakups, 1);
+}
+
+#[test]
+fn test_passivated_objects_never_explode() {
+    let mut world = explosion_world();
+    world.insert_resource(ExplosionConfig {
+        rocket_body_rate: 1.0e12, // Certain to explode within the step unless passivated
+        ..Default::default()
+    });
+
+    let passivated = world
+        .spawn((
+            create_test_orbital_state(800.0),
+            PhysicsObject::new(10.0, 2.2, Meters(2.0)),
+            ObjectClass::RocketBody,
+            ScheduledExplosion { time: 0.0 },
+            Passivated,
+        ))
+        .id();
+    let active = world
+        .spawn((
+            create_test_orbital_state(800.0),
+            PhysicsObject::new(10.0, 2.2, Meters(2.0)),
+            ObjectClass::RocketBody,
+        ))
+        .id();
+
+    // The first run only records the time, the second sees a 60 s step
+    let system = world.register_system(explosion_system);
+    world.run_system(system).unwrap();
+    world.resource_mut::<SimulationTime>().current += 60.0;
+    world.run_system(system).unwrap();
+
+    assert!(world.get_entity(passivated).is_ok());
+    assert!(world.get_entity(active).is_err());
+
+    let events = world.resource::<Events<ExplosionEvent>>();
+    let triggers: Vec<_> = events.iter_current_update_events().map(|e| (e.entity, e.trigger)).collect();
+    assert_eq!(triggers, vec![(active, ExplosionTrigger::Spontaneous)]);
+}
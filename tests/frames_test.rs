@@ -0,0 +1,126 @@
+// Unit tests for Earth orientation and reference frame conversions
+// Tests sidereal time, TEME/GCRF/Earth-fixed rotations against Vallado's worked examples, geodetic points and look angles
+
+use kessler_simulator::utils::*;
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+
+/// Vallado, Fundamentals of Astrodynamics, example 3-15 (2004-04-06 07:51:28.386009 UTC)
+fn vallado_epoch() -> Epoch {
+    Epoch::from_utc(2004, 4, 6, 7, 51, 28.386009)
+}
+
+const VALLADO_TEME_POSITION: DVec3 = DVec3::new(5094.18016210, 6127.64465950, 6380.34453270);
+const VALLADO_TEME_VELOCITY: DVec3 = DVec3::new(-4.746131487, 0.785818041, 5.531931288);
+
+#[test]
+fn test_gmst_matches_reference() {
+    // Vallado example 3-5: 1992-08-20 12:14 UT1
+    let epoch = Epoch::from_utc(1992, 8, 20, 12, 14, 0.0);
+    assert_relative_eq!(gmst(&epoch).to_degrees(), 152.578787886, epsilon = 1e-6);
+
+    // One solar day later the Earth has turned slightly more than once
+    let next_day = epoch.plus_seconds(86400.0);
+    let advance = (gmst(&next_day) - gmst(&epoch)).rem_euclid(std::f64::consts::TAU).to_degrees();
+    assert_relative_eq!(advance, 0.9856473, epsilon = 1e-6);
+}
+
+#[test]
+fn test_earth_rotation_angle() {
+    let j2000 = Epoch::j2000();
+    assert_relative_eq!(earth_rotation_angle(&j2000).to_degrees(), 280.46061837504, epsilon = 1e-9);
+    // At J2000 there is no accumulated precession, so ERA and GMST agree
+    assert_relative_eq!(earth_rotation_angle(&j2000), gmst(&j2000), epsilon = 1e-8);
+
+    // Twenty years on they have drifted apart by the precession in right ascension (~0.28°)
+    let later = Epoch::from_utc(2020, 1, 1, 12, 0, 0.0);
+    let drift = (gmst(&later) - earth_rotation_angle(&later)).to_degrees();
+    assert!(drift > 0.25 && drift < 0.3, "drift {}", drift);
+}
+
+#[test]
+fn test_teme_to_gcrf_matches_reference() {
+    let (position, velocity) = teme_to_gcrf(VALLADO_TEME_POSITION, VALLADO_TEME_VELOCITY, &vallado_epoch());
+
+    // The truncated nutation series is good to a few tens of metres here
+    let expected_position = DVec3::new(5102.508958, 6123.011401, 6378.136928);
+    let expected_velocity = DVec3::new(-4.74322016, 0.79053650, 5.53375528);
+    assert!((position - expected_position).length() < 0.05, "position {:?}", position);
+    assert!((velocity - expected_velocity).length() < 5e-5, "velocity {:?}", velocity);
+
+    // The rotation preserves lengths and undoes cleanly
+    assert_relative_eq!(position.length(), VALLADO_TEME_POSITION.length(), epsilon = 1e-9);
+    let (back, back_velocity) = gcrf_to_teme(position, velocity, &vallado_epoch());
+    assert!((back - VALLADO_TEME_POSITION).length() < 1e-9);
+    assert!((back_velocity - VALLADO_TEME_VELOCITY).length() < 1e-12);
+}
+
+#[test]
+fn test_eci_to_ecef_matches_reference() {
+    // Shift by the example's UT1−UTC so the Earth angle matches; polar motion is left out
+    let epoch = Epoch::from_utc(2004, 4, 6, 7, 51, 28.386009 - 0.4399619);
+    let (position, velocity) = teme_to_gcrf(VALLADO_TEME_POSITION, VALLADO_TEME_VELOCITY, &epoch);
+    let (earth_fixed, ground_velocity) = eci_to_ecef_state(position, velocity, &epoch);
+
+    let expected_position = DVec3::new(-1033.4793830, 7901.2952754, 6380.3565958);
+    let expected_velocity = DVec3::new(-3.225636520, -2.872451450, 5.531924446);
+    assert!((earth_fixed - expected_position).length() < 0.05, "position {:?}", earth_fixed);
+    assert!((ground_velocity - expected_velocity).length() < 1e-4, "velocity {:?}", ground_velocity);
+
+    let (inertial, inertial_velocity) = ecef_to_eci_state(earth_fixed, ground_velocity, &epoch);
+    assert!((inertial - position).length() < 1e-9);
+    assert!((inertial_velocity - velocity).length() < 1e-12);
+    assert!((ecef_to_eci(eci_to_ecef(position, &epoch), &epoch) - position).length() < 1e-9);
+}
+
+#[test]
+fn test_ground_fixed_point_moves_with_earth() {
+    let epoch = vallado_epoch();
+    let site = Geodetic { latitude: 0.0, longitude: 45.0, altitude: 0.0 };
+    let earth_fixed = geodetic_to_earth_fixed(&site);
+
+    // A point on the equator co-rotates at ω·R in inertial space
+    let (_, velocity) = ecef_to_eci_state(earth_fixed, DVec3::ZERO, &epoch);
+    assert_relative_eq!(velocity.length(), EARTH_ROTATION_RATE * WGS84_EQUATORIAL_RADIUS, epsilon = 1e-9);
+
+    // Its inertial position maps back to the same ground point at the same instant
+    let ground = ground_point(ecef_to_eci(earth_fixed, &epoch), &epoch);
+    assert_relative_eq!(ground.latitude, 0.0, epsilon = 1e-9);
+    assert_relative_eq!(ground.longitude, 45.0, epsilon = 1e-9);
+    assert_relative_eq!(ground.altitude, 0.0, epsilon = 1e-6);
+}
+
+#[test]
+fn test_geodetic_round_trip() {
+    for &(latitude, longitude, altitude) in &[(0.0, 0.0, 0.0), (51.5, -0.1, 0.05), (-33.9, 151.2, 550.0), (89.0, 120.0, 35786.0)] {
+        let site = Geodetic { latitude, longitude, altitude };
+        let back = earth_fixed_to_geodetic(geodetic_to_earth_fixed(&site));
+        assert_relative_eq!(back.latitude, latitude, epsilon = 1e-9);
+        assert_relative_eq!(back.longitude, longitude, epsilon = 1e-9);
+        assert_relative_eq!(back.altitude, altitude, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_look_angles() {
+    let site = Geodetic { latitude: 40.0, longitude: -105.0, altitude: 1.6 };
+
+    // Straight up along the ellipsoid normal
+    let overhead = geodetic_to_earth_fixed(&Geodetic { altitude: 500.0, ..site });
+    let angles = look_angles(&site, overhead);
+    assert_relative_eq!(angles.elevation, 90.0, epsilon = 1e-6);
+    assert_relative_eq!(angles.range, 498.4, epsilon = 1e-6);
+    assert!(angles.is_visible(10.0));
+
+    // Due east along the local horizon
+    let (sin_lon, cos_lon) = site.longitude.to_radians().sin_cos();
+    let east = geodetic_to_earth_fixed(&site) + 1000.0 * DVec3::new(-sin_lon, cos_lon, 0.0);
+    let angles = look_angles(&site, east);
+    assert_relative_eq!(angles.azimuth, 90.0, epsilon = 1e-9);
+    assert_relative_eq!(angles.elevation, 0.0, epsilon = 1e-9);
+    assert!(!angles.is_visible(10.0));
+
+    // The antipode is straight down
+    let antipode = geodetic_to_earth_fixed(&Geodetic { latitude: -40.0, longitude: 75.0, altitude: 0.0 });
+    assert!(look_angles(&site, antipode).elevation < -89.0);
+}
@@ -0,0 +1,106 @@
+// Unit tests for the zonal harmonic geopotential
+// Tests point-mass limit, consistency with the potential, and J2 nodal precession
+
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+use kessler_simulator::resources::*;
+use kessler_simulator::utils::*;
+
+#[test]
+fn test_point_mass_matches_inverse_square() {
+    let constants = Constants::default();
+    let gravity = Geopotential::new(&constants, GravityModel::PointMass);
+
+    let position = DVec3::new(7000.0, 0.0, 0.0);
+    let acc = gravity.acceleration_f64(position);
+
+    let mu = constants.gravitational_parameter.0;
+    assert_relative_eq!(acc.x, -mu / (7000.0 * 7000.0), max_relative = 1e-12);
+    assert_eq!(acc.y, 0.0);
+    assert_eq!(acc.z, 0.0);
+}
+
+#[test]
+fn test_acceleration_is_gradient_of_potential() {
+    // Each harmonic's acceleration must match a central-difference gradient of its potential
+    let constants = Constants::default();
+    let position = DVec3::new(4200.0, -3100.0, 4800.0);
+    let h = 1.0; // km, large enough that roundoff in the potential difference stays small
+
+    let models = [GravityModel::PointMass, GravityModel::J2, GravityModel::J3, GravityModel::J4];
+    for pair in models.windows(2) {
+        let lower = Geopotential::new(&constants, pair[0]);
+        let higher = Geopotential::new(&constants, pair[1]);
+
+        // Isolate the term added by the higher model
+        let term_acc = higher.acceleration_f64(position) - lower.acceleration_f64(position);
+        let term_potential = |p: DVec3| higher.potential(p) - lower.potential(p);
+
+        for axis in [DVec3::X, DVec3::Y, DVec3::Z] {
+            let numeric = (term_potential(position + axis * h) - term_potential(position - axis * h))
+                / (2.0 * h);
+            assert_relative_eq!(term_acc.dot(axis), numeric, epsilon = 1e-13, max_relative = 1e-4);
+        }
+    }
+}
+
+#[test]
+fn test_j2_perturbation_magnitude() {
+    // J2 is roughly a 1e-3 correction to central gravity in LEO
+    let constants = Constants::default();
+    let position = DVec3::new(6778.0, 0.0, 0.0);
+
+    let central = Geopotential::new(&constants, GravityModel::PointMass).acceleration_f64(position);
+    let oblate = Geopotential::new(&constants, GravityModel::J2).acceleration_f64(position);
+
+    let ratio = (oblate - central).length() / central.length();
+    assert!(ratio > 1e-3 && ratio < 2e-3, "J2/central ratio out of range: {:.3e}", ratio);
+
+    // On the equator the extra pull is toward Earth
+    assert!(oblate.x < central.x);
+}
+
+#[test]
+fn test_j2_nodal_precession_rate() {
+    // Sun-synchronous orbit: RAAN should advance ~0.9856 deg/day
+    let constants = Constants::default();
+    let forces = ForceModel::gravity_only(Geopotential::new(&constants, GravityModel::J2));
+    let mu = constants.gravitational_parameter.0;
+
+    let a = constants.earth_equatorial_radius.0 + 700.0;
+    let inclination = 98.19_f64.to_radians();
+    let speed = (mu / a).sqrt();
+
+    let mut position = DVec3::new(a, 0.0, 0.0);
+    let mut velocity = DVec3::new(0.0, speed * inclination.cos(), speed * inclination.sin());
+
+    let node = |r: DVec3, v: DVec3| -> f64 {
+        let h = r.cross(v);
+        h.x.atan2(-h.y)
+    };
+    let initial_node = node(position, velocity);
+
+    let dt = 10.0;
+    for _ in 0..8640 {
+        let (p, v) = RK4Integrator.integrate(position, velocity, dt, &forces, ForceCoefficients::default());
+        position = p;
+        velocity = v;
+    }
+
+    let drift_deg = (node(position, velocity) - initial_node).to_degrees();
+
+    let n = (mu / (a * a * a)).sqrt();
+    let expected_deg = (-1.5 * n * constants.j2 * (constants.earth_equatorial_radius.0 / a).powi(2)
+        * inclination.cos()
+        * 86400.0)
+        .to_degrees();
+
+    assert!(expected_deg > 0.9 && expected_deg < 1.1);
+    assert_relative_eq!(drift_deg, expected_deg, max_relative = 0.1);
+}
+
+#[test]
+fn test_integrator_config_defaults_to_j2() {
+    let config = IntegratorConfig::default();
+    assert_eq!(config.gravity_model, GravityModel::J2);
+}
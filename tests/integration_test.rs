@@ -1,6 +1,7 @@
 // Integration tests for full simulation cycles
 // Tests collision cascades, data loading integration, and system interactions
 
+use bevy::math::DVec3;
 use bevy::prelude::*;
 use kessler_simulator::components::*;
 use kessler_simulator::resources::*;
@@ -29,7 +30,7 @@ fn test_tle_parsing_to_satellite_spawning() {
 
     // Step 3: Verify state vectors are valid
     let constants = Constants::default();
-    let orbital_state = OrbitalState::new(position, velocity, 1000.0);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(1000.0));
     assert_orbital_valid(&orbital_state, &constants);
 }
 
@@ -43,8 +44,8 @@ fn test_physics_and_collision_integration() {
     let mut state2 = create_test_orbital_state(400.0);
 
     // Place them at same position (guaranteed collision)
-    state1.position = Vec3::new(6771.0, 0.0, 0.0);
-    state2.position = Vec3::new(6771.0, 0.0, 0.0);
+    state1.position = DVec3::new(6771.0, 0.0, 0.0);
+    state2.position = DVec3::new(6771.0, 0.0, 0.0);
 
     let initial_energy1 = state1.total_energy(constants.gravitational_parameter);
     let initial_energy2 = state2.total_energy(constants.gravitational_parameter);
@@ -77,7 +78,7 @@ fn test_collision_cascade_simulation() {
     let mut state2 = create_test_orbital_state(400.0);
 
     // Place objects at same position
-    let collision_point = Vec3::new(6771.0, 0.0, 0.0);
+    let collision_point = DVec3::new(6771.0, 0.0, 0.0);
     state1.position = collision_point;
     state2.position = collision_point;
 
@@ -90,7 +91,7 @@ fn test_collision_cascade_simulation() {
 
     // Verify that collision point is reasonable
     assert!(
-        collision_point.length() > constants.earth_radius as f32,
+        collision_point.length() > constants.earth_radius.0,
         "Collision should occur above Earth surface"
     );
 }
@@ -157,9 +158,9 @@ fn test_analytics_during_simulation() {
     let altitudes = vec![300.0, 400.0, 500.0, 600.0];
 
     for altitude in &altitudes {
-        let position = Vec3::new((6371.0 + altitude) as f32, 0.0, 0.0);
-        let velocity = Vec3::new(0.0, 7.66, 0.0);
-        let orbital_state = OrbitalState::new(position, velocity, 1000.0);
+        let position = DVec3::new(6371.0 + altitude, 0.0, 0.0);
+        let velocity = DVec3::new(0.0, 7.66, 0.0);
+        let orbital_state = OrbitalState::new(position, velocity, Kg(1000.0));
         let energy = orbital_state.total_energy(constants.gravitational_parameter);
         analytics.add_energy_measurement(*altitude, energy);
     }
@@ -193,13 +194,13 @@ fn test_end_to_end_simulation_cycle() {
     let mut state2 = create_test_orbital_state(400.0);
 
     // Step 2: Position them for collision
-    let collision_point = Vec3::new(6771.0, 0.0, 0.0);
+    let collision_point = DVec3::new(6771.0, 0.0, 0.0);
     state1.position = collision_point;
     state2.position = collision_point;
 
     // Give them different velocities for realistic collision
-    state1.velocity = Vec3::new(7.5, 0.0, 0.0); // Moving in +x
-    state2.velocity = Vec3::new(-7.5, 0.0, 0.0); // Moving in -x (head-on collision)
+    state1.velocity = DVec3::new(7.5, 0.0, 0.0); // Moving in +x
+    state2.velocity = DVec3::new(-7.5, 0.0, 0.0); // Moving in -x (head-on collision)
 
     // Step 3: Run physics (objects would move, but we're testing at collision point)
     let initial_energy = state1.total_energy(constants.gravitational_parameter)
@@ -209,7 +210,7 @@ fn test_end_to_end_simulation_cycle() {
     // For this test, we verify energy calculation
     let relative_velocity = (state2.velocity - state1.velocity).length();
     let collision_energy =
-        0.5 * (state1.mass + state2.mass) as f32 * relative_velocity * relative_velocity;
+        0.5 * (state1.mass + state2.mass).0 * relative_velocity * relative_velocity;
 
     assert!(
         collision_energy > 0.0,
@@ -243,8 +244,8 @@ fn test_debris_mechanics_integration() {
     }
 
     // Low altitude debris should have lower radius (decaying)
-    let debris_radius = debris_state.position.length() as f64;
-    let satellite_radius = satellite_state.position.length() as f64;
+    let debris_radius = debris_state.position.length();
+    let satellite_radius = satellite_state.position.length();
 
     // Both should still be valid orbital states
     assert_orbital_valid(&debris_state, &constants);
@@ -267,6 +268,6 @@ fn test_system_resources_initialization() {
     assert_eq!(collision_pairs.pairs.len(), 0);
 
     // Verify constants are correct
-    assert!(constants.earth_radius > 0.0);
-    assert!(constants.gravitational_parameter > 0.0);
+    assert!(constants.earth_radius.0 > 0.0);
+    assert!(constants.gravitational_parameter.0 > 0.0);
 }
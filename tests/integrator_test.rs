@@ -0,0 +1,220 @@
+// Unit tests for the integrators
+// Tests the adaptive Dormand-Prince step control, symplectic energy behaviour, convergence order and the integrator registry
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+fn point_mass(constants: &Constants) -> ForceModel {
+    ForceModel::gravity_only(Geopotential::new(constants, GravityModel::PointMass))
+}
+
+fn control(min_step: f64, max_step: f64) -> StepControl {
+    StepControl { min_step, max_step, tolerance: 1.0e-10 }
+}
+
+/// Apogee state of an orbit with the given perigee and apogee altitudes
+fn eccentric_orbit(constants: &Constants, perigee_altitude: f64, apogee_altitude: f64) -> (DVec3, DVec3, f64) {
+    let mu = constants.gravitational_parameter.0;
+    let r_apogee = constants.earth_radius.0 + apogee_altitude;
+    let r_perigee = constants.earth_radius.0 + perigee_altitude;
+    let a = 0.5 * (r_apogee + r_perigee);
+    let v_apogee = (mu * (2.0 / r_apogee - 1.0 / a)).sqrt();
+    let period = 2.0 * std::f64::consts::PI * (a * a * a / mu).sqrt();
+    (DVec3::new(r_apogee, 0.0, 0.0), DVec3::new(0.0, v_apogee, 0.0), period)
+}
+
+#[test]
+fn test_dormand_prince_step_matches_rk4() {
+    let constants = Constants::default();
+    let forces = point_mass(&constants);
+    let state = create_test_orbital_state(400.0);
+
+    let (dp_position, dp_velocity, error) =
+        DormandPrinceIntegrator::step(state.position, state.velocity, 10.0, &forces, ForceCoefficients::default(), 1.0e-10);
+    let (rk_position, rk_velocity) = RK4Integrator.integrate(state.position, state.velocity, 10.0, &forces, ForceCoefficients::default());
+
+    assert!(error < 1.0, "10 s LEO step should meet tolerance, error {:.3}", error);
+    assert!((dp_position - rk_position).length() < 1.0e-6);
+    assert!((dp_velocity - rk_velocity).length() < 1.0e-9);
+}
+
+#[test]
+fn test_eccentric_orbit_closes_with_smaller_steps_at_perigee() {
+    let constants = Constants::default();
+    let forces = point_mass(&constants);
+    let (position, velocity, period) = eccentric_orbit(&constants, 300.0, 20000.0);
+    let control = control(0.01, 600.0);
+
+    let result = DormandPrinceIntegrator::integrate(position, velocity, period, &forces, ForceCoefficients::default(), &control, 60.0);
+    assert!((result.position - position).length() < 1.0e-3, "Orbit failed to close within 1 m");
+    assert!(result.steps < 2000, "Took {} steps", result.steps);
+
+    // Step proposed after reaching perigee is much smaller than the one back at apogee
+    let half = DormandPrinceIntegrator::integrate(position, velocity, period / 2.0, &forces, ForceCoefficients::default(), &control, 60.0);
+    assert_relative_eq!(half.position.length(), constants.earth_radius.0 + 300.0, epsilon = 1.0e-3);
+    assert!(
+        half.next_step * 5.0 < result.next_step,
+        "Perigee step {:.1} s vs apogee step {:.1} s",
+        half.next_step,
+        result.next_step
+    );
+}
+
+#[test]
+fn test_geo_grows_to_max_step() {
+    let constants = Constants::default();
+    let forces = point_mass(&constants);
+    let state = create_test_orbital_state(35786.0);
+
+    let result =
+        DormandPrinceIntegrator::integrate(state.position, state.velocity, 3600.0, &forces, ForceCoefficients::default(), &control(0.01, 300.0), 1.0);
+
+    assert_eq!(result.next_step, 300.0);
+    assert!(result.steps < 25, "Took {} steps", result.steps);
+}
+
+#[test]
+fn test_steps_never_leave_bounds() {
+    let constants = Constants::default();
+    let forces = point_mass(&constants);
+    let state = create_test_orbital_state(400.0);
+
+    // An unattainable tolerance drives the step down to the floor, where steps are accepted
+    let strict = StepControl { min_step: 0.5, max_step: 10.0, tolerance: 1.0e-30 };
+    let result = DormandPrinceIntegrator::integrate(state.position, state.velocity, 10.0, &forces, ForceCoefficients::default(), &strict, 10.0);
+    assert_eq!(result.next_step, 0.5);
+    assert!(result.steps >= 20);
+
+    let step = strict.clamp(1.0e6);
+    assert_eq!(step, 10.0);
+}
+
+#[test]
+fn test_physics_system_uses_adaptive_steps_when_enabled() {
+    let config = IntegratorConfig {
+        adaptive_timestep: true,
+        max_timestep: 30.0,
+        gravity_model: GravityModel::PointMass,
+        atmosphere_model: None,
+        ..Default::default()
+    };
+    let mut world = physics_test_world(config, 60.0);
+    world.resource_mut::<SimulationTime>().accumulator = 60.0;
+
+    let entity = world.spawn(create_test_orbital_state(400.0)).id();
+    let initial = world.get::<OrbitalState>(entity).unwrap().position;
+
+    world.run_system_once(physics_system).unwrap();
+
+    let step = world.get::<AdaptiveStep>(entity).expect("Adaptive step should be recorded").0;
+    assert!(step > 0.01 && step <= 30.0);
+
+    // A circular LEO orbit covers ~7.67 km/s × 60 s
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    assert_relative_eq!((state.position - initial).length(), 7.67 * 60.0, max_relative = 0.01);
+    assert_relative_eq!(state.position.length(), initial.length(), max_relative = 1.0e-6);
+}
+
+/// Largest relative energy error over the first tenth and over all of `steps` fixed steps
+fn energy_error_growth(kind: IntegratorKind, steps: usize, dt: f64) -> (f64, f64) {
+    let constants = Constants::default();
+    let forces = point_mass(&constants);
+    let mu = constants.gravitational_parameter.0;
+    let energy = |p: DVec3, v: DVec3| 0.5 * v.length_squared() - mu / p.length();
+
+    let (mut position, mut velocity, _) = eccentric_orbit(&constants, 400.0, 1000.0);
+    let initial = energy(position, velocity);
+    let integrator = kind.integrator();
+
+    let (mut early, mut overall) = (0.0_f64, 0.0_f64);
+    for i in 0..steps {
+        (position, velocity) = integrator.integrate(position, velocity, dt, &forces, ForceCoefficients::default());
+        overall = overall.max(((energy(position, velocity) - initial) / initial).abs());
+        if i < steps / 10 {
+            early = overall;
+        }
+    }
+    (early, overall)
+}
+
+#[test]
+fn test_symplectic_integrators_keep_energy_bounded() {
+    // A month of 30 s steps on a 400 x 1000 km orbit
+    let steps = 86400;
+
+    for kind in [IntegratorKind::VelocityVerlet, IntegratorKind::Yoshida4] {
+        let (early, overall) = energy_error_growth(kind, steps, 30.0);
+        assert!(overall <= 1.1 * early, "{:?} energy error grew from {:.2e} to {:.2e}", kind, early, overall);
+    }
+
+    // RK4 is more accurate per step but its energy error keeps growing
+    let (early, overall) = energy_error_growth(IntegratorKind::RK4, steps, 30.0);
+    assert!(overall > 5.0 * early, "RK4 energy error {:.2e} -> {:.2e}", early, overall);
+
+    let (_, yoshida) = energy_error_growth(IntegratorKind::Yoshida4, steps, 30.0);
+    let (_, verlet) = energy_error_growth(IntegratorKind::VelocityVerlet, steps, 30.0);
+    assert!(yoshida < verlet / 100.0);
+}
+
+#[test]
+fn test_yoshida_is_fourth_order() {
+    let constants = Constants::default();
+    let forces = point_mass(&constants);
+    let (position, velocity, period) = eccentric_orbit(&constants, 400.0, 1000.0);
+
+    // Position error after one period for a given step count
+    let closure = |kind: IntegratorKind, steps: usize| {
+        let dt = period / steps as f64;
+        let (mut p, mut v) = (position, velocity);
+        for _ in 0..steps {
+            (p, v) = kind.integrator().integrate(p, v, dt, &forces, ForceCoefficients::default());
+        }
+        (p - position).length()
+    };
+
+    // Halving the step cuts the error ~16x for 4th order and ~4x for 2nd order
+    let yoshida = closure(IntegratorKind::Yoshida4, 100) / closure(IntegratorKind::Yoshida4, 200);
+    let verlet = closure(IntegratorKind::VelocityVerlet, 100) / closure(IntegratorKind::VelocityVerlet, 200);
+    assert!(yoshida > 12.0 && yoshida < 20.0, "Yoshida error ratio {:.2}", yoshida);
+    assert!(verlet > 3.0 && verlet < 5.0, "Verlet error ratio {:.2}", verlet);
+}
+
+#[test]
+fn test_integrator_registry() {
+    let names: Vec<_> = IntegratorKind::ALL.iter().map(|k| k.integrator().name()).collect();
+    assert_eq!(names, vec!["Euler", "Velocity Verlet", "RK4", "Yoshida 4"]);
+
+    assert_eq!(IntegratorConfig::default().integrator, IntegratorKind::Euler);
+    assert!(IntegratorKind::Yoshida4.is_symplectic());
+    assert!(!IntegratorKind::RK4.is_symplectic());
+}
+
+#[test]
+fn test_physics_system_uses_configured_integrator() {
+    let config = IntegratorConfig {
+        integrator: IntegratorKind::Yoshida4,
+        gravity_model: GravityModel::PointMass,
+        atmosphere_model: None,
+        ..Default::default()
+    };
+    let mut world = physics_test_world(config, 30.0);
+    world.resource_mut::<SimulationTime>().accumulator = 30.0;
+
+    let initial = create_test_orbital_state(400.0);
+    let expected = Yoshida4Integrator.integrate(initial.position, initial.velocity, 30.0, &point_mass(&Constants::default()), ForceCoefficients::default());
+    let entity = world.spawn(initial).id();
+
+    world.run_system_once(physics_system).unwrap();
+
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    assert_eq!((state.position, state.velocity), expected);
+    assert!(world.get::<AdaptiveStep>(entity).is_none());
+}
@@ -0,0 +1,277 @@
+// Unit tests for launch traffic
+// Tests schedule parsing, what a launch puts in orbit, orbit raising into operations and the statistical model
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::prelude::*;
+use common::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn launch_world(config: LaunchConfig) -> World {
+    let integrator = IntegratorConfig { integrator: IntegratorKind::RK4, atmosphere_model: None, ..Default::default() };
+    let mut world = physics_test_world(integrator, 10.0);
+    world.insert_resource(config);
+    world.init_resource::<LaunchLog>();
+    world.init_resource::<CatalogNumbers>();
+    world.insert_resource(LifecycleConfig::default());
+    world.init_resource::<LifecycleStatistics>();
+    world
+}
+
+fn scheduled(time: f64, profile: LaunchProfile) -> LaunchConfig {
+    LaunchConfig { schedule: vec![ScheduledLaunch { time, profile }], ..Default::default() }
+}
+
+#[test]
+fn test_parse_launch_schedule() {
+    let json = r#"[
+        {"time": 86400.0, "name": "GEO-1", "payload_mass": 4000.0, "target_altitude": 35786.0,
+         "inclination": 0.0, "disposal": {"Graveyard": {"raise": 300.0}}},
+        {"time": 0.0, "name": "Shell-1", "payloads": 60, "insertion_altitude": 300.0, "disposal": "Abandon"}
+    ]"#;
+    let schedule = parse_launch_schedule(json).unwrap();
+
+    // Sorted by launch time, with unspecified fields taking the profile defaults
+    assert_eq!(schedule.len(), 2);
+    assert_eq!(schedule[0].profile.name, "Shell-1");
+    assert_eq!(schedule[0].profile.payloads, 60);
+    assert_eq!(schedule[0].profile.target_altitude, LaunchProfile::default().target_altitude);
+    assert_eq!(schedule[0].profile.disposal, DisposalStrategy::Abandon);
+    assert_eq!(schedule[1].profile.disposal, DisposalStrategy::geo_graveyard());
+    assert_eq!(schedule[1].profile.payloads, 1);
+
+    assert!(parse_launch_schedule("[{\"name\": \"no time\"}]").is_err());
+    assert!(load_launch_schedule(std::path::Path::new("missing_schedule.json")).is_err());
+
+    let example = load_launch_schedule(std::path::Path::new("assets/launches/example_schedule.json")).unwrap();
+    assert_eq!(example.len(), 4);
+    assert!(example.windows(2).all(|pair| pair[0].time <= pair[1].time));
+}
+
+#[test]
+fn test_launch_places_objects_in_orbit() {
+    let profile = LaunchProfile {
+        name: "Shell".to_string(),
+        payloads: 3,
+        insertion_altitude: 300.0,
+        target_altitude: 550.0,
+        upper_stage_mass: 4000.0,
+        mission_related_objects: 2,
+        ..Default::default()
+    };
+    let mut world = launch_world(LaunchConfig { passivate_upper_stages: true, ..scheduled(0.0, profile) });
+    let launch = world.register_system(launch_system);
+    world.run_system(launch).unwrap();
+    world.run_system(launch).unwrap();
+
+    // Launched once, not again on the next frame
+    let log = world.resource::<LaunchLog>();
+    assert_eq!(log.launches.len(), 1);
+    assert_eq!(log.total_objects(), 6);
+    assert_relative_eq!(log.launches[0].mass, 3000.0 + 4000.0 + 10.0);
+
+    let constants = Constants::default();
+    let mut payloads = world.query::<(&Satellite, &OrbitalState, &Mission, &ManeuverPlan)>();
+    let mut catalog_numbers: Vec<u32> = Vec::new();
+    for (satellite, state, mission, plan) in payloads.iter(&world) {
+        assert!(satellite.active);
+        assert_relative_eq!((state.radius() - constants.earth_radius).0, 300.0, epsilon = 1e-6);
+        assert_eq!(mission.phase, MissionPhase::OrbitRaising);
+        assert_eq!(plan.pending().count(), 2);
+        catalog_numbers.push(satellite.norad_id);
+    }
+    catalog_numbers.sort();
+    assert_eq!(catalog_numbers, vec![GENERATED_CATALOG_START, GENERATED_CATALOG_START + 1, GENERATED_CATALOG_START + 2]);
+
+    let mut stages = world.query::<(&Satellite, &ObjectClass, Option<&Passivated>)>();
+    let stage: Vec<_> = stages.iter(&world).filter(|(_, class, _)| **class == ObjectClass::RocketBody).collect();
+    assert_eq!(stage.len(), 1);
+    assert!(!stage[0].0.active);
+    assert_eq!(stage[0].0.norad_id, GENERATED_CATALOG_START + 3);
+    assert!(stage[0].2.is_some());
+    assert_eq!(world.query::<&Debris>().iter(&world).count(), 2);
+
+    // Released along the orbit, not on top of each other
+    let mut positions = world.query::<&OrbitalState>();
+    let positions: Vec<_> = positions.iter(&world).map(|state| state.position).collect();
+    for (i, a) in positions.iter().enumerate() {
+        for b in &positions[i + 1..] {
+            assert!(a.distance(*b) > 0.5);
+        }
+    }
+}
+
+#[test]
+fn test_launches_and_constellations_share_catalog_numbers() {
+    let profile = LaunchProfile { payloads: 5, upper_stage_mass: 2000.0, mission_related_objects: 3, ..Default::default() };
+    let mut world = launch_world(LaunchConfig {
+        schedule: vec![
+            ScheduledLaunch { time: 0.0, profile: profile.clone() },
+            ScheduledLaunch { time: 0.0, profile },
+        ],
+        ..Default::default()
+    });
+    world.insert_resource(ButtonInput::<KeyCode>::default());
+    world.insert_resource(StressTestConfig {
+        constellation: Some(ConstellationDefinition {
+            name: "Shell".to_string(),
+            shells: vec![WalkerConstellation::delta("Shell", 40, 5, 2, 550.0, 53.0)],
+        }),
+        ..Default::default()
+    });
+    let launch = world.register_system(launch_system);
+    let stress = world.register_system(stress_test_spawn_system);
+    world.run_system(launch).unwrap();
+    world.run_system(stress).unwrap();
+
+    // Payloads and upper stages, then the constellation, numbered from one range without gaps
+    let mut numbers: Vec<u32> = world.query::<&Satellite>().iter(&world).map(|s| s.norad_id).collect();
+    numbers.sort();
+    let expected: Vec<u32> = (GENERATED_CATALOG_START..GENERATED_CATALOG_START + 52).collect();
+    assert_eq!(numbers, expected);
+    assert_eq!(world.resource::<CatalogNumbers>().next(), GENERATED_CATALOG_START + 52);
+}
+
+/// Fly a single launched payload through orbit raising and return its state, mission, box and
+/// propellant left
+fn raise(profile: LaunchProfile, hours: usize) -> (OrbitalState, Mission, Option<StationKeeping>, f64, f64) {
+    let mut world = launch_world(scheduled(0.0, profile));
+    let launch = world.register_system(launch_system);
+    let physics = world.register_system(physics_system);
+    let lifecycle = world.register_system(lifecycle_system);
+    world.run_system(launch).unwrap();
+    for _ in 0..hours * 6 {
+        world.resource_mut::<SimulationTime>().accumulator += 600.0;
+        world.run_system(physics).unwrap();
+        world.run_system(lifecycle).unwrap();
+    }
+    let (state, mission, station_keeping, plan) = world
+        .query::<(&OrbitalState, &Mission, Option<&StationKeeping>, &ManeuverPlan)>()
+        .single(&world)
+        .unwrap();
+    let (position, velocity) = (state.position, state.velocity);
+    let mass = state.mass;
+    let now = world.resource::<SimulationTime>().current;
+    let propellant = plan.propellant;
+    (OrbitalState::new(position, velocity, mass), mission.clone(), station_keeping.cloned(), propellant, now)
+}
+
+#[test]
+fn test_orbit_raising_into_operations() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let profile = LaunchProfile { insertion_altitude: 300.0, target_altitude: 550.0, inclination: 0.0, ..Default::default() };
+
+    // Hohmann transfer: operational within an orbit, on a circular orbit at the target
+    let (state, mission, station_keeping, propellant, now) = raise(profile.clone(), 3);
+    assert!(mission.is_operational());
+    assert!(mission.start > 0.0 && mission.start < 3600.0);
+    assert_relative_eq!(mission.end_of_life(), mission.start + profile.mission_lifetime);
+    let elements = KeplerianElements::from_state(state.position, state.velocity, mu);
+    assert_relative_eq!(elements.semi_major_axis - constants.earth_radius.0, 550.0, epsilon = 2.0);
+    assert!(elements.eccentricity < 5e-3);
+    assert!(now >= 3.0 * 3600.0);
+
+    // Arrived with the standard propellant load left for station-keeping and disposal
+    assert_relative_eq!(propellant, PROPELLANT_FRACTION * state.mass.0, max_relative = 1e-6);
+
+    // Holding the slot it arrived in
+    let Some(StationKeepingBox::Constellation { semi_major_axis, epoch, .. }) = station_keeping.map(|s| s.station) else {
+        panic!("expected a constellation slot");
+    };
+    assert_relative_eq!(semi_major_axis - constants.earth_radius.0, 550.0, epsilon = 2.0);
+    assert_relative_eq!(epoch, mission.start);
+
+    // Low-thrust spiral: slower, but ends on the same orbit
+    let spiral = LaunchProfile { raising_thrust: Some(10.0), isp: 1500.0, ..profile };
+    let (state, mission, _, _, _) = raise(spiral, 6);
+    assert!(mission.is_operational());
+    assert!(mission.start > 3600.0);
+    let elements = KeplerianElements::from_state(state.position, state.velocity, mu);
+    assert_relative_eq!(elements.semi_major_axis - constants.earth_radius.0, 550.0, epsilon = 5.0);
+    assert!(elements.eccentricity < 5e-3);
+}
+
+#[test]
+fn test_geostationary_payload_takes_slot() {
+    let profile = LaunchProfile {
+        insertion_altitude: 500.0,
+        target_altitude: 35786.0,
+        inclination: 0.0,
+        ..Default::default()
+    };
+    let (state, mission, station_keeping, propellant, _) = raise(profile, 8);
+    assert!(mission.is_operational());
+    assert!(matches!(station_keeping.unwrap().station, StationKeepingBox::Geostationary { .. }));
+    assert_relative_eq!(propellant, PROPELLANT_FRACTION * state.mass.0, max_relative = 1e-6);
+
+    // Payloads inserted straight into their orbit take their slot at the first lifecycle check
+    let (_, mission, station_keeping, propellant, _) = raise(LaunchProfile::default(), 1);
+    assert!(mission.is_operational());
+    assert_relative_eq!(mission.start, 600.0);
+    assert_relative_eq!(propellant, PROPELLANT_FRACTION * LaunchProfile::default().payload_mass);
+    assert!(matches!(station_keeping.unwrap().station, StationKeepingBox::Constellation { .. }));
+}
+
+#[test]
+fn test_statistical_launch_model() {
+    let profile = |weight: f64, name: &str| WeightedProfile {
+        weight,
+        profile: LaunchProfile { name: name.to_string(), ..Default::default() },
+    };
+    let model = LaunchModel {
+        launches_per_year: 1000.0,
+        growth_rate: 0.1,
+        profiles: vec![profile(0.8, "A"), profile(0.2, "B")],
+    };
+    assert_relative_eq!(model.rate(10.0 * SECONDS_PER_YEAR), 1.1_f64.powi(10) * model.rate(0.0), max_relative = 1e-12);
+
+    // A year of daily frames at a flat rate
+    let model = LaunchModel { growth_rate: 0.0, ..model };
+    let mut world = launch_world(LaunchConfig { model: Some(model), ..Default::default() });
+    let launch = world.register_system(launch_system);
+    for day in 0..=365 {
+        world.resource_mut::<SimulationTime>().current = day as f64 * 86400.0;
+        world.run_system(launch).unwrap();
+    }
+
+    let log = world.resource::<LaunchLog>();
+    let launches = log.launches.len() as f64;
+    assert!((launches - 1000.0).abs() < 150.0, "{} launches", launches);
+    let share = log.launches.iter().filter(|l| l.name == "A").count() as f64 / launches;
+    assert!((share - 0.8).abs() < 0.05, "share {}", share);
+    assert_eq!(log.total_payloads(), log.launches.len());
+}
+
+#[test]
+fn test_poisson_large_means() {
+    // Means far past where exp(-mean) underflows, as in a multi-year analytical frame
+    let mut rng = StdRng::seed_from_u64(24);
+    for _ in 0..20 {
+        let count = poisson(&mut rng, 10_000.0) as f64;
+        assert!((count - 10_000.0).abs() < 5.0 * 100.0, "{} events", count);
+    }
+    assert_eq!(poisson(&mut rng, 0.0), 0);
+}
+
+#[test]
+fn test_launch_log_yearly_summary() {
+    let record = |time: f64, payloads: u32, upper_stage: bool| LaunchRecord {
+        name: "L".to_string(),
+        time,
+        payloads,
+        upper_stage,
+        mission_related_objects: 0,
+        mass: 0.0,
+    };
+    let log = LaunchLog {
+        launches: vec![record(0.0, 20, true), record(100.0, 1, false), record(2.5 * SECONDS_PER_YEAR, 5, true)],
+    };
+    assert_eq!(log.yearly_summary(), vec![(2, 22), (0, 0), (1, 6)]);
+    assert_eq!(log.total_payloads(), 26);
+}
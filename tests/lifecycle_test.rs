@@ -0,0 +1,232 @@
+// Unit tests for the satellite lifecycle model
+// Tests orbital lifetime estimates, disposal burn sizing, missions of satellites already in service and how the lifecycle system ends missions
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use common::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const BALLISTIC_COEFFICIENT: f64 = 0.01;
+
+fn drag_propagator() -> AnalyticalPropagator {
+    propagator(GravityModel::J2, Some(AtmosphereModel::Exponential))
+}
+
+/// Apply a burn to a state `burn.time` seconds later along its mean orbit
+fn apply(state: &OrbitalState, burn: &Burn, mu: f64) -> KeplerianElements {
+    let propagator = propagator(GravityModel::J2, None);
+    let elements = KeplerianElements::from_state(state.position, state.velocity, mu);
+    let (position, velocity) = propagator.propagate(&elements, burn.time, 0.0).unwrap().to_state(mu);
+    KeplerianElements::from_state(position, velocity + rtn_to_inertial(position, velocity, burn.delta_v), mu)
+}
+
+#[test]
+fn test_orbital_lifetime() {
+    let drag = drag_propagator();
+    let radius = |altitude: f64| Constants::default().earth_radius.0 + altitude;
+    let lifetime = |altitude: f64| drag.orbital_lifetime(radius(altitude), BALLISTIC_COEFFICIENT, 120.0);
+
+    // Weeks to months from 300 km, far beyond 25 years from 900 km
+    assert!(lifetime(300.0) > 86400.0 && lifetime(300.0) < SECONDS_PER_YEAR);
+    assert!(lifetime(900.0) > 25.0 * SECONDS_PER_YEAR);
+    assert!(lifetime(400.0) < lifetime(500.0) && lifetime(500.0) < lifetime(600.0));
+
+    // Twice the ballistic coefficient decays twice as fast
+    let heavy = drag.orbital_lifetime(radius(500.0), 0.5 * BALLISTIC_COEFFICIENT, 120.0);
+    assert_relative_eq!(heavy, 2.0 * lifetime(500.0), max_relative = 1e-9);
+
+    assert_eq!(lifetime(100.0), 0.0);
+    let vacuum = propagator(GravityModel::J2, None);
+    assert_eq!(vacuum.orbital_lifetime(radius(500.0), BALLISTIC_COEFFICIENT, 120.0), f64::INFINITY);
+}
+
+#[test]
+fn test_deorbit_lowers_perigee() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let strategy = DisposalStrategy::Deorbit { perigee_altitude: 200.0 };
+
+    // Circular: one retrograde burn straight away
+    let circular = create_test_orbital_state(800.0);
+    let burns = disposal_burns(&strategy, &circular, 100.0, &constants, HYDRAZINE_ISP);
+    assert_eq!(burns.len(), 1);
+    assert_eq!(burns[0].time, 100.0);
+    assert!(burns[0].delta_v.y < 0.0);
+    let lowered = apply(&circular, &burns[0], mu);
+    assert_relative_eq!(lowered.perigee_radius() - constants.earth_radius.0, 200.0, epsilon = 5.0);
+
+    // Eccentric: the burn waits for apogee, where it costs least
+    let mut eccentric = create_test_orbital_state(600.0);
+    eccentric.velocity *= 1.02;
+    let burns = disposal_burns(&strategy, &eccentric, 0.0, &constants, HYDRAZINE_ISP);
+    let elements = KeplerianElements::from_state(eccentric.position, eccentric.velocity, mu);
+    assert_relative_eq!(burns[0].time, 0.5 * elements.period(mu).unwrap(), max_relative = 0.01);
+    let lowered = apply(&eccentric, &burns[0], mu);
+    assert_relative_eq!(lowered.perigee_radius() - constants.earth_radius.0, 200.0, epsilon = 5.0);
+
+    // Nothing to do when perigee is already low enough
+    assert!(disposal_burns(&strategy, &create_test_orbital_state(180.0), 0.0, &constants, HYDRAZINE_ISP).is_empty());
+}
+
+#[test]
+fn test_graveyard_raise() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let geostationary = create_test_orbital_state(geosynchronous_radius(mu) - constants.earth_radius.0);
+    let burns = disposal_burns(&DisposalStrategy::geo_graveyard(), &geostationary, 0.0, &constants, HYDRAZINE_ISP);
+
+    // Two prograde burns of about 5.5 m/s each, half a transfer orbit apart
+    assert_eq!(burns.len(), 2);
+    assert!(burns.iter().all(|b| b.delta_v.y > 0.0));
+    let total: f64 = burns.iter().map(|b| b.delta_v.y * METERS_PER_KM).sum();
+    assert_relative_eq!(total, 10.9, epsilon = 0.3);
+
+    let transfer = apply(&geostationary, &burns[0], mu);
+    let (position, velocity) = transfer.to_state(mu);
+    let raised = apply(&OrbitalState::new(position, velocity, Kg(2000.0)), &burns[1], mu);
+    assert_relative_eq!(raised.semi_major_axis, geosynchronous_radius(mu) + GEO_GRAVEYARD_RAISE, epsilon = 2.0);
+    assert!(raised.eccentricity < 1e-4);
+
+    assert!(disposal_burns(&DisposalStrategy::Abandon, &geostationary, 0.0, &constants, HYDRAZINE_ISP).is_empty());
+}
+
+#[test]
+fn test_in_service_missions_by_orbit() {
+    let constants = Constants::default();
+    let mut rng = StdRng::seed_from_u64(7);
+    let now = 1000.0;
+
+    let leo = in_service_mission(&create_test_orbital_state(550.0), false, now, &constants, &mut rng);
+    assert_eq!(leo.disposal, DisposalStrategy::Deorbit { perigee_altitude: 200.0 });
+    assert_relative_eq!(leo.lifetime, 5.0 * SECONDS_PER_YEAR);
+    let meo = in_service_mission(&create_test_orbital_state(20200.0), false, now, &constants, &mut rng);
+    assert_eq!(meo.disposal, DisposalStrategy::Graveyard { raise: 500.0 });
+    let geo = in_service_mission(&create_test_orbital_state(35786.0), true, now, &constants, &mut rng);
+    assert_eq!(geo.disposal, DisposalStrategy::geo_graveyard());
+    assert_relative_eq!(geo.lifetime, 15.0 * SECONDS_PER_YEAR);
+
+    // Already operating, each with some of its life left
+    for _ in 0..100 {
+        let mission = in_service_mission(&create_test_orbital_state(550.0), false, now, &constants, &mut rng);
+        assert!(mission.is_operational());
+        assert!(mission.start <= now);
+        assert!(mission.end_of_life() > now && mission.end_of_life() <= now + mission.lifetime);
+    }
+}
+
+fn lifecycle_world(config: LifecycleConfig) -> World {
+    let mut world = simulation_test_world(0.0);
+    world.insert_resource(config);
+    world.init_resource::<LifecycleStatistics>();
+    world
+}
+
+fn spawn_mission(world: &mut World, altitude: f64, mission: Mission) -> Entity {
+    world
+        .spawn((
+            Satellite::new("SAT".to_string(), 1, true),
+            create_test_orbital_state(altitude),
+            PhysicsObject::satellite(Kg(1000.0)),
+            mission,
+        ))
+        .id()
+}
+
+#[test]
+fn test_missions_end_by_outcome() {
+    let config = LifecycleConfig { disposal_success_rate: 1.0, ..Default::default() };
+    let mut world = lifecycle_world(config);
+    let deorbit = DisposalStrategy::Deorbit { perigee_altitude: 200.0 };
+
+    let decaying = spawn_mission(&mut world, 350.0, Mission::new(0.0, 0.0, deorbit));
+    let abandoned = spawn_mission(&mut world, 900.0, Mission::new(0.0, 0.0, DisposalStrategy::Abandon));
+    let disposing = spawn_mission(&mut world, 900.0, Mission::new(0.0, 0.0, deorbit));
+    let keeping = StationKeeping::new(StationKeepingBox::Geostationary {
+        longitude: 0.0,
+        longitude_tolerance: 0.1,
+        max_inclination: 0.1,
+    });
+    world.entity_mut(disposing).insert(keeping);
+    let operating = spawn_mission(&mut world, 900.0, Mission::new(0.0, SECONDS_PER_YEAR, deorbit));
+    let dead = spawn_mission(&mut world, 900.0, Mission::new(0.0, SECONDS_PER_YEAR, deorbit));
+    world.get_mut::<Satellite>(dead).unwrap().active = false;
+
+    world.run_system_once(lifecycle_system).unwrap();
+
+    let outcome = |world: &World, entity: Entity| world.get::<Mission>(entity).unwrap().outcome();
+    assert_eq!(outcome(&world, decaying), Some(MissionOutcome::NaturalDecay));
+    assert!(world.get::<Passivated>(decaying).is_some());
+    assert_eq!(outcome(&world, abandoned), Some(MissionOutcome::Abandoned));
+    assert!(world.get::<Passivated>(abandoned).is_none());
+    assert_eq!(outcome(&world, dead), Some(MissionOutcome::DisposalFailed));
+    assert!(world.get::<Mission>(operating).unwrap().is_operational());
+    assert!(world.get::<Satellite>(operating).unwrap().active);
+
+    // The disposing satellite stops station-keeping and is handed its deorbit burn
+    assert_eq!(world.get::<Mission>(disposing).unwrap().phase, MissionPhase::Disposing);
+    assert!(world.get::<StationKeeping>(disposing).is_none());
+    assert!(world.get::<Satellite>(disposing).unwrap().active);
+    assert_eq!(world.get::<ManeuverPlan>(disposing).unwrap().pending().count(), 1);
+
+    // Once the plan has no burns left the disposal is complete
+    world.get_mut::<ManeuverPlan>(disposing).unwrap().cancel_pending();
+    world.run_system_once(lifecycle_system).unwrap();
+    assert_eq!(outcome(&world, disposing), Some(MissionOutcome::Disposed));
+    assert!(world.get::<Passivated>(disposing).is_some());
+    assert!(!world.get::<Satellite>(disposing).unwrap().active);
+
+    let statistics = world.resource::<LifecycleStatistics>();
+    assert_eq!(statistics.missions_ended(), 4);
+    assert_relative_eq!(statistics.compliance_rate().unwrap(), 0.5);
+    assert!(statistics.disposal_delta_v > 0.0);
+}
+
+#[test]
+fn test_disposal_success_rate() {
+    let deorbit = DisposalStrategy::Deorbit { perigee_altitude: 200.0 };
+    let mut world = lifecycle_world(LifecycleConfig { disposal_success_rate: 0.0, ..Default::default() });
+    let failed = spawn_mission(&mut world, 900.0, Mission::new(0.0, 0.0, deorbit));
+    world.run_system_once(lifecycle_system).unwrap();
+    assert_eq!(world.get::<Mission>(failed).unwrap().outcome(), Some(MissionOutcome::DisposalFailed));
+    assert!(world.get::<Passivated>(failed).is_none());
+    assert!(world.get::<ManeuverPlan>(failed).is_none());
+
+    // Over many satellites the compliance rate follows the success rate
+    let mut world = lifecycle_world(LifecycleConfig { disposal_success_rate: 0.7, ..Default::default() });
+    for _ in 0..2000 {
+        spawn_mission(&mut world, 900.0, Mission::new(0.0, 0.0, DisposalStrategy::geo_graveyard()));
+    }
+    world.run_system_once(lifecycle_system).unwrap();
+    let statistics = world.resource::<LifecycleStatistics>();
+    let succeeded = 2000 - statistics.disposal_failed;
+    assert!((succeeded as f64 / 2000.0 - 0.7).abs() < 0.05, "{} succeeded", succeeded);
+}
+
+#[test]
+fn test_compliance_lifetime_rule() {
+    // Left in place, a satellite complies with a rule allowing more than its residual lifetime
+    // and must dispose under one allowing less
+    let state = create_test_orbital_state(600.0);
+    let physics_object = PhysicsObject::satellite(Kg(1000.0));
+    let propagator = drag_propagator();
+    let semi_major_axis = propagator.mean_semi_major_axis(state.position, state.velocity);
+    let lifetime = propagator.orbital_lifetime(semi_major_axis, physics_object.ballistic_coefficient(state.mass), 120.0);
+    let deorbit = DisposalStrategy::Deorbit { perigee_altitude: 200.0 };
+
+    let outcome = |compliance_lifetime: f64| {
+        let config = LifecycleConfig { compliance_lifetime, disposal_success_rate: 0.0, ..Default::default() };
+        let mut world = lifecycle_world(config);
+        let entity = spawn_mission(&mut world, 600.0, Mission::new(0.0, 0.0, deorbit));
+        world.run_system_once(lifecycle_system).unwrap();
+        world.get::<Mission>(entity).unwrap().outcome()
+    };
+    assert_eq!(outcome(1.1 * lifetime), Some(MissionOutcome::NaturalDecay));
+    assert_eq!(outcome(0.9 * lifetime), Some(MissionOutcome::DisposalFailed));
+}
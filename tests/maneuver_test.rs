@@ -0,0 +1,199 @@
+// Unit tests for maneuver planning and execution
+// Tests the rocket equation, RTN axes, sub-step timing of impulsive burns, finite burns, propellant limits and every backend
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+const DT: f64 = 10.0;
+
+fn maneuver_world(backend: PhysicsBackend, plan: ManeuverPlan) -> (World, Entity) {
+    let config = IntegratorConfig { integrator: IntegratorKind::RK4, atmosphere_model: None, backend, ..Default::default() };
+    let mut world = physics_test_world(config, DT);
+    let entity = world.spawn((create_test_orbital_state(500.0), plan)).id();
+    (world, entity)
+}
+
+/// Copy of an object's state before the physics stage moves it
+struct Snapshot {
+    position: DVec3,
+    velocity: DVec3,
+    mass: Kg,
+}
+
+fn snapshot(world: &World, entity: Entity) -> Snapshot {
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    Snapshot { position: state.position, velocity: state.velocity, mass: state.mass }
+}
+
+/// Run the physics stage for `frames` frames of `seconds` each
+fn run(world: &mut World, frames: usize, seconds: f64) {
+    let system = world.register_system(physics_system);
+    for _ in 0..frames {
+        world.resource_mut::<SimulationTime>().accumulator += seconds;
+        world.run_system(system).unwrap();
+    }
+}
+
+fn rk4_steps(mut state: (DVec3, DVec3), steps: usize) -> (DVec3, DVec3) {
+    let forces = IntegratorConfig { atmosphere_model: None, ..Default::default() }
+        .force_model(&Constants::default(), &Epoch::j2000());
+    for _ in 0..steps {
+        state = RK4Integrator.integrate(state.0, state.1, DT, &forces, ForceCoefficients::default());
+    }
+    state
+}
+
+#[test]
+fn test_rocket_equation() {
+    let burn = Burn::finite(0.0, DVec3::new(0.0, 0.1, 0.0), BurnFrame::Rtn, 300.0, 20.0);
+    let exhaust_velocity = 300.0 * STANDARD_GRAVITY / 1000.0;
+    assert_relative_eq!(burn.exhaust_velocity(), exhaust_velocity);
+
+    let propellant = burn.propellant_mass(Kg(1000.0)).0;
+    assert_relative_eq!(exhaust_velocity * (1000.0 / (1000.0 - propellant)).ln(), 0.1, epsilon = 1e-12);
+    // Mass flow F/ve burns that propellant in the burn duration
+    assert_relative_eq!(burn.duration(Kg(1000.0)), propellant * exhaust_velocity * 1000.0 / 20.0, epsilon = 1e-9);
+    assert_eq!(Burn::impulsive(0.0, burn.delta_v, BurnFrame::Rtn, 300.0).duration(Kg(1000.0)), 0.0);
+}
+
+#[test]
+fn test_rtn_axes() {
+    let state = create_test_orbital_state(500.0);
+    let (radial, transverse, normal) = rtn_basis(state.position, state.velocity);
+    assert_relative_eq!(radial.dot(transverse), 0.0, epsilon = 1e-12);
+    assert_relative_eq!(radial.cross(transverse).dot(normal), 1.0, epsilon = 1e-12);
+    // On a circular orbit the transverse axis is the direction of motion
+    assert!(transverse.angle_between(state.velocity) < 1e-9);
+
+    let vector = DVec3::new(0.3, -1.2, 0.7);
+    let inertial = rtn_to_inertial(state.position, state.velocity, vector);
+    assert!((inertial_to_rtn(state.position, state.velocity, inertial) - vector).length() < 1e-12);
+}
+
+#[test]
+fn test_plan_keeps_burns_in_time_order() {
+    let mut plan = ManeuverPlan::default();
+    for time in [300.0, 100.0, 200.0] {
+        plan.schedule(Burn::impulsive(time, DVec3::Y * 0.001, BurnFrame::Rtn, HYDRAZINE_ISP));
+    }
+    let times: Vec<f64> = plan.pending().map(|b| b.time).collect();
+    assert_eq!(times, vec![100.0, 200.0, 300.0]);
+    assert_eq!(plan.next_event(), Some(100.0));
+
+    plan.cancel_pending();
+    assert!(plan.is_idle());
+    assert_eq!(plan.next_event(), None);
+}
+
+#[test]
+fn test_impulsive_burn_fires_at_next_sub_step() {
+    for backend in [PhysicsBackend::Ecs, PhysicsBackend::Packed] {
+        let delta_v = DVec3::new(0.0, 0.0, 0.05);
+        let mut plan = ManeuverPlan::default();
+        plan.schedule(Burn::impulsive(25.0, delta_v, BurnFrame::Inertial, 300.0));
+        let (mut world, entity) = maneuver_world(backend, plan);
+        let initial = snapshot(&world, entity);
+
+        // A 60 s frame of six sub-steps; the burn at t = 25 s fires on the boundary at 30 s
+        run(&mut world, 1, 60.0);
+        let (position, velocity) = rk4_steps((initial.position, initial.velocity), 3);
+        let expected = rk4_steps((position, velocity + delta_v), 3);
+
+        let state = world.get::<OrbitalState>(entity).unwrap();
+        assert!((state.position - expected.0).length() < 1e-9, "{:?}: position", backend);
+        assert!((state.velocity - expected.1).length() < 1e-12, "{:?}: velocity", backend);
+
+        // The propellant comes out of the object's mass
+        let plan = world.get::<ManeuverPlan>(entity).unwrap();
+        let propellant = Burn::impulsive(25.0, delta_v, BurnFrame::Inertial, 300.0).propellant_mass(initial.mass).0;
+        assert_relative_eq!(state.mass.0, initial.mass.0 - propellant, epsilon = 1e-9);
+        assert_relative_eq!(plan.propellant_used, propellant, epsilon = 1e-9);
+        assert_relative_eq!(plan.delta_v_used, 0.05, epsilon = 1e-12);
+        assert_eq!(plan.burns_completed, 1);
+        assert!(plan.is_idle());
+
+        // Later frames leave the packed copy in step with the ECS
+        run(&mut world, 1, 60.0);
+        let expected = rk4_steps(expected, 6);
+        let state = world.get::<OrbitalState>(entity).unwrap();
+        assert!((state.position - expected.0).length() < 1e-9, "{:?}: coast after burn", backend);
+    }
+}
+
+#[test]
+fn test_finite_burn_spans_frames() {
+    let burn = Burn::finite(0.0, DVec3::new(0.0, 0.02, 0.0), BurnFrame::Rtn, 300.0, 5.0);
+    let mut plan = ManeuverPlan::default();
+    plan.schedule(burn);
+    let (mut world, entity) = maneuver_world(PhysicsBackend::Packed, plan);
+    let initial = snapshot(&world, entity);
+    let duration = burn.duration(initial.mass);
+    assert!(duration > 120.0, "burn should outlast a frame, lasts {} s", duration);
+
+    run(&mut world, 1, 60.0);
+    assert!(world.get::<ManeuverPlan>(entity).unwrap().is_burning());
+
+    run(&mut world, (duration / 60.0).ceil() as usize, 60.0);
+    let plan = world.get::<ManeuverPlan>(entity).unwrap();
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    assert!(plan.is_idle());
+    assert_relative_eq!(plan.delta_v_used, 0.02, epsilon = 1e-12);
+    assert_relative_eq!(state.mass.0, (initial.mass - burn.propellant_mass(initial.mass)).0, epsilon = 1e-9);
+
+    // Along-track thrust raised the orbit
+    let mu = Constants::default().gravitational_parameter.0;
+    let before = KeplerianElements::from_state(initial.position, initial.velocity, mu);
+    let after = KeplerianElements::from_state(state.position, state.velocity, mu);
+    assert!(after.semi_major_axis > before.semi_major_axis + 30.0);
+}
+
+#[test]
+fn test_burn_is_cut_short_when_propellant_runs_out() {
+    let mut plan = ManeuverPlan::new(1.0);
+    plan.schedule(Burn::impulsive(0.0, DVec3::new(0.0, 1.0, 0.0), BurnFrame::Rtn, 300.0));
+    let (mut world, entity) = maneuver_world(PhysicsBackend::Ecs, plan);
+    let initial_mass = world.get::<OrbitalState>(entity).unwrap().mass.0;
+
+    run(&mut world, 1, 10.0);
+    let plan = world.get::<ManeuverPlan>(entity).unwrap();
+    let exhaust_velocity = 300.0 * STANDARD_GRAVITY / 1000.0;
+    assert_eq!(plan.propellant, 0.0);
+    assert_relative_eq!(plan.delta_v_used, exhaust_velocity * (initial_mass / (initial_mass - 1.0)).ln(), epsilon = 1e-12);
+    assert_eq!(plan.burns_completed, 1);
+    assert_relative_eq!(world.get::<OrbitalState>(entity).unwrap().mass.0, initial_mass - 1.0);
+}
+
+#[test]
+fn test_analytical_backend_executes_burns() {
+    let mu = Constants::default().gravitational_parameter.0;
+    let mut plan = ManeuverPlan::default();
+    plan.schedule(Burn::impulsive(600.0, DVec3::new(0.0, 0.1, 0.0), BurnFrame::Rtn, 300.0));
+    let (mut world, entity) = maneuver_world(PhysicsBackend::Analytical, plan);
+    world.resource_mut::<IntegratorConfig>().gravity_model = GravityModel::PointMass;
+    let initial = snapshot(&world, entity);
+
+    // One long frame jumps across the burn
+    run(&mut world, 1, 3600.0);
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    assert!(world.get::<ManeuverPlan>(entity).unwrap().is_idle());
+
+    // Vis-viva: a prograde Δv on a circular orbit gives a = 1 / (2/r - v²/μ)
+    let r = initial.position.length();
+    let v = initial.velocity.length() + 0.1;
+    let expected = 1.0 / (2.0 / r - v * v / mu);
+    let after = KeplerianElements::from_state(state.position, state.velocity, mu);
+    assert_relative_eq!(after.semi_major_axis, expected, max_relative = 1e-9);
+
+    // The next frame re-anchors on the new orbit and keeps it
+    run(&mut world, 1, 3600.0);
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    let later = KeplerianElements::from_state(state.position, state.velocity, mu);
+    assert_relative_eq!(later.semi_major_axis, expected, max_relative = 1e-9);
+}
@@ -0,0 +1,149 @@
+// Unit tests for the packed physics store
+// Tests slot reuse, stale handle rejection and that the packed backend stays correct under spawn/despawn churn
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+fn insert(store: &mut PackedPhysicsStore, world: &mut World, x: f64) -> (Entity, PackedHandle) {
+    let entity = world.spawn_empty().id();
+    (entity, store.insert(entity, DVec3::new(x, 0.0, 0.0), DVec3::ZERO, ForceCoefficients::default(), None))
+}
+
+#[test]
+fn test_swap_remove_keeps_other_handles_valid() {
+    let mut world = World::new();
+    let mut store = PackedPhysicsStore::default();
+    let handles: Vec<_> = (0..5).map(|i| insert(&mut store, &mut world, i as f64)).collect();
+
+    assert!(store.remove(handles[1].1));
+    assert_eq!(store.len(), 4);
+
+    // The last entry was moved into the hole and still resolves to its own data
+    for &(entity, handle) in handles.iter().filter(|(_, h)| *h != handles[1].1) {
+        let i = store.index(handle).expect("Live handle should resolve");
+        assert_eq!(store.entities()[i], entity);
+    }
+    let moved = store.index(handles[4].1).unwrap();
+    assert_eq!(moved, 1);
+    assert_eq!(store.position(moved).x, 4.0);
+}
+
+#[test]
+fn test_stale_handles_are_rejected_after_slot_reuse() {
+    let mut world = World::new();
+    let mut store = PackedPhysicsStore::default();
+    let (_, old) = insert(&mut store, &mut world, 1.0);
+
+    assert!(store.remove(old));
+    assert!(!store.remove(old), "Removing twice should fail");
+
+    let (entity, new) = insert(&mut store, &mut world, 2.0);
+    assert_ne!(old, new, "Reused slot must carry a new generation");
+    assert!(!store.contains(old));
+    assert_eq!(store.entities()[store.index(new).unwrap()], entity);
+
+    store.clear();
+    assert!(store.is_empty());
+    assert!(!store.contains(new));
+}
+
+#[test]
+fn test_sync_drops_unseen_entries() {
+    let mut world = World::new();
+    let mut store = PackedPhysicsStore::default();
+    let handles: Vec<_> = (0..4).map(|i| insert(&mut store, &mut world, i as f64)).collect();
+
+    store.begin_sync();
+    for &(_, handle) in &handles[..2] {
+        let i = store.index(handle).unwrap();
+        store.mark_seen(i);
+    }
+    assert_eq!(store.end_sync(), 2);
+    assert!(store.contains(handles[0].1) && store.contains(handles[1].1));
+    assert!(!store.contains(handles[2].1) && !store.contains(handles[3].1));
+}
+
+fn churn_world(backend: PhysicsBackend) -> World {
+    physics_test_world(IntegratorConfig { integrator: IntegratorKind::RK4, backend, ..Default::default() }, 10.0)
+}
+
+/// Spawn and despawn objects every frame, returning the final state of the survivors in spawn order
+fn run_churn(backend: PhysicsBackend) -> Vec<(DVec3, DVec3)> {
+    let mut world = churn_world(backend);
+    let system = world.register_system(physics_system);
+    let mut alive: Vec<Entity> = Vec::new();
+
+    for frame in 0..20 {
+        for i in 0..10 {
+            let mut state = create_test_orbital_state(300.0 + 50.0 * i as f64);
+            state.velocity.z = 0.01 * frame as f64;
+            alive.push(world.spawn(state).id());
+        }
+        // Despawn a spread of objects, including some spawned this frame
+        for k in (0..alive.len()).rev().step_by(3).take(4) {
+            let entity = alive.remove(k);
+            world.despawn(entity);
+        }
+        // Another system nudging an object must be picked up by the store
+        if let Some(&entity) = alive.first() {
+            world.get_mut::<OrbitalState>(entity).unwrap().velocity.x += 0.001;
+        }
+
+        world.resource_mut::<SimulationTime>().accumulator += 20.0;
+        world.run_system(system).unwrap();
+    }
+
+    alive
+        .iter()
+        .map(|&e| {
+            let state = world.get::<OrbitalState>(e).unwrap();
+            (state.position, state.velocity)
+        })
+        .collect()
+}
+
+#[test]
+fn test_packed_backend_matches_ecs_under_churn() {
+    let ecs = run_churn(PhysicsBackend::Ecs);
+    let packed = run_churn(PhysicsBackend::Packed);
+
+    assert_eq!(ecs.len(), packed.len());
+    assert_eq!(ecs, packed, "Packed backend drifted from the ECS backend under churn");
+}
+
+#[test]
+fn test_despawned_objects_leave_the_store() {
+    let mut world = churn_world(PhysicsBackend::Packed);
+    let system = world.register_system(physics_system);
+    let entities: Vec<_> = (0..6).map(|i| world.spawn(create_test_orbital_state(400.0 + i as f64)).id()).collect();
+
+    world.resource_mut::<SimulationTime>().accumulator = 10.0;
+    world.run_system(system).unwrap();
+    let handle = *world.get::<PackedHandle>(entities[0]).expect("Packed objects carry a handle");
+
+    for &entity in &entities[..3] {
+        world.despawn(entity);
+    }
+    let survivor = world.get::<OrbitalState>(entities[5]).unwrap();
+    let (position, velocity) = (survivor.position, survivor.velocity);
+    world.resource_mut::<SimulationTime>().accumulator = 10.0;
+    world.run_system(system).unwrap();
+
+    // Survivors still advance by exactly one step
+    let forces = IntegratorConfig::default().force_model(&Constants::default(), &Epoch::j2000());
+    let expected = RK4Integrator.integrate(position, velocity, 10.0, &forces, ForceCoefficients::default());
+    let state = world.get::<OrbitalState>(entities[5]).unwrap();
+    assert_eq!((state.position, state.velocity), expected);
+
+    // A new object may reuse the freed slot but the old handle must not resolve to it
+    let newcomer = world.spawn(create_test_orbital_state(500.0)).id();
+    world.resource_mut::<SimulationTime>().accumulator = 10.0;
+    world.run_system(system).unwrap();
+    assert_ne!(*world.get::<PackedHandle>(newcomer).unwrap(), handle);
+}
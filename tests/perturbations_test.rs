@@ -0,0 +1,149 @@
+// Unit tests for lunisolar gravity and solar radiation pressure
+// Tests the Sun/Moon ephemerides against known events, third-body and SRP accelerations, Earth shadow and GEO inclination growth
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::utils::*;
+use approx::assert_relative_eq;
+use bevy::math::DVec3;
+
+fn angle_deg(a: DVec3, b: DVec3) -> f64 {
+    a.angle_between(b).to_degrees()
+}
+
+#[test]
+fn test_sun_position_at_equinox_and_solstice() {
+    // March equinox 2024-03-20 03:06 UTC: the Sun crosses the equator at RA 0 (of date)
+    let equinox = sun_position(&Epoch::from_utc(2024, 3, 20, 3, 6, 0.0));
+    assert!(angle_deg(equinox, DVec3::X) < 0.5, "equinox sun {:?}", equinox);
+    assert_relative_eq!(equinox.length() / Constants::default().astronomical_unit.0, 0.996, epsilon = 0.002);
+
+    // June solstice 2024-06-20 20:51 UTC: greatest northern declination
+    let solstice = sun_position(&Epoch::from_utc(2024, 6, 20, 20, 51, 0.0));
+    let declination = (solstice.z / solstice.length()).asin().to_degrees();
+    assert_relative_eq!(declination, 23.44, epsilon = 0.05);
+}
+
+#[test]
+fn test_moon_position_at_new_and_full_moon() {
+    // Total solar eclipse of 2024-04-08: Sun and Moon line up
+    let eclipse = Epoch::from_utc(2024, 4, 8, 18, 18, 0.0);
+    assert!(angle_deg(moon_position(&eclipse), sun_position(&eclipse)) < 1.0);
+
+    // Full moon 2024-04-23 23:49 UTC: opposite the Sun to within the lunar latitude
+    let full = Epoch::from_utc(2024, 4, 23, 23, 49, 0.0);
+    assert!(angle_deg(moon_position(&full), sun_position(&full)) > 174.0);
+
+    // Distance stays between perigee and apogee over a month
+    for day in 0..30 {
+        let distance = moon_position(&eclipse.plus_seconds(day as f64 * 86400.0)).length();
+        assert!((356000.0..407000.0).contains(&distance), "distance {}", distance);
+    }
+}
+
+#[test]
+fn test_third_body_acceleration_is_tidal() {
+    let constants = Constants::default();
+    let third_body = ThirdBodyGravity::at(&constants, &Epoch::j2000());
+
+    // The Earth's centre feels no differential pull
+    assert_eq!(third_body.acceleration_f64(DVec3::ZERO), DVec3::ZERO);
+
+    // Along the line to the Moon the pull is stretched by about 2μr/d³
+    let r = 42164.0;
+    let moon_direction = third_body.moon.normalize();
+    let moon_only = third_body_acceleration(r * moon_direction, third_body.moon, third_body.moon_mu);
+    let expected = 2.0 * third_body.moon_mu.0 * r / third_body.moon.length().powi(3);
+    assert!(moon_only.dot(moon_direction) > 0.0);
+    assert_relative_eq!(moon_only.length(), expected, max_relative = 0.2);
+
+    // At right angles it squeezes inward with about half that strength
+    let across = moon_direction.any_orthonormal_vector() * r;
+    let squeeze = third_body_acceleration(across, third_body.moon, third_body.moon_mu);
+    assert_relative_eq!(squeeze.dot(across.normalize()), -0.5 * expected, max_relative = 0.05);
+}
+
+#[test]
+fn test_radiation_pressure_and_shadow() {
+    let constants = Constants::default();
+    let epoch = Epoch::from_utc(2024, 3, 20, 3, 6, 0.0);
+    let srp = SolarRadiationPressure::at(&constants, &epoch, 1.3);
+    let sun_direction = srp.sun.normalize();
+
+    // Sunlit side: pushed straight away from the Sun at P·Cr·A/m scaled to the Sun's distance
+    let sunlit = 7000.0 * sun_direction;
+    let acc = srp.acceleration_f64(sunlit, 0.01);
+    let expected = constants.solar_radiation_pressure * 1.3 * 0.01 / 1000.0
+        * (constants.astronomical_unit.0 / (srp.sun - sunlit).length()).powi(2);
+    assert_relative_eq!(acc.length(), expected, max_relative = 1e-12);
+    assert!(angle_deg(acc, -sun_direction) < 1e-6);
+    assert_eq!(srp.acceleration_f64(sunlit, 0.0), DVec3::ZERO);
+
+    // Directly behind the Earth is eclipsed; far enough off the axis is not
+    let behind = -7000.0 * sun_direction;
+    assert!(in_earth_shadow(behind, srp.sun, srp.shadow_radius));
+    assert_eq!(srp.acceleration_f64(behind, 0.01), DVec3::ZERO);
+    let offset = behind + 7000.0 * sun_direction.any_orthonormal_vector();
+    assert!(!in_earth_shadow(offset, srp.sun, srp.shadow_radius));
+    assert!(srp.acceleration_f64(offset, 0.01).length() > 0.0);
+}
+
+#[test]
+fn test_force_coefficients_from_physics_object() {
+    let object = PhysicsObject::new(2.0, 2.2, Meters(1.0));
+    let coefficients = object.force_coefficients(Kg(100.0));
+    assert_relative_eq!(coefficients.ballistic_coefficient, 0.044);
+    assert_relative_eq!(coefficients.area_to_mass, 0.02);
+    assert_eq!(object.force_coefficients(Kg(0.0)), ForceCoefficients::default());
+}
+
+#[test]
+fn test_config_enables_perturbations() {
+    let constants = Constants::default();
+    let epoch = Epoch::j2000();
+
+    let default = IntegratorConfig::default().force_model(&constants, &epoch);
+    assert!(default.third_body.is_none());
+    assert!(default.radiation_pressure.is_none());
+
+    let config = IntegratorConfig {
+        third_body_gravity: true,
+        solar_radiation_pressure: Some(1.5),
+        ..Default::default()
+    };
+    let forces = config.force_model(&constants, &epoch);
+    assert_eq!(forces.third_body.unwrap().sun, sun_position(&epoch));
+    assert_eq!(forces.radiation_pressure.unwrap().reflectivity, 1.5);
+}
+
+#[test]
+fn test_lunisolar_gravity_tilts_geostationary_orbit() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+    let radius = 42164.0;
+    let start = Epoch::from_utc(2024, 1, 1, 0, 0, 0.0);
+
+    let inclination_after = |third_body_gravity: bool| {
+        let config = IntegratorConfig { third_body_gravity, gravity_model: GravityModel::PointMass, ..Default::default() };
+        let mut position = DVec3::new(radius, 0.0, 0.0);
+        let mut velocity = DVec3::new(0.0, (mu / radius).sqrt(), 0.0);
+        let dt = 300.0;
+        let steps_per_day = 288;
+
+        // Thirty days, moving the Sun and Moon once a day
+        for day in 0..30 {
+            let midday = start.plus_seconds((day as f64 + 0.5) * 86400.0);
+            let forces = config.force_model(&constants, &midday);
+            for _ in 0..steps_per_day {
+                (position, velocity) =
+                    RK4Integrator.integrate(position, velocity, dt, &forces, ForceCoefficients::default());
+            }
+        }
+        KeplerianElements::from_state(position, velocity, mu).inclination.to_degrees()
+    };
+
+    // Unperturbed GEO stays equatorial; the Sun and Moon tilt it by roughly 0.8° a year
+    assert!(inclination_after(false) < 1e-9);
+    let tilted = inclination_after(true);
+    assert!(tilted > 0.02 && tilted < 0.2, "inclination {}", tilted);
+}
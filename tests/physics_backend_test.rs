@@ -0,0 +1,99 @@
+// Unit tests for the physics stage backends
+// Tests that the ECS and packed backends agree and that each object is integrated once per step
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+fn physics_world(config: IntegratorConfig) -> (World, Vec<Entity>) {
+    let mut world = physics_test_world(config, 5.0);
+
+    let mut entities = Vec::new();
+    for (i, altitude) in [180.0, 400.0, 800.0, 20200.0, 35786.0].into_iter().enumerate() {
+        let mut state = create_test_orbital_state(altitude);
+        // Tilt and stretch the orbits so every force term is exercised
+        state.velocity = DVec3::new(0.1 * i as f64, state.velocity.y * 1.05, 0.8 * i as f64);
+        let entity = if i % 2 == 0 {
+            world.spawn((state, PhysicsObject::satellite(Kg(500.0)))).id()
+        } else {
+            world.spawn(state).id()
+        };
+        entities.push(entity);
+    }
+    (world, entities)
+}
+
+/// Run the physics stage for a few frames of 12 sub-steps each
+fn run_frames(world: &mut World, entities: &[Entity]) -> Vec<(DVec3, DVec3)> {
+    let system = world.register_system(physics_system);
+    for _ in 0..3 {
+        world.resource_mut::<SimulationTime>().accumulator += 60.0;
+        world.run_system(system).unwrap();
+    }
+    entities
+        .iter()
+        .map(|&e| {
+            let state = world.get::<OrbitalState>(e).unwrap();
+            (state.position, state.velocity)
+        })
+        .collect()
+}
+
+fn assert_backends_agree(config: impl Fn() -> IntegratorConfig) {
+    let (mut ecs_world, ecs_entities) = physics_world(IntegratorConfig { backend: PhysicsBackend::Ecs, ..config() });
+    let (mut packed_world, packed_entities) =
+        physics_world(IntegratorConfig { backend: PhysicsBackend::Packed, ..config() });
+
+    let ecs = run_frames(&mut ecs_world, &ecs_entities);
+    let packed = run_frames(&mut packed_world, &packed_entities);
+
+    for ((ecs_position, ecs_velocity), (packed_position, packed_velocity)) in ecs.iter().zip(packed.iter()) {
+        assert!((*ecs_position - *packed_position).length() < 1e-9, "Positions differ");
+        assert!((*ecs_velocity - *packed_velocity).length() < 1e-12, "Velocities differ");
+    }
+}
+
+#[test]
+fn test_backends_agree_with_fixed_steps() {
+    for integrator in IntegratorKind::ALL {
+        assert_backends_agree(|| IntegratorConfig { integrator, ..Default::default() });
+    }
+}
+
+#[test]
+fn test_backends_agree_with_adaptive_steps() {
+    assert_backends_agree(|| IntegratorConfig {
+        adaptive_timestep: true,
+        max_timestep: 60.0,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn test_each_object_integrated_once_per_step() {
+    for backend in [PhysicsBackend::Ecs, PhysicsBackend::Packed] {
+        let config = IntegratorConfig { integrator: IntegratorKind::RK4, backend, ..Default::default() };
+        let forces = config.force_model(&Constants::default(), &Epoch::j2000());
+        let (mut world, _) = physics_world(config);
+
+        let initial = create_test_orbital_state(400.0);
+        let mut expected = (initial.position, initial.velocity);
+        let entity = world.spawn(initial).id();
+        for _ in 0..12 {
+            expected = RK4Integrator.integrate(expected.0, expected.1, 5.0, &forces, ForceCoefficients::default());
+        }
+
+        world.resource_mut::<SimulationTime>().accumulator = 60.0;
+        let system = world.register_system(physics_system);
+        world.run_system(system).unwrap();
+
+        let state = world.get::<OrbitalState>(entity).unwrap();
+        assert_eq!((state.position, state.velocity), expected, "{:?} backend", backend);
+        assert_eq!(world.resource::<SimulationTime>().current, 60.0);
+    }
+}
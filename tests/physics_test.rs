@@ -1,12 +1,13 @@
 // Unit tests for physics system functionality
-// Tests gravitational force, orbital velocity, energy calculations, Euler integration, and energy conservation
+// Tests gravitational force, orbital velocity, energy calculations, integration, energy conservation and state precision
 
 use kessler_simulator::components::*;
 use kessler_simulator::resources::*;
 use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
 mod common;
 use approx::assert_relative_eq;
-use bevy::prelude::*;
+use bevy::math::DVec3;
 use common::*;
 
 #[test]
@@ -15,9 +16,9 @@ fn test_gravitational_force_calculation() {
     let constants = Constants::default();
 
     // At ISS altitude (~400 km), gravitational acceleration should be approximately 8.7 m/s²
-    let altitude_km = 400.0;
+    let altitude_km = Km(400.0);
     let radius_km = constants.earth_radius + altitude_km;
-    let acceleration = constants.gravity_acceleration(radius_km);
+    let acceleration = constants.gravity_acceleration(radius_km).0;
 
     // g = GM/r², at 400 km: g ≈ 8.7 m/s²
     let expected_acceleration = 8.7; // m/s²
@@ -30,15 +31,15 @@ fn test_circular_velocity() {
     let constants = Constants::default();
 
     // At ISS altitude (~400 km), circular velocity should be approximately 7.66 km/s
-    let altitude_km = 400.0;
-    let velocity = constants.circular_velocity(altitude_km);
+    let altitude_km = Km(400.0);
+    let velocity = constants.circular_velocity(altitude_km).0;
 
     let expected_velocity = 7.66; // km/s
     assert_relative_eq!(velocity, expected_velocity, epsilon = 0.1);
 
     // At GEO altitude (35786 km), circular velocity should be approximately 3.07 km/s
-    let geo_altitude = 35786.0;
-    let geo_velocity = constants.circular_velocity(geo_altitude);
+    let geo_altitude = Km(35786.0);
+    let geo_velocity = constants.circular_velocity(geo_altitude).0;
 
     let expected_geo_velocity = 3.07; // km/s
     assert_relative_eq!(geo_velocity, expected_geo_velocity, epsilon = 0.1);
@@ -50,9 +51,9 @@ fn test_escape_velocity() {
     let constants = Constants::default();
 
     // Escape velocity should be sqrt(2) times circular velocity
-    let altitude_km = 400.0;
-    let circular_vel = constants.circular_velocity(altitude_km);
-    let escape_vel = constants.escape_velocity(constants.earth_radius + altitude_km);
+    let altitude_km = Km(400.0);
+    let circular_vel = constants.circular_velocity(altitude_km).0;
+    let escape_vel = constants.escape_velocity(constants.earth_radius + altitude_km).0;
 
     let expected_escape = circular_vel * 2.0_f64.sqrt();
     assert_relative_eq!(escape_vel, expected_escape, epsilon = 0.1);
@@ -61,11 +62,11 @@ fn test_escape_velocity() {
 #[test]
 fn test_kinetic_energy() {
     // Test kinetic energy calculation
-    let position = Vec3::new(6771.0, 0.0, 0.0); // 400 km altitude
-    let velocity = Vec3::new(0.0, 7.66, 0.0); // 7.66 km/s
+    let position = DVec3::new(6771.0, 0.0, 0.0); // 400 km altitude
+    let velocity = DVec3::new(0.0, 7.66, 0.0); // 7.66 km/s
     let mass = 1000.0; // 1000 kg
 
-    let orbital_state = OrbitalState::new(position, velocity, mass);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(mass));
     let ke = orbital_state.kinetic_energy();
 
     // KE = 0.5 * m * v²
@@ -79,11 +80,11 @@ fn test_kinetic_energy() {
 fn test_potential_energy() {
     // Test potential energy calculation
     let constants = Constants::default();
-    let position = Vec3::new(6771.0, 0.0, 0.0); // 400 km altitude
-    let velocity = Vec3::new(0.0, 7.66, 0.0);
+    let position = DVec3::new(6771.0, 0.0, 0.0); // 400 km altitude
+    let velocity = DVec3::new(0.0, 7.66, 0.0);
     let mass = 1000.0;
 
-    let orbital_state = OrbitalState::new(position, velocity, mass);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(mass));
     let pe = orbital_state.potential_energy(constants.gravitational_parameter);
 
     // PE = -GMm/r
@@ -94,8 +95,8 @@ fn test_potential_energy() {
     );
 
     // At higher altitude, potential energy should be less negative (closer to zero)
-    let higher_position = Vec3::new(10000.0, 0.0, 0.0);
-    let higher_state = OrbitalState::new(higher_position, velocity, mass);
+    let higher_position = DVec3::new(10000.0, 0.0, 0.0);
+    let higher_state = OrbitalState::new(higher_position, velocity, Kg(mass));
     let higher_pe = higher_state.potential_energy(constants.gravitational_parameter);
 
     assert!(
@@ -108,11 +109,11 @@ fn test_potential_energy() {
 fn test_total_energy() {
     // Test total energy calculation
     let constants = Constants::default();
-    let position = Vec3::new(6771.0, 0.0, 0.0); // 400 km altitude
-    let velocity = Vec3::new(0.0, 7.66, 0.0);
+    let position = DVec3::new(6771.0, 0.0, 0.0); // 400 km altitude
+    let velocity = DVec3::new(0.0, 7.66, 0.0);
     let mass = 1000.0;
 
-    let orbital_state = OrbitalState::new(position, velocity, mass);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(mass));
     let total_energy = orbital_state.total_energy(constants.gravitational_parameter);
 
     // For a bound circular orbit, total energy should be negative
@@ -186,10 +187,10 @@ fn test_orbital_period_preservation() {
     let mut state = create_test_orbital_state(400.0);
 
     let initial_position = state.position;
-    let initial_radius = initial_position.length() as f64;
+    let initial_radius = initial_position.length();
 
     // Calculate expected orbital period: T = 2π * sqrt(r³/GM)
-    let mu = constants.gravitational_parameter / 1e9; // Convert to km³/s²
+    let mu = constants.gravitational_parameter.0; // km³/s²
     let expected_period =
         2.0 * std::f64::consts::PI * (initial_radius * initial_radius * initial_radius / mu).sqrt();
 
@@ -203,7 +204,7 @@ fn test_orbital_period_preservation() {
 
     // After one period, position should be approximately back to initial
     // (allowing for numerical errors)
-    let final_radius = state.position.length() as f64;
+    let final_radius = state.position.length();
     assert_relative_eq!(final_radius, initial_radius, epsilon = 100.0);
 }
 
@@ -268,17 +269,17 @@ fn test_physics_system_with_multiple_objects() {
 fn test_zero_velocity_case() {
     // Test edge case: object with zero velocity (should fall)
     let constants = Constants::default();
-    let position = Vec3::new(6771.0, 0.0, 0.0); // 400 km altitude
-    let velocity = Vec3::ZERO; // Zero velocity
-    let mut state = OrbitalState::new(position, velocity, 1000.0);
+    let position = DVec3::new(6771.0, 0.0, 0.0); // 400 km altitude
+    let velocity = DVec3::ZERO; // Zero velocity
+    let mut state = OrbitalState::new(position, velocity, Kg(1000.0));
 
-    let initial_radius = state.position.length() as f64;
+    let initial_radius = state.position.length();
 
     // Run physics step
     run_physics_step(&mut state, &constants, 1.0);
 
     // Object should fall (radius should decrease)
-    let final_radius = state.position.length() as f64;
+    let final_radius = state.position.length();
     assert!(
         final_radius < initial_radius,
         "Object with zero velocity should fall: {:.2} -> {:.2} km",
@@ -314,3 +315,35 @@ fn test_very_high_altitude() {
         speed
     );
 }
+
+#[test]
+fn test_state_resolves_centimetres_at_geo() {
+    // A 1 cm offset at GEO radius is below f32 resolution (~4 m) but survives in f64
+    let mut state = create_test_orbital_state(35786.0);
+    let start = state.position;
+    state.position.x += 1.0e-5;
+
+    assert_relative_eq!((state.position - start).length(), 1.0e-5, max_relative = 1e-6);
+}
+
+#[test]
+fn test_rk4_geo_orbit_closes_after_one_period() {
+    // One point-mass period at GEO should return to the start to well under f32 resolution
+    let constants = Constants::default();
+    let forces = ForceModel::gravity_only(Geopotential::new(&constants, GravityModel::PointMass));
+    let mu = constants.gravitational_parameter.0;
+
+    let state = create_test_orbital_state(35786.0);
+    let radius = state.radius().0;
+    let period = 2.0 * std::f64::consts::PI * (radius.powi(3) / mu).sqrt();
+
+    let steps = 8640;
+    let dt = period / steps as f64;
+    let (mut position, mut velocity) = (state.position, state.velocity);
+    for _ in 0..steps {
+        (position, velocity) = RK4Integrator.integrate(position, velocity, dt, &forces, ForceCoefficients::default());
+    }
+
+    let closure_m = (position - state.position).length() * 1000.0;
+    assert!(closure_m < 1.0, "GEO orbit failed to close: {:.3} m", closure_m);
+}
@@ -0,0 +1,126 @@
+// Property-based tests for breakup conservation laws
+// Tests that random collisions and explosions conserve mass exactly and linear momentum to rounding error
+
+use kessler_simulator::components::*;
+use kessler_simulator::utils::*;
+use bevy::math::DVec3;
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn parent(mass: f64, velocity: DVec3, characteristic_length: f64, class: ObjectClass) -> BreakupParent {
+    BreakupParent {
+        mass,
+        velocity,
+        characteristic_length,
+        class,
+    }
+}
+
+fn class_strategy() -> impl Strategy<Value = ObjectClass> {
+    prop_oneof![
+        Just(ObjectClass::Spacecraft),
+        Just(ObjectClass::RocketBody),
+        Just(ObjectClass::Debris),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_breakup_conserves_mass(
+        seed in any::<u64>(),
+        target_mass in 1.0..5000.0f64,
+        projectile_mass in 0.01..500.0f64,
+        target_length in 0.1..5.0f64,
+        projectile_length in 0.05..2.0f64,
+        relative_speed in 0.1..15.0f64,
+        target_class in class_strategy(),
+        projectile_class in class_strategy(),
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let config = BreakupConfig::default();
+        let target = parent(target_mass, DVec3::new(0.0, 7.5, 0.0), target_length, target_class);
+        let projectile = parent(
+            projectile_mass,
+            DVec3::new(relative_speed, 7.5, 0.0),
+            projectile_length,
+            projectile_class,
+        );
+
+        let result = collision_breakup(&config, &target, &projectile, &mut rng);
+        let total = target_mass + projectile_mass;
+
+        // Every kilogram ends up in a fragment, the unresolved bucket or the remnant
+        prop_assert!(result.unresolved_mass >= 0.0);
+        prop_assert!(result.remnant_mass >= 0.0);
+        prop_assert!(result.fragments.iter().all(|f| f.mass > 0.0));
+        prop_assert!((result.total_mass() - total).abs() <= 1e-9 * total,
+                     "Mass {} vs parents {}", result.total_mass(), total);
+        if result.catastrophic {
+            prop_assert_eq!(result.remnant_mass, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_breakup_conserves_momentum(
+        seed in any::<u64>(),
+        target_mass in 1.0..5000.0f64,
+        projectile_mass in 0.01..500.0f64,
+        vx in -10.0..10.0f64,
+        vy in -10.0..10.0f64,
+        vz in -10.0..10.0f64,
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let config = BreakupConfig::default();
+        let target = parent(target_mass, DVec3::new(7.0, -1.0, 2.0), 2.0, ObjectClass::Spacecraft);
+        let projectile = parent(projectile_mass, DVec3::new(vx, vy, vz), 0.5, ObjectClass::Debris);
+
+        let result = collision_breakup(&config, &target, &projectile, &mut rng);
+
+        let expected = parent_momentum(&[&target, &projectile]);
+        let error = (result.total_momentum() - expected).length();
+
+        // Velocities are stored in f64, so only rounding error remains
+        let scale = (target_mass + projectile_mass) * 20.0;
+        prop_assert!(error <= 1e-9 * scale, "Momentum error {:.3e} kg·km/s", error);
+    }
+
+    #[test]
+    fn test_explosion_conserves_mass_and_momentum(
+        seed in any::<u64>(),
+        mass in 10.0..9000.0f64,
+        characteristic_length in 0.5..10.0f64,
+        scaling_factor in 0.1..1.0f64,
+        class in class_strategy(),
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let config = BreakupConfig::default();
+        let body = parent(mass, DVec3::new(-3.0, 6.5, 1.2), characteristic_length, class);
+
+        let result = explosion_breakup(&config, &body, scaling_factor, &mut rng);
+
+        prop_assert!(result.unresolved_mass >= 0.0);
+        prop_assert!((result.total_mass() - mass).abs() <= 1e-9 * mass);
+        let error = (result.total_momentum() - parent_momentum(&[&body])).length();
+        prop_assert!(error <= 1e-9 * mass * 10.0, "Momentum error {:.3e} kg·km/s", error);
+    }
+
+    #[test]
+    fn test_fragment_cap_moves_mass_to_unresolved(
+        seed in any::<u64>(),
+        max_fragments in 1usize..50,
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let config = BreakupConfig { min_characteristic_length: 0.05, max_fragments };
+        let target = parent(1500.0, DVec3::new(7.5, 0.0, 0.0), 3.0, ObjectClass::Spacecraft);
+        let projectile = parent(300.0, DVec3::new(-7.5, 0.0, 0.0), 1.0, ObjectClass::RocketBody);
+
+        let result = collision_breakup(&config, &target, &projectile, &mut rng);
+
+        prop_assert!(result.fragments.len() <= max_fragments);
+        prop_assert!((result.total_mass() - 1800.0).abs() <= 1e-9 * 1800.0);
+        prop_assert!(result.unresolved_mass > 0.0);
+    }
+}
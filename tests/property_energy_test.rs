@@ -3,7 +3,8 @@
 
 use kessler_simulator::components::*;
 use kessler_simulator::resources::*;
-use bevy::prelude::*;
+use kessler_simulator::utils::Kg;
+use bevy::math::DVec3;
 use proptest::prelude::*;
 use approx::assert_relative_eq;
 
@@ -18,7 +19,7 @@ proptest! {
     ) {
         // Generate random orbital state
         let mut state = create_test_orbital_state(altitude);
-        state.mass = mass;
+        state.mass = Kg(mass);
         
         let constants = Constants::default();
         let initial_energy = state.total_energy(constants.gravitational_parameter);
@@ -45,10 +46,10 @@ proptest! {
         let constants = Constants::default();
         
         let mut state1 = create_test_orbital_state(altitude);
-        state1.mass = mass1;
+        state1.mass = Kg(mass1);
         
         let mut state2 = create_test_orbital_state(altitude);
-        state2.mass = mass2;
+        state2.mass = Kg(mass2);
         
         let energy1 = state1.total_energy(constants.gravitational_parameter);
         let energy2 = state2.total_energy(constants.gravitational_parameter);
@@ -71,13 +72,13 @@ proptest! {
         // Test that potential energy becomes less negative (closer to zero) at higher altitude
         let constants = Constants::default();
         
-        let position1 = Vec3::new((6371.0 + altitude1) as f32, 0.0, 0.0);
-        let velocity1 = Vec3::new(0.0, 7.66, 0.0);
-        let state1 = OrbitalState::new(position1, velocity1, mass);
+        let position1 = DVec3::new(6371.0 + altitude1, 0.0, 0.0);
+        let velocity1 = DVec3::new(0.0, 7.66, 0.0);
+        let state1 = OrbitalState::new(position1, velocity1, Kg(mass));
         
-        let position2 = Vec3::new((6371.0 + altitude2) as f32, 0.0, 0.0);
-        let velocity2 = Vec3::new(0.0, 7.66, 0.0);
-        let state2 = OrbitalState::new(position2, velocity2, mass);
+        let position2 = DVec3::new(6371.0 + altitude2, 0.0, 0.0);
+        let velocity2 = DVec3::new(0.0, 7.66, 0.0);
+        let state2 = OrbitalState::new(position2, velocity2, Kg(mass));
         
         let pe1 = state1.potential_energy(constants.gravitational_parameter);
         let pe2 = state2.potential_energy(constants.gravitational_parameter);
@@ -97,13 +98,13 @@ proptest! {
         
         // Test that kinetic energy increases with velocity magnitude
         let radius = 6371.0 + altitude;
-        let position = Vec3::new(radius as f32, 0.0, 0.0);
+        let position = DVec3::new(radius, 0.0, 0.0);
         
-        let velocity1 = Vec3::new(0.0, speed1 as f32, 0.0);
-        let state1 = OrbitalState::new(position, velocity1, mass);
+        let velocity1 = DVec3::new(0.0, speed1, 0.0);
+        let state1 = OrbitalState::new(position, velocity1, Kg(mass));
         
-        let velocity2 = Vec3::new(0.0, speed2 as f32, 0.0);
-        let state2 = OrbitalState::new(position, velocity2, mass);
+        let velocity2 = DVec3::new(0.0, speed2, 0.0);
+        let state2 = OrbitalState::new(position, velocity2, Kg(mass));
         
         let ke1 = state1.kinetic_energy();
         let ke2 = state2.kinetic_energy();
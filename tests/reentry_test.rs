@@ -0,0 +1,168 @@
+// Unit tests for reentry detection
+// Tests geodetic conversion, interface crossing criteria, the reentry log and the detection system
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+#[test]
+fn test_geodetic_conversion_round_trip() {
+    for &(lat, lon, alt) in &[(0.0, 0.0, 0.0), (45.0, 30.0, 0.5), (-33.9, 151.2, 120.0), (78.2, -15.6, 400.0)] {
+        let site = Geodetic { latitude: lat, longitude: lon, altitude: alt };
+        let geodetic = earth_fixed_to_geodetic(geodetic_to_earth_fixed(&site));
+        assert_relative_eq!(geodetic.latitude, lat, epsilon = 1e-9);
+        assert_relative_eq!(geodetic.longitude, lon, epsilon = 1e-9);
+        assert_relative_eq!(geodetic.altitude, alt, epsilon = 1e-6);
+    }
+
+    // Over the pole altitude is measured from the polar radius
+    let polar_radius = WGS84_EQUATORIAL_RADIUS * (1.0 - WGS84_FLATTENING);
+    let pole = earth_fixed_to_geodetic(DVec3::new(0.0, 0.0, polar_radius + 100.0));
+    assert_relative_eq!(pole.latitude, 90.0, epsilon = 1e-9);
+    assert_relative_eq!(pole.altitude, 100.0, epsilon = 1e-6);
+}
+
+#[test]
+fn test_earth_rotation_moves_ground_track_west() {
+    // A fixed inertial point drifts west as the Earth turns underneath it
+    let position = DVec3::new(7000.0, 0.0, 0.0);
+    let earth_fixed = inertial_to_earth_fixed(position, 30.0_f64.to_radians());
+    let geodetic = earth_fixed_to_geodetic(earth_fixed);
+    assert_relative_eq!(geodetic.longitude, -30.0, epsilon = 1e-9);
+    assert_relative_eq!(earth_fixed.length(), 7000.0, epsilon = 1e-9);
+}
+
+#[test]
+fn test_altitude_criterion() {
+    let constants = Constants::default();
+    let config = ReentryConfig::default();
+    assert_eq!(config.interface_altitude_km, 120.0);
+
+    assert!(!config.has_reentered(&create_test_orbital_state(150.0), &constants));
+    assert!(config.has_reentered(&create_test_orbital_state(110.0), &constants));
+
+    // Surface interface only removes objects inside the Earth
+    let surface = ReentryConfig { interface_altitude_km: 0.0, ..Default::default() };
+    assert!(!surface.has_reentered(&create_test_orbital_state(110.0), &constants));
+    assert!(surface.has_reentered(&create_test_orbital_state(-5.0), &constants));
+}
+
+#[test]
+fn test_perigee_criterion() {
+    let constants = Constants::default();
+    let mu = constants.gravitational_parameter.0;
+
+    // At apogee of a 400 x 100 km orbit
+    let r_apogee = constants.earth_radius.0 + 400.0;
+    let r_perigee = constants.earth_radius.0 + 100.0;
+    let a = 0.5 * (r_apogee + r_perigee);
+    let v_apogee = (mu * (2.0 / r_apogee - 1.0 / a)).sqrt();
+    let state = OrbitalState::new(
+        DVec3::new(r_apogee, 0.0, 0.0),
+        DVec3::new(0.0, v_apogee, 0.0),
+        Kg(500.0),
+    );
+
+    assert_relative_eq!(
+        KeplerianElements::from_state(state.position, state.velocity, mu).perigee_radius(),
+        r_perigee,
+        epsilon = 0.05
+    );
+
+    let altitude = ReentryConfig::default();
+    let perigee = ReentryConfig { criterion: ReentryCriterion::Perigee, ..Default::default() };
+    assert!(!altitude.has_reentered(&state, &constants));
+    assert!(perigee.has_reentered(&state, &constants));
+
+    // Circular orbits above the interface survive under both criteria
+    let circular = create_test_orbital_state(300.0);
+    assert!(!perigee.has_reentered(&circular, &constants));
+}
+
+#[test]
+fn test_reentry_log_yearly_summary() {
+    let mut log = ReentryLog::default();
+
+    for &(time, mass) in &[(1000.0, 10.0), (2.0e6, 20.0), (1.5 * SECONDS_PER_YEAR, 30.0)] {
+        let mut reentering = create_test_orbital_state(100.0);
+        reentering.mass = Kg(mass);
+        let sim_time = SimulationTime { current: time, ..Default::default() };
+        log.record(ReentryEvent::new(Entity::PLACEHOLDER, None, &reentering, &sim_time));
+    }
+
+    assert_eq!(log.total(), 3);
+    assert_relative_eq!(log.total_mass(), 60.0);
+    assert_eq!(log.yearly_summary(), vec![(2, 30.0), (1, 30.0)]);
+    assert_relative_eq!(log.rate_per_year(2.0 * SECONDS_PER_YEAR), 1.5);
+    assert_eq!(log.rate_per_year(0.0), 0.0);
+}
+
+#[test]
+fn test_reentry_event_carries_ground_point() {
+    let state = OrbitalState::new(DVec3::new(0.0, 6450.0, 0.0), DVec3::new(-7.8, 0.0, 0.0), Kg(250.0));
+    let sim_time = SimulationTime::default();
+
+    // The inertial +Y axis lies 90° east of the vernal equinox, which Greenwich trails by GMST
+    let event = ReentryEvent::new(Entity::PLACEHOLDER, Some(25544), &state, &sim_time);
+    let expected = (90.0 - gmst(&sim_time.now()).to_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+    assert_eq!(event.norad_id, Some(25544));
+    assert_eq!(event.time, 0.0);
+    assert_relative_eq!(event.latitude, 0.0, epsilon = 0.01);
+    assert_relative_eq!(event.longitude, expected, epsilon = 0.01);
+    assert_eq!(event.mass, 250.0);
+
+    // Six hours later the Earth has turned a quarter revolution east
+    let later_time = SimulationTime { current: 21600.0, ..Default::default() };
+    let later = ReentryEvent::new(Entity::PLACEHOLDER, None, &state, &later_time);
+    let turned = (event.longitude - later.longitude).rem_euclid(360.0);
+    assert_eq!(later.time, 21600.0);
+    assert_relative_eq!(turned, (EARTH_ROTATION_RATE * 21600.0).to_degrees(), epsilon = 1e-3);
+}
+
+#[test]
+fn test_reentry_detection_system_despawns_and_records() {
+    let mut world = World::new();
+    world.insert_resource(Constants::default());
+    world.insert_resource(SimulationTime { current: 5000.0, ..Default::default() });
+    world.init_resource::<ReentryConfig>();
+    world.init_resource::<ReentryLog>();
+    world.init_resource::<Events<ReentryEvent>>();
+
+    let survivor = world.spawn(create_test_orbital_state(400.0)).id();
+    let debris = world.spawn((create_test_orbital_state(100.0), Debris::new(None, 1, 0.0))).id();
+    let satellite = world
+        .spawn((
+            create_test_orbital_state(-10.0),
+            Satellite::new("DECAYED".to_string(), 12345, false),
+        ))
+        .id();
+    // Already destroyed in a collision this frame: left to the system that removes it
+    let destroyed = world.spawn((create_test_orbital_state(100.0), ScheduledForDeletion)).id();
+
+    world.run_system_once(reentry_detection_system).unwrap();
+
+    assert!(world.get_entity(survivor).is_ok());
+    assert!(world.get_entity(destroyed).is_ok());
+    assert!(world.get_entity(debris).is_err());
+    assert!(world.get_entity(satellite).is_err());
+
+    let log = world.resource::<ReentryLog>();
+    assert_eq!(log.total(), 2);
+    assert!(log.events.iter().all(|e| e.time == 5000.0));
+    assert!(log.events.iter().any(|e| e.entity == satellite && e.norad_id == Some(12345)));
+    assert!(log.events.iter().any(|e| e.entity == debris && e.norad_id.is_none()));
+
+    let events = world.resource::<Events<ReentryEvent>>();
+    assert_eq!(events.len(), 2);
+
+    // Detection is deterministic: running again finds nothing new
+    world.run_system_once(reentry_detection_system).unwrap();
+    assert_eq!(world.resource::<ReentryLog>().total(), 2);
+}
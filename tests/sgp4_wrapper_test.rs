@@ -41,16 +41,16 @@ fn test_tle_to_state_vectors_basic() {
     let (position, velocity) = result.unwrap();
 
     // Position should be reasonable (above Earth surface)
-    let radius = position.length() as f64;
+    let radius = position.length();
     let constants = Constants::default();
     assert!(
-        radius > constants.earth_radius,
+        radius > constants.earth_radius.0,
         "Position radius should be above Earth surface: {:.2} km",
         radius
     );
 
     // Velocity should be positive
-    let speed = velocity.length() as f64;
+    let speed = velocity.length();
     assert!(
         speed > 0.0,
         "Velocity should be positive: {:.2} km/s",
@@ -155,7 +155,7 @@ fn test_known_satellite_iss() {
 
     // ISS altitude is approximately 400 km
     let constants = Constants::default();
-    let altitude = position.length() as f64 - constants.earth_radius;
+    let altitude = position.length() - constants.earth_radius.0;
     assert!(
         altitude > 300.0 && altitude < 500.0,
         "ISS altitude should be around 400 km: {:.2} km",
@@ -163,7 +163,7 @@ fn test_known_satellite_iss() {
     );
 
     // ISS orbital velocity is approximately 7.66 km/s
-    let speed = velocity.length() as f64;
+    let speed = velocity.length();
     assert!(
         speed > 7.0 && speed < 8.0,
         "ISS velocity should be around 7.66 km/s: {:.2} km/s",
@@ -187,7 +187,7 @@ fn test_known_satellite_hubble() {
 
     // Hubble altitude is approximately 540 km
     let constants = Constants::default();
-    let altitude = position.length() as f64 - constants.earth_radius;
+    let altitude = position.length() - constants.earth_radius.0;
     assert!(
         altitude > 500.0 && altitude < 600.0,
         "Hubble altitude should be around 540 km: {:.2} km",
@@ -195,7 +195,7 @@ fn test_known_satellite_hubble() {
     );
 
     // Hubble orbital velocity is approximately 7.5 km/s
-    let speed = velocity.length() as f64;
+    let speed = velocity.length();
     assert!(
         speed > 7.0 && speed < 8.0,
         "Hubble velocity should be around 7.5 km/s: {:.2} km/s",
@@ -231,7 +231,7 @@ fn test_high_eccentricity() {
     // High eccentricity might cause convergence issues, but should still work
     if result.is_ok() {
         let (position, velocity) = result.unwrap();
-        let radius = position.length() as f64;
+        let radius = position.length();
         assert!(radius > 0.0, "Position should be valid");
         assert!(velocity.length() > 0.0, "Velocity should be valid");
     }
@@ -266,7 +266,7 @@ fn test_low_altitude() {
 
     let (position, velocity) = result.unwrap();
     let constants = Constants::default();
-    let altitude = position.length() as f64 - constants.earth_radius;
+    let altitude = position.length() - constants.earth_radius.0;
 
     // Should still be above Earth surface
     assert!(
@@ -341,7 +341,7 @@ fn test_energy_conservation_in_conversion() {
     let constants = Constants::default();
 
     // Create orbital state and check energy
-    let orbital_state = OrbitalState::new(position, velocity, 1000.0);
+    let orbital_state = OrbitalState::new(position, velocity, Kg(1000.0));
     let total_energy = orbital_state.total_energy(constants.gravitational_parameter);
 
     // For a bound orbit, total energy should be negative
@@ -390,3 +390,129 @@ fn test_days_since_epoch() {
         days
     );
 }
+
+/// Published SGP4 verification case (Vallado et al., "Revisiting Spacetrack Report #3", tcppver.out)
+struct VerificationCase {
+    line1: &'static str,
+    line2: &'static str,
+    /// (minutes since epoch, TEME position km, TEME velocity km/s)
+    states: &'static [(f64, [f64; 3], [f64; 3])],
+}
+
+const VALLADO_VERIFICATION_CASES: &[VerificationCase] = &[
+    // TEME example satellite (near Earth)
+    VerificationCase {
+        line1: "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+        line2: "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        states: &[
+            (0.0, [7022.46529266, -1400.08296755, 0.03995155], [1.893841015, 6.405893759, 4.534807250]),
+            (360.0, [-7154.03120202, -3783.17682504, -3536.19412294], [4.741887409, -4.151817765, -2.093935425]),
+            (1440.0, [-938.55923943, -6268.18748831, -4294.02924751], [7.536105209, -0.427127707, 0.989878080]),
+        ],
+    },
+    // Near Earth normal drag case
+    VerificationCase {
+        line1: "1 06251U 62025E   06176.82412014  .00008885  00000-0  12808-3 0  3985",
+        line2: "2 06251  58.0579  54.0425 0030035 139.1568 221.1854 15.56387291  6774",
+        states: &[
+            (0.0, [3988.31022699, 5498.96657235, 0.90055879], [-3.290032738, 2.357652820, 6.496623475]),
+            (360.0, [4993.62642836, 2890.54969900, -3600.40145627], [0.347333429, 5.707031557, 5.070699638]),
+        ],
+    },
+    // Near Earth, low eccentricity (sun-synchronous)
+    VerificationCase {
+        line1: "1 28057U 03049A   06177.78615833  .00000060  00000-0  35940-4 0  1836",
+        line2: "2 28057  98.4283 247.6961 0000884  88.1964 271.9322 14.35478080140550",
+        states: &[
+            (0.0, [-2715.28237486, -6619.26436889, -0.01341443], [-1.008587273, 0.422782003, 7.385272942]),
+            (600.0, [-2506.52558454, -6628.98655094, -988.07784497], [-1.390577189, -0.556164143, 7.312736468]),
+        ],
+    },
+    // Molniya (deep space, 12h resonance)
+    VerificationCase {
+        line1: "1 08195U 75081A   06176.33215444  .00000099  00000-0  11873-3 0   813",
+        line2: "2 08195  64.1586 279.0717 6877146 264.7651  20.2257  2.00491383225656",
+        states: &[
+            (0.0, [2349.89483350, -14785.93811562, 0.02119378], [2.721488096, -3.256811655, 4.498416672]),
+            (360.0, [19089.29762968, 3107.89495018, 39958.14661370], [-0.410308034, 1.640332277, -0.306873818]),
+        ],
+    },
+    // GPS (deep space, near circular 12h)
+    VerificationCase {
+        line1: "1 28129U 03058A   06175.57071136 -.00000104  00000-0  10000-3 0   459",
+        line2: "2 28129  54.7298 324.8098 0048506 266.2640  93.1663  2.00562768 18443",
+        states: &[
+            (0.0, [21707.46412351, -15318.61752390, 0.13551152], [1.304029214, 1.816904974, 3.161919976]),
+            (480.0, [-18453.06134549, -3150.83256134, -18685.83030936], [2.106017925, -2.860236337, -1.586151870]),
+        ],
+    },
+    // Geosynchronous, Lyddane choice, propagated backwards
+    VerificationCase {
+        line1: "1 04632U 70093B   04031.91070959 -.00000084  00000-0  10000-3 0  9955",
+        line2: "2 04632  11.4628 273.1101 1450506 207.6000 143.9350  1.20231981 44145",
+        states: &[
+            (0.0, [2334.11450085, -41920.44035349, -0.03867437], [2.826321032, -0.065091664, 0.570936053]),
+            (-5184.0, [-29020.02587128, 13819.84419063, -5713.33679183], [-1.768068390, -3.235371192, -0.395206135]),
+        ],
+    },
+];
+
+#[test]
+fn test_vallado_verification_vectors() {
+    // Full SGP4/SDP4 must reproduce the published verification ephemerides
+    for case in VALLADO_VERIFICATION_CASES {
+        let tle = TleRecord::from_tle_lines("VERIFICATION", case.line1, case.line2)
+            .expect("Verification TLE should parse");
+        let propagator = Sgp4Propagator::from_tle(&tle).expect("SGP4 should initialize");
+
+        for &(minutes, expected_position, expected_velocity) in case.states {
+            let (position, velocity) = propagator
+                .propagate_minutes(minutes)
+                .expect("SGP4 propagation should succeed");
+
+            for axis in 0..3 {
+                assert!(
+                    (position[axis] - expected_position[axis]).abs() < 1e-3,
+                    "NORAD {} at t={} min: position[{}] = {:.8} km, expected {:.8} km",
+                    tle.norad_id, minutes, axis, position[axis], expected_position[axis]
+                );
+                assert!(
+                    (velocity[axis] - expected_velocity[axis]).abs() < 1e-6,
+                    "NORAD {} at t={} min: velocity[{}] = {:.9} km/s, expected {:.9} km/s",
+                    tle.norad_id, minutes, axis, velocity[axis], expected_velocity[axis]
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_tle_epoch_julian_day() {
+    // 00179.78495062 is 2000-06-27 18:50:19.733571 UTC
+    let tle = TleRecord::from_tle_lines(
+        "VERIFICATION",
+        "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+        "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+    )
+    .unwrap();
+
+    assert_relative_eq!(tle_epoch_julian_day(&tle), 2451723.28495062, epsilon = 1e-8);
+}
+
+#[test]
+fn test_decayed_object_reports_error() {
+    // Sub-orbital verification case: perigee below the surface, lost about 50 minutes after epoch
+    let tle = TleRecord::from_tle_lines(
+        "SUB-ORBITAL",
+        "1 28872U 05037B   05333.02012661  .25992681  00000-0  24476-3 0  1534",
+        "2 28872  96.4736 157.9986 0303955 244.0492 110.6523 16.46015938 10708",
+    )
+    .unwrap();
+    let propagator = Sgp4Propagator::from_tle(&tle).unwrap();
+
+    assert!(propagator.propagate_minutes(0.0).is_ok());
+    assert!(
+        propagator.propagate_minutes(60.0).is_err(),
+        "Propagation past reentry should report an error"
+    );
+}
@@ -0,0 +1,290 @@
+// Unit tests for station-keeping
+// Tests mean semi-major axis recovery, constellation and geostationary box corrections, retirement at the disposal reserve and a drag-fighting run
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use common::*;
+
+fn j2_forces() -> ForceModel {
+    IntegratorConfig { atmosphere_model: None, ..Default::default() }.force_model(&Constants::default(), &Epoch::j2000())
+}
+
+/// A geostationary satellite over `longitude` degrees east, tilted by `inclination` degrees
+fn geostationary(longitude: f64, inclination: f64, epoch: &Epoch) -> OrbitalState {
+    let radius = geosynchronous_radius(Constants::default().gravitational_parameter.0);
+    let (sin_lon, cos_lon) = longitude.to_radians().sin_cos();
+    let (position, velocity) = ecef_to_eci_state(DVec3::new(cos_lon, sin_lon, 0.0) * radius, DVec3::ZERO, epoch);
+    let tilt = bevy::math::DQuat::from_axis_angle(position.normalize(), inclination.to_radians());
+    OrbitalState::new(position, tilt * velocity, Kg(2000.0))
+}
+
+#[test]
+fn test_mean_semi_major_axis_is_steady_under_j2() {
+    let propagator = propagator(GravityModel::J2, None);
+    let mu = propagator.mu;
+    let forces = j2_forces();
+    let state = inclined_orbit(550.0, 53.0);
+    let (mut position, mut velocity) = (state.position, state.velocity);
+
+    let (mut mean_min, mut mean_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut osculating_min, mut osculating_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for _ in 0..600 {
+        (position, velocity) = RK4Integrator.integrate(position, velocity, 10.0, &forces, ForceCoefficients::default());
+        let mean = propagator.mean_semi_major_axis(position, velocity);
+        let osculating = KeplerianElements::from_state(position, velocity, mu).semi_major_axis;
+        (mean_min, mean_max) = (mean_min.min(mean), mean_max.max(mean));
+        (osculating_min, osculating_max) = (osculating_min.min(osculating), osculating_max.max(osculating));
+    }
+
+    // Over one orbit the osculating value swings by kilometres, the mean value by metres
+    assert!(osculating_max - osculating_min > 5.0);
+    assert!(mean_max - mean_min < 0.05, "mean varies by {} km", mean_max - mean_min);
+}
+
+#[test]
+fn test_constellation_slot_tracks_satellite_under_j2() {
+    let constants = Constants::default();
+    let config = StationKeepingConfig::default();
+    let forces = j2_forces();
+    let state = inclined_orbit(550.0, 53.0);
+    let slot = StationKeeping::constellation_slot(&state, &constants, 0.0, 1.0, 10.0);
+
+    // A day of J2 motion keeps the satellite in its slot: nothing to correct
+    let (mut position, mut velocity) = (state.position, state.velocity);
+    for _ in 0..8640 {
+        (position, velocity) = RK4Integrator.integrate(position, velocity, 10.0, &forces, ForceCoefficients::default());
+    }
+    let later = OrbitalState::new(position, velocity, state.mass);
+    let burns = station_keeping_burns(&slot.station, &later, 86400.0, &Epoch::j2000(), &constants, &config);
+    assert!(burns.is_empty(), "{:?}", burns);
+}
+
+#[test]
+fn test_decayed_satellite_is_raised_back() {
+    let constants = Constants::default();
+    let config = StationKeepingConfig::default();
+    let slot = StationKeeping::constellation_slot(&inclined_orbit(550.0, 53.0), &constants, 0.0, 1.0, 10.0);
+
+    // Three km low at the slot position: a prograde burn of about n·Δa/2
+    let low = inclined_orbit(547.0, 53.0);
+    let burns = station_keeping_burns(&slot.station, &low, 0.0, &Epoch::j2000(), &constants, &config);
+    assert_eq!(burns.len(), 1);
+    let burn = burns[0];
+    assert_eq!(burn.frame, BurnFrame::Rtn);
+    let n = (constants.gravitational_parameter.0 / (constants.earth_radius.0 + 550.0).powi(3)).sqrt();
+    assert_relative_eq!(burn.delta_v.y, 0.5 * n * 3.0, max_relative = 0.05);
+    assert_eq!(burn.delta_v.x, 0.0);
+}
+
+#[test]
+fn test_geostationary_east_west_correction() {
+    let constants = Constants::default();
+    let config = StationKeepingConfig::default();
+    let epoch = Epoch::from_utc(2024, 1, 1, 0, 0, 0.0);
+    let station = StationKeeping::geostationary(10.0, 0.05, 0.1).station;
+
+    // Inside the deadband nothing happens
+    let inside = geostationary(10.02, 0.0, &epoch);
+    assert!(station_keeping_burns(&station, &inside, 0.0, &epoch, &constants, &config).is_empty());
+
+    // East of the slot: raise the orbit so it drifts back west at the phasing rate
+    let east = geostationary(10.2, 0.0, &epoch);
+    let burns = station_keeping_burns(&station, &east, 0.0, &epoch, &constants, &config);
+    assert_eq!(burns.len(), 1);
+    let delta_v = burns[0].delta_v.y;
+    assert!(delta_v > 0.0);
+    let propagator = propagator(GravityModel::J2, None);
+    let (position, velocity) = (east.position, east.velocity + rtn_to_inertial(east.position, east.velocity, burns[0].delta_v));
+    let offset = propagator.mean_semi_major_axis(position, velocity) - geosynchronous_radius(propagator.mu);
+    let drift_rate = -1.5 * EARTH_ROTATION_RATE * offset / geosynchronous_radius(propagator.mu);
+    assert_relative_eq!(drift_rate, -0.2_f64.to_radians() / config.phasing_time, max_relative = 0.05);
+}
+
+#[test]
+fn test_geostationary_inclination_correction_at_node() {
+    let constants = Constants::default();
+    let config = StationKeepingConfig::default();
+    let epoch = Epoch::from_utc(2024, 1, 1, 0, 0, 0.0);
+    let station = StationKeeping::geostationary(10.0, 0.05, 0.1).station;
+    let propagator = propagator(GravityModel::J2, None);
+    // Tilted about the node it starts on, then a quarter orbit on towards the descending node
+    let at_node = geostationary(10.0, 0.5, &epoch);
+    let quarter = 0.5 * std::f64::consts::PI / EARTH_ROTATION_RATE;
+    let elements = KeplerianElements::from_state(at_node.position, at_node.velocity, propagator.mu);
+    let (position, velocity) = propagator.propagate(&elements, quarter, 0.0).unwrap().to_state(propagator.mu);
+    let tilted = OrbitalState::new(position, velocity, at_node.mass);
+    let epoch = epoch.plus_seconds(quarter);
+
+    let burns = station_keeping_burns(&station, &tilted, 0.0, &epoch, &constants, &config);
+    assert_eq!(burns.len(), 1);
+    let burn = burns[0];
+    assert_eq!(burn.frame, BurnFrame::Inertial);
+    // Plane change of 2·v·sin(i/2) a quarter orbit away, at the node
+    assert_relative_eq!(burn.delta_v.length(), 2.0 * tilted.speed() * 0.25_f64.to_radians().sin(), max_relative = 0.01);
+    assert_relative_eq!(burn.time, quarter, max_relative = 0.01);
+
+    // Applied at the node it leaves the orbit equatorial
+    let elements = KeplerianElements::from_state(tilted.position, tilted.velocity, propagator.mu);
+    let (position, velocity) = propagator.propagate(&elements, burn.time, 0.0).unwrap().to_state(propagator.mu);
+    let pole = ecef_to_eci(DVec3::Z, &epoch);
+    let inclination = position.cross(velocity + burn.delta_v).angle_between(pole).to_degrees();
+    assert!(inclination < 1e-3, "inclination {}", inclination);
+}
+
+#[test]
+fn test_geostationary_slot_only_for_geostationary_orbits() {
+    let constants = Constants::default();
+    let epoch = Epoch::from_utc(2024, 1, 1, 0, 0, 0.0);
+
+    // Held over the longitude it was found at, keeping an inclination it already has
+    let station = geostationary_slot(&geostationary(-75.2, 0.0, &epoch), &epoch, &constants).unwrap();
+    let StationKeepingBox::Geostationary { longitude, longitude_tolerance, max_inclination } = station.station else {
+        panic!("expected a geostationary box, got {:?}", station.station);
+    };
+    assert_relative_eq!(longitude, -75.2, epsilon = 1e-6);
+    assert_eq!(longitude_tolerance, GEO_LONGITUDE_TOLERANCE);
+    assert_relative_eq!(max_inclination, GEO_MAX_INCLINATION, epsilon = 1e-6);
+    let inclined = geostationary_slot(&geostationary(140.0, 2.0, &epoch), &epoch, &constants).unwrap();
+    assert!(matches!(
+        inclined.station,
+        StationKeepingBox::Geostationary { max_inclination, .. } if (max_inclination - 2.0).abs() < 1e-6
+    ));
+
+    // Inclined geosynchronous, low and supersynchronous orbits hold no geostationary slot
+    assert!(geostationary_slot(&geostationary(140.0, 40.0, &epoch), &epoch, &constants).is_none());
+    assert!(geostationary_slot(&inclined_orbit(550.0, 0.0), &epoch, &constants).is_none());
+    let mut graveyard = geostationary(10.0, 0.0, &epoch);
+    graveyard.position *= 1.01;
+    graveyard.velocity /= 1.01_f64.sqrt();
+    assert!(geostationary_slot(&graveyard, &epoch, &constants).is_none());
+}
+
+fn keeping_world() -> World {
+    let config = IntegratorConfig { integrator: IntegratorKind::RK4, backend: PhysicsBackend::Packed, ..Default::default() };
+    let mut world = physics_test_world(config, 10.0);
+    world.insert_resource(StationKeepingConfig::default());
+    world.init_resource::<StationKeepingStatistics>();
+    world
+}
+
+fn deorbit_mission() -> Mission {
+    Mission::new(0.0, SECONDS_PER_YEAR, DisposalStrategy::Deorbit { perigee_altitude: 200.0 })
+}
+
+#[test]
+fn test_system_plans_burns_and_retires_satellites() {
+    let mut world = keeping_world();
+    let constants = Constants::default();
+    let slot = StationKeeping::constellation_slot(&inclined_orbit(550.0, 53.0), &constants, 0.0, 1.0, 10.0);
+    let satellite = |name: &str| Satellite::new(name.to_string(), 1, true);
+
+    let low = world.spawn((satellite("LOW"), inclined_orbit(547.0, 53.0), deorbit_mission(), slot.clone())).id();
+    // Enough for the raising burn but not for the deorbit after it
+    let short = world
+        .spawn((satellite("SHORT"), inclined_orbit(547.0, 53.0), deorbit_mission(), slot.clone(), ManeuverPlan::new(20.0)))
+        .id();
+    let unmanaged = world.spawn((satellite("UNMANAGED"), inclined_orbit(547.0, 53.0), slot.clone())).id();
+
+    world.run_system_once(station_keeping_system).unwrap();
+
+    // The low satellite is given a plan with its raising burn and a standard propellant load
+    let keeping = world.get::<StationKeeping>(low).unwrap();
+    assert_eq!(keeping.maneuvers, 1);
+    assert!(keeping.delta_v > 0.0);
+    let plan = world.get::<ManeuverPlan>(low).unwrap();
+    assert_eq!(plan.pending().count(), 1);
+    assert_relative_eq!(plan.propellant, PROPELLANT_FRACTION * world.get::<OrbitalState>(low).unwrap().mass.0);
+    assert!(world.get::<Satellite>(low).unwrap().active);
+
+    // The short one stops station-keeping with its reserve untouched and its mission ended
+    assert!(world.get::<StationKeeping>(short).is_none());
+    assert_eq!(world.get::<ManeuverPlan>(short).unwrap().propellant, 20.0);
+    assert!(world.get::<Mission>(short).unwrap().end_of_life() <= 0.0);
+
+    // Without a mission there is nothing to keep
+    assert_eq!(world.get::<StationKeeping>(unmanaged).unwrap().maneuvers, 0);
+    assert!(world.get::<ManeuverPlan>(unmanaged).is_none());
+
+    let statistics = world.resource::<StationKeepingStatistics>();
+    assert_eq!(statistics.maneuvers, 1);
+    assert_eq!(statistics.retired_out_of_propellant, 1);
+
+    // End of life disposes of it with the reserve, under a rule that rules out natural decay
+    world.insert_resource(LifecycleConfig { disposal_success_rate: 1.0, compliance_lifetime: 0.0, ..Default::default() });
+    world.init_resource::<LifecycleStatistics>();
+    world.entity_mut(short).insert(PhysicsObject::satellite(Kg(1000.0)));
+    world.run_system_once(lifecycle_system).unwrap();
+    assert_eq!(world.get::<Mission>(short).unwrap().phase, MissionPhase::Disposing);
+    assert_eq!(world.get::<ManeuverPlan>(short).unwrap().pending().count(), 1);
+}
+
+#[test]
+fn test_station_keeping_holds_box_against_drag() {
+    let mut world = keeping_world();
+    let constants = Constants::default();
+    let state = inclined_orbit(300.0, 53.0);
+    let slot = StationKeeping::constellation_slot(&state, &constants, 0.0, 0.5, 20.0);
+    let station = slot.station;
+    let kept = world
+        .spawn((
+            Satellite::new("KEPT".to_string(), 1, true),
+            state,
+            PhysicsObject::satellite(Kg(500.0)),
+            deorbit_mission(),
+            slot,
+        ))
+        .id();
+    let drifting = world
+        .spawn((inclined_orbit(300.0, 53.0), PhysicsObject::satellite(Kg(500.0))))
+        .id();
+
+    let physics = world.register_system(physics_system);
+    let keeping = world.register_system(station_keeping_system);
+    let propagator = propagator(GravityModel::J2, None);
+    let StationKeepingBox::Constellation { semi_major_axis: reference, .. } = station else {
+        unreachable!()
+    };
+    let mean_axis = |world: &World, entity: Entity| {
+        let state = world.get::<OrbitalState>(entity).unwrap();
+        propagator.mean_semi_major_axis(state.position, state.velocity)
+    };
+
+    // Five days of 10-minute frames
+    for _ in 0..720 {
+        world.resource_mut::<SimulationTime>().accumulator += 600.0;
+        world.run_system(physics).unwrap();
+        world.run_system(keeping).unwrap();
+        let altitude_error = mean_axis(&world, kept) - reference;
+        assert!(altitude_error.abs() < 1.0, "left the box by {} km", altitude_error);
+    }
+
+    // Drag pulled the uncontrolled twin well below the box; keeping it up cost propellant
+    assert!(reference - mean_axis(&world, drifting) > 1.0);
+    let keeping = world.get::<StationKeeping>(kept).unwrap();
+    assert!(keeping.maneuvers > 0);
+    assert_relative_eq!(world.get::<ManeuverPlan>(kept).unwrap().delta_v_used, keeping.delta_v, max_relative = 1e-9);
+    assert!(within_along_track_box(&station, &world, kept, &constants));
+}
+
+/// Whether the satellite ends the run inside its along-track deadband
+fn within_along_track_box(station: &StationKeepingBox, world: &World, entity: Entity, constants: &Constants) -> bool {
+    let StationKeepingBox::Constellation { latitude_argument, latitude_rate, semi_major_axis, along_track_tolerance, .. } =
+        *station
+    else {
+        return false;
+    };
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    let now = world.resource::<SimulationTime>().current;
+    let elements = KeplerianElements::from_state(state.position, state.velocity, constants.gravitational_parameter.0);
+    let error = (elements.argument_of_latitude() - latitude_argument - latitude_rate * now + std::f64::consts::PI)
+        .rem_euclid(std::f64::consts::TAU)
+        - std::f64::consts::PI;
+    (semi_major_axis * error).abs() < 2.0 * along_track_tolerance
+}
@@ -0,0 +1,147 @@
+// Unit tests for the epoch-anchored simulation clock
+// Tests calendar/Julian date conversions, leap seconds, ISO-8601 round trips and TLE propagation relative to the epoch
+
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use common::*;
+
+#[test]
+fn test_j2000_reference() {
+    let j2000 = Epoch::j2000();
+    assert_eq!(j2000.utc_julian_day(), 2451545.0);
+    assert_eq!(j2000.mjd(), 51544.5);
+    assert_eq!(j2000.to_iso8601(), "2000-01-01T12:00:00.000Z");
+    assert_eq!(j2000.tai_minus_utc(), 32.0);
+}
+
+#[test]
+fn test_calendar_round_trip() {
+    assert_eq!(calendar_to_mjd(1858, 11, 17), 0);
+    assert_eq!(calendar_to_mjd(2000, 1, 1), 51544);
+    assert_eq!(calendar_to_mjd(2024, 2, 29), 60369);
+    for mjd in [-1000, 0, 41317, 51544, 60369, 88000] {
+        let (year, month, day) = mjd_to_calendar(mjd);
+        assert_eq!(calendar_to_mjd(year, month, day), mjd);
+    }
+}
+
+#[test]
+fn test_leap_second_table() {
+    assert_eq!(tai_minus_utc(calendar_to_mjd(1972, 1, 1)), 10.0);
+    assert_eq!(tai_minus_utc(calendar_to_mjd(1999, 1, 1)), 32.0);
+    assert_eq!(tai_minus_utc(calendar_to_mjd(2016, 12, 31)), 36.0);
+    assert_eq!(tai_minus_utc(calendar_to_mjd(2017, 1, 1)), 37.0);
+    assert_eq!(tai_minus_utc(calendar_to_mjd(2030, 6, 1)), 37.0);
+}
+
+#[test]
+fn test_elapsed_time_counts_leap_seconds() {
+    let before = Epoch::parse_iso8601("2016-12-31T23:59:59Z").unwrap();
+    let after = Epoch::parse_iso8601("2017-01-01T00:00:00Z").unwrap();
+    assert_eq!(after.seconds_since(&before), 2.0);
+
+    // The inserted second is displayed as :60
+    let leap = before.plus_seconds(1.5);
+    assert_eq!(leap.to_iso8601(), "2016-12-31T23:59:60.500Z");
+    assert_eq!(Epoch::parse_iso8601("2016-12-31T23:59:60.5Z").unwrap(), leap);
+    assert_eq!(leap.utc_julian_day(), Epoch::from_utc(2017, 1, 1, 0, 0, 0.0).utc_julian_day());
+
+    // Across a year without a leap second, a day is exactly 86400 s
+    let start = Epoch::from_utc(2023, 6, 30, 0, 0, 0.0);
+    assert_eq!(Epoch::from_utc(2023, 7, 1, 0, 0, 0.0).seconds_since(&start), 86400.0);
+}
+
+#[test]
+fn test_leap_second_only_parses_at_end_of_leap_day() {
+    assert!(Epoch::parse_iso8601("2016-12-31T23:59:60Z").is_ok());
+    for bad in [
+        "2024-03-01T12:30:60Z",
+        "2016-12-31T23:58:60Z",
+        "2016-12-31T22:59:60Z",
+        "2016-12-30T23:59:60Z",
+        "2017-01-01T00:00:60Z",
+        "2023-12-31T23:59:60Z",
+    ] {
+        assert!(Epoch::parse_iso8601(bad).is_err(), "{:?} should not parse", bad);
+    }
+}
+
+#[test]
+fn test_iso8601_round_trip() {
+    for text in ["2024-03-01T12:30:05.250Z", "1999-12-31T23:59:59.999Z", "2023-07-19T02:57:46.000Z"] {
+        assert_eq!(Epoch::parse_iso8601(text).unwrap().to_iso8601(), text);
+    }
+    assert_eq!(Epoch::parse_iso8601("2024-03-01").unwrap().to_iso8601(), "2024-03-01T00:00:00.000Z");
+    assert_eq!(Epoch::parse_iso8601("2024-03-01 06:15").unwrap().to_iso8601(), "2024-03-01T06:15:00.000Z");
+
+    assert!(Epoch::parse_iso8601("2024-02-29").is_ok());
+    for bad in ["", "2024-13-01", "2024-03-01T25:00:00Z", "yesterday", "2024-03-01T12:00:00:00"] {
+        assert!(Epoch::parse_iso8601(bad).is_err(), "{:?} should not parse", bad);
+    }
+    // Days past the end of the month, including the leap day of a common year
+    for bad in ["2024-02-31", "2023-02-29", "2024-04-31", "2024-04-00"] {
+        assert!(Epoch::parse_iso8601(bad).is_err(), "{:?} should not parse", bad);
+    }
+
+    let epoch = Epoch::parse_iso8601("2024-03-01T12:00:00Z").unwrap();
+    assert_relative_eq!(Epoch::from_utc_julian_day(epoch.utc_julian_day()).tai_seconds, epoch.tai_seconds, epsilon = 1e-4);
+}
+
+#[test]
+fn test_simulation_clock_follows_epoch() {
+    let epoch = Epoch::parse_iso8601("2024-03-01T00:00:00Z").unwrap();
+    let mut sim_time = SimulationTime::with_epoch(epoch);
+    assert!(!sim_time.epoch_from_catalog);
+
+    sim_time.current = 90.0 * 60.0;
+    assert_eq!(sim_time.iso8601(), "2024-03-01T01:30:00.000Z");
+    assert_relative_eq!(sim_time.mjd(), calendar_to_mjd(2024, 3, 1) as f64 + 1.5 / 24.0, epsilon = 1e-9);
+    assert_relative_eq!(sim_time.julian_day(), sim_time.mjd() + MJD_OFFSET, epsilon = 1e-9);
+
+    // Re-anchoring keeps the simulation time and moves the epoch
+    sim_time.anchor_to(Epoch::parse_iso8601("2025-01-01T00:00:00Z").unwrap());
+    assert_eq!(sim_time.current, 90.0 * 60.0);
+    assert_eq!(sim_time.iso8601(), "2025-01-01T00:00:00.000Z");
+}
+
+#[test]
+fn test_catalog_sets_default_epoch() {
+    let mut older = create_iss_tle();
+    older.epoch_day = 150.5;
+    let newer = create_hubble_tle();
+    let records = vec![older, newer.clone()];
+    assert_eq!(newest_tle_epoch(&records), Some(tle_epoch(&newer)));
+
+    let mut sim_time = SimulationTime::default();
+    anchor_epoch_to_catalog(&mut sim_time, &records);
+    assert_eq!(sim_time.now(), tle_epoch(&newer));
+    assert_eq!(sim_time.iso8601(), "2023-07-19T00:00:00.000Z");
+
+    // An explicitly configured epoch is kept
+    let fixed = Epoch::parse_iso8601("2020-01-01").unwrap();
+    let mut configured = SimulationTime::with_epoch(fixed);
+    anchor_epoch_to_catalog(&mut configured, &records);
+    assert_eq!(configured.epoch, fixed);
+}
+
+#[test]
+fn test_tle_propagation_is_relative_to_simulation_epoch() {
+    let tle = create_iss_tle();
+    let propagator = Sgp4Propagator::from_tle(&tle).unwrap();
+    let epoch = tle_epoch(&tle);
+
+    // At the TLE epoch the state is the SGP4 epoch state, rotated out of TEME
+    let (_, state) = create_satellite_from_tle(&tle, &epoch).unwrap();
+    let (position, velocity) = propagator.propagate_minutes(0.0).unwrap();
+    let (position, velocity) = teme_to_gcrf(position, velocity, &epoch);
+    assert!((state.position - position).length() < 1e-6);
+    assert!((state.velocity - velocity).length() < 1e-9);
+
+    // Half a day later matches propagating 720 minutes from the TLE epoch
+    let (position, _) = propagate_to_epoch(&tle, &epoch.plus_seconds(43200.0)).unwrap();
+    let (expected, _) = propagator.propagate_minutes(720.0).unwrap();
+    assert!((position - expected).length() < 1e-3);
+}
@@ -0,0 +1,91 @@
+// Unit tests for the fixed-timestep accumulator
+// Tests sub-step draining, pausing, the per-frame budget and clock/orbit agreement at high speed
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::*;
+use kessler_simulator::utils::*;
+mod common;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use common::*;
+use std::time::Duration;
+
+#[test]
+fn test_accumulator_drains_whole_steps() {
+    let mut sim_time = SimulationTime { speed_multiplier: 64.0, timestep: 2.0, ..Default::default() };
+
+    sim_time.advance(0.078125); // 5 simulated seconds
+    assert_eq!(sim_time.consume_steps(), 2);
+    assert_eq!(sim_time.current, 4.0);
+    assert_relative_eq!(sim_time.accumulator, 1.0, epsilon = 1e-12);
+    assert_eq!(sim_time.frame_duration(), 4.0);
+
+    // The remainder carries into the next frame
+    sim_time.advance(0.015625);
+    assert_eq!(sim_time.consume_steps(), 1);
+    assert_eq!(sim_time.current, 6.0);
+    assert!(!sim_time.behind);
+}
+
+#[test]
+fn test_paused_simulation_takes_no_steps() {
+    let mut sim_time = SimulationTime::default();
+    sim_time.pause();
+    sim_time.advance(1.0);
+
+    assert_eq!(sim_time.consume_steps(), 0);
+    assert_eq!(sim_time.current, 0.0);
+    assert_eq!(sim_time.accumulator, 0.0);
+}
+
+#[test]
+fn test_budget_drops_backlog_and_reports_behind() {
+    let mut sim_time = SimulationTime { speed_multiplier: 86400.0, max_substeps: 100, ..Default::default() };
+
+    sim_time.advance(0.5); // 43200 s due, 100 s allowed
+    assert_eq!(sim_time.consume_steps(), 100);
+    assert_eq!(sim_time.current, 100.0);
+    assert!(sim_time.behind);
+    assert_relative_eq!(sim_time.dropped_time, 43100.0, epsilon = 1e-6);
+    assert!(sim_time.accumulator < sim_time.timestep);
+
+    // Catching up clears the flag
+    sim_time.set_speed(1.0);
+    sim_time.advance(1.0);
+    sim_time.consume_steps();
+    assert!(!sim_time.behind);
+}
+
+#[test]
+fn test_clock_and_orbit_agree_at_high_speed() {
+    let constants = Constants::default();
+    let config = IntegratorConfig {
+        integrator: IntegratorKind::RK4,
+        gravity_model: GravityModel::PointMass,
+        atmosphere_model: None,
+        ..Default::default()
+    };
+    let mut world = physics_test_world(config, 10.0);
+    world.resource_mut::<Time>().advance_by(Duration::from_millis(100));
+    world.resource_mut::<SimulationTime>().speed_multiplier = 86400.0;
+
+    let entity = world.spawn(create_test_orbital_state(400.0)).id();
+    world.run_system_once(physics_system).unwrap();
+
+    // One 100 ms frame at 86400x is 8640 simulated seconds
+    let sim_time = world.resource::<SimulationTime>();
+    assert_relative_eq!(sim_time.current, 8640.0, epsilon = 1e-6);
+    assert_eq!(sim_time.frame_steps, 864);
+    assert!(!sim_time.behind);
+
+    // The object sweeps the angle a circular orbit covers in that time
+    let radius = constants.earth_radius.0 + 400.0;
+    let mean_motion = (constants.gravitational_parameter.0 / radius.powi(3)).sqrt();
+    let expected = (mean_motion * 8640.0).rem_euclid(std::f64::consts::TAU);
+
+    let state = world.get::<OrbitalState>(entity).unwrap();
+    let angle = state.position.y.atan2(state.position.x).rem_euclid(std::f64::consts::TAU);
+    assert_relative_eq!(angle, expected, epsilon = 1e-6);
+}
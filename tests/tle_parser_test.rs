@@ -41,6 +41,20 @@ fn test_parse_exponential_notation() {
     assert!(tle.mean_motion_ddot.abs() > 0.0, "Exponential notation should be parsed");
 }
 
+#[test]
+fn test_parse_bstar_assumed_decimal_point() {
+    // BSTAR uses an assumed leading decimal point: "28098-4" is 0.28098e-4, "-13525-3" is -0.13525e-3
+    let line1 = "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753";
+    let line2 = "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667";
+    let tle = TleRecord::from_tle_lines("VANGUARD 1", line1, line2).unwrap();
+    assert!((tle.bstar - 0.28098e-4).abs() < 1e-12, "bstar = {}", tle.bstar);
+
+    let line1 = "1 21897U 92011A   06176.02341244 -.00001273  00000-0 -13525-3 0  3044";
+    let line2 = "2 21897  62.1749 198.0096 7421690 253.0462  20.1561  2.01269994104880";
+    let tle = TleRecord::from_tle_lines("MOLNIYA", line1, line2).unwrap();
+    assert!((tle.bstar + 0.13525e-3).abs() < 1e-12, "bstar = {}", tle.bstar);
+}
+
 #[test]
 fn test_parse_decimal_fraction() {
     // Test parsing decimal fraction for eccentricity
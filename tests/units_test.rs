@@ -0,0 +1,86 @@
+// Unit tests for unit-safe quantities
+// Tests conversions, arithmetic and the collision threshold that compares metre radii to km positions
+
+use kessler_simulator::components::*;
+use kessler_simulator::resources::*;
+use kessler_simulator::systems::collision::*;
+use kessler_simulator::utils::*;
+use approx::assert_relative_eq;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+#[test]
+fn test_length_and_speed_conversions() {
+    assert_eq!(Km::from(Meters(1500.0)), Km(1.5));
+    assert_eq!(Meters::from(Km(0.25)), Meters(250.0));
+    assert_eq!(KmPerSec::from(MetersPerSec(7660.0)), KmPerSec(7.66));
+    assert_eq!(MetersPerSec::from(KmPerSec(0.1)), MetersPerSec(100.0));
+    assert_eq!(Km::from(Meters::from(Km(6771.0))), Km(6771.0));
+}
+
+#[test]
+fn test_quantity_arithmetic() {
+    assert_eq!(Km(1.0) + Km(2.5), Km(3.5));
+    assert_eq!(Meters(3.0) - Meters(1.0), Meters(2.0));
+    assert_eq!(Kg(10.0) * 2.0, Kg(20.0));
+    assert_eq!(0.5 * Kg(10.0), Kg(5.0));
+    assert_eq!(Km(9.0) / 3.0, Km(3.0));
+    assert_eq!(Km(9.0) / Km(3.0), 3.0);
+    assert!(Km(0.5) < Km(0.6));
+    assert_eq!(format!("{}", KmPerSec(7.5)), "7.5 km/s");
+
+    let mut total = Kg(1.0);
+    total += Kg(2.0);
+    total -= Kg(0.5);
+    assert_eq!(total, Kg(2.5));
+
+    assert_eq!(Km::between(DVec3::new(3.0, 0.0, 0.0), DVec3::new(0.0, 4.0, 0.0)), Km(5.0));
+}
+
+#[test]
+fn test_orbital_state_radius_and_energy_units() {
+    let constants = Constants::default();
+    let state = OrbitalState::new(DVec3::new(7000.0, 0.0, 0.0), DVec3::new(0.0, 7.5, 0.0), Kg(100.0));
+
+    assert_eq!(state.radius(), Km(7000.0));
+    assert_relative_eq!(state.kinetic_energy(), 0.5 * 100.0 * 7500.0 * 7500.0, max_relative = 1e-6);
+    assert_relative_eq!(
+        state.potential_energy(constants.gravitational_parameter),
+        -constants.gravitational_parameter.0 * METERS_PER_KM.powi(3) * 100.0 / 7.0e6,
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(constants.gravitational_parameter.0, 398600.4418, max_relative = 1e-12);
+}
+
+fn detect_collisions(separation_km: f64, radius: Meters) -> usize {
+    let mut world = World::new();
+    world.insert_resource(SimulationTime::default());
+    world.init_resource::<CollisionPairs>();
+
+    let position = DVec3::new(6771.0, 0.0, 0.0);
+    let mut octree = SpatialOctree::default();
+    for offset in [0.0, separation_km] {
+        let at = position + DVec3::new(0.0, offset, 0.0);
+        let entity = world
+            .spawn((
+                OrbitalState::new(at, DVec3::new(0.0, 7.67, 0.0), Kg(500.0)),
+                PhysicsObject::new(1.0, 2.2, radius),
+            ))
+            .id();
+        octree.root.insert(entity, at.as_vec3());
+    }
+    world.insert_resource(octree);
+
+    world.run_system_once(collision_detection_system).unwrap();
+    world.resource::<CollisionPairs>().pairs.len()
+}
+
+#[test]
+fn test_collision_threshold_uses_metre_radii() {
+    // Two 1 m objects half a kilometre apart are nowhere near touching
+    assert_eq!(detect_collisions(0.5, Meters(1.0)), 0);
+
+    // 1.5 m apart they overlap
+    assert_eq!(detect_collisions(0.0015, Meters(1.0)), 1);
+}